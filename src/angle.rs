@@ -1,53 +1,101 @@
-use std::f64::consts::PI;
+use alloc::vec::Vec;
+use core::f64::consts::PI;
+
+#[cfg(not(any(feature = "std", test)))]
+use crate::math::Float;
 
 /// An angle in degrees
-#[derive(Debug, PartialOrd, PartialEq, Clone, Copy)]
+/// angles compare with `f64::total_cmp` so they can be sorted and used as keys even when NaN
+#[derive(Debug, Clone, Copy, Default)]
 pub struct AngleDegrees {
     angle: f64
 }
 
 /// An angle in radians, f64 is assumed to be in radians
-#[derive(Debug, PartialOrd, PartialEq, Clone, Copy)]
+/// angles compare with `f64::total_cmp` so they can be sorted and used as keys even when NaN
+#[derive(Debug, Clone, Copy, Default)]
 pub struct AngleRadians {
     angle: f64
 }
 
 impl AngleRadians {
     /// Create a new angle in radians
-    pub fn new(angle: f64) -> Self {
+    pub const fn new(angle: f64) -> Self {
         Self { angle }
     }
 
     /// Get 2pi
-    pub fn two_pi() -> Self {
+    pub const fn two_pi() -> Self {
         Self::new(2.0 * PI)
     }
 
     /// Get pi
-    pub fn pi() -> Self {
+    pub const fn pi() -> Self {
         Self::new(PI)
     }
 
     /// Get pi/2
-    pub fn half_pi() -> Self {
+    pub const fn half_pi() -> Self {
         Self::new(PI / 2.0)
     }
 
     /// Get pi/4
-    pub fn quarter_pi() -> Self {
+    pub const fn quarter_pi() -> Self {
         Self::new(PI / 4.0)
     }
 
     /// Get pi/3
-    pub fn third_pi() -> Self {
+    pub const fn third_pi() -> Self {
         Self::new(PI / 3.0)
     }
 
     /// Get pi/6
-    pub fn sixth_pi() -> Self {
+    pub const fn sixth_pi() -> Self {
         Self::new(PI / 6.0)
     }
 
+    /// Calculate the euclidean remainder of the angle divided by a modulus
+    /// unlike `%` the result is never negative, it is in the range 0 to |modulus|
+    pub fn rem_euclid(&self, modulus: AngleRadians) -> AngleRadians {
+        Self::new(self.angle.rem_euclid(modulus.angle))
+    }
+
+    /// Wrap the angle into the range 0 to 2pi
+    /// negative angles wrap around to the positive equivalent
+    pub fn wrap(&self) -> AngleRadians {
+        Self::new(wrap(self.angle, 2.0 * PI))
+    }
+
+    /// Calculate the circular mean of a set of angles, in the range -pi to pi
+    /// this is the direction of the sum of the unit vectors at each angle so it averages correctly across the wrap
+    /// Returns None for an empty set
+    ///
+    /// the mean is ambiguous when the angles cancel out, e.g. for two opposite angles,
+    /// if the mean resultant length is below `CIRCULAR_TOLERANCE` the mean is 0
+    pub fn circular_mean(angles: &[AngleRadians]) -> Option<AngleRadians> {
+        let (sin, cos) = mean_resultant(angles.iter().map(|angle| angle.angle))?;
+        if sin.hypot(cos) < CIRCULAR_TOLERANCE {
+            return Some(Self::new(0.0));
+        }
+        Some(Self::new(sin.atan2(cos)))
+    }
+
+    /// Calculate the circular variance of a set of angles
+    /// this is 1 minus the mean resultant length, from 0 for identical angles up to 1 for angles that cancel out
+    /// Returns None for an empty set
+    pub fn circular_variance(angles: &[AngleRadians]) -> Option<f64> {
+        let (sin, cos) = mean_resultant(angles.iter().map(|angle| angle.angle))?;
+        Some(1.0 - sin.hypot(cos).min(1.0))
+    }
+
+    /// Calculate the circular standard deviation of a set of angles
+    /// this is sqrt(-2 ln R) for the mean resultant length R, which is infinite for angles that cancel out
+    /// Returns None for an empty set
+    pub fn circular_std(angles: &[AngleRadians]) -> Option<AngleRadians> {
+        let (sin, cos) = mean_resultant(angles.iter().map(|angle| angle.angle))?;
+        Some(Self::new((-2.0 * sin.hypot(cos).min(1.0).ln()).sqrt()))
+    }
+
     /// Get the sine of the angle
     pub fn sin(&self) -> f64 {
         self.angle.sin()
@@ -91,10 +139,43 @@ impl AngleRadians {
 
 impl AngleDegrees {
     /// Create a new angle in degrees
-    pub fn new(angle: f64) -> Self {
+    pub const fn new(angle: f64) -> Self {
         Self { angle }
     }
 
+    /// Calculate the euclidean remainder of the angle divided by a modulus
+    /// unlike `%` the result is never negative, it is in the range 0 to |modulus|
+    pub fn rem_euclid(&self, modulus: AngleDegrees) -> AngleDegrees {
+        Self::new(self.angle.rem_euclid(modulus.angle))
+    }
+
+    /// Wrap the angle into the range 0 to 360 degrees
+    /// negative angles wrap around to the positive equivalent
+    pub fn wrap(&self) -> AngleDegrees {
+        Self::new(wrap(self.angle, 360.0))
+    }
+
+    /// Calculate the circular mean of a set of angles, in the range -180 to 180 degrees
+    /// see `AngleRadians::circular_mean`
+    pub fn circular_mean(angles: &[AngleDegrees]) -> Option<AngleDegrees> {
+        let radians: Vec<AngleRadians> = angles.iter().map(AngleRadians::from).collect();
+        AngleRadians::circular_mean(&radians).map(AngleDegrees::from)
+    }
+
+    /// Calculate the circular variance of a set of angles
+    /// see `AngleRadians::circular_variance`
+    pub fn circular_variance(angles: &[AngleDegrees]) -> Option<f64> {
+        let radians: Vec<AngleRadians> = angles.iter().map(AngleRadians::from).collect();
+        AngleRadians::circular_variance(&radians)
+    }
+
+    /// Calculate the circular standard deviation of a set of angles
+    /// see `AngleRadians::circular_std`
+    pub fn circular_std(angles: &[AngleDegrees]) -> Option<AngleDegrees> {
+        let radians: Vec<AngleRadians> = angles.iter().map(AngleRadians::from).collect();
+        AngleRadians::circular_std(&radians).map(AngleDegrees::from)
+    }
+
     /// Get the sine of the angle
     pub fn sin(&self) -> f64 {
         AngleRadians::from_degrees(AngleDegrees::new(self.angle)).sin()
@@ -179,7 +260,54 @@ impl From<&AngleRadians> for f64 {
     }
 }
 
-impl std::ops::Div<f64> for AngleRadians {
+/// The mean resultant length below which the circular mean of a set of angles is treated as undefined
+pub const CIRCULAR_TOLERANCE: f64 = 1e-12;
+
+/// Get the mean of the sines and cosines of a set of angles in radians
+/// or None if there are no angles
+#[allow(clippy::cast_precision_loss)]
+fn mean_resultant(angles: impl Iterator<Item = f64>) -> Option<(f64, f64)> {
+    let (count, sin, cos) = angles.fold((0_usize, 0.0, 0.0), |(count, sin, cos), angle| {
+        (count + 1, sin + angle.sin(), cos + angle.cos())
+    });
+    if count == 0 {
+        return None;
+    }
+    Some((sin / count as f64, cos / count as f64))
+}
+
+/// Wrap an angle into the range 0 to a full turn
+/// `rem_euclid` can round a tiny negative angle up to the full turn itself so that is folded back to 0
+fn wrap(angle: f64, turn: f64) -> f64 {
+    let wrapped = angle.rem_euclid(turn);
+    if wrapped >= turn {
+        0.0
+    } else {
+        wrapped
+    }
+}
+
+impl core::ops::Rem<AngleRadians> for AngleRadians {
+    type Output = AngleRadians;
+
+    /// Take the remainder with the same sign as the angle like `%` on f64
+    /// use `rem_euclid` or `wrap` for a result that is never negative
+    fn rem(self, rhs: AngleRadians) -> AngleRadians {
+        (self.angle % rhs.angle).into()
+    }
+}
+
+impl core::ops::Rem<AngleDegrees> for AngleDegrees {
+    type Output = AngleDegrees;
+
+    /// Take the remainder with the same sign as the angle like `%` on f64
+    /// use `rem_euclid` or `wrap` for a result that is never negative
+    fn rem(self, rhs: AngleDegrees) -> AngleDegrees {
+        AngleDegrees::new(self.angle % rhs.angle)
+    }
+}
+
+impl core::ops::Div<f64> for AngleRadians {
     type Output = AngleRadians;
 
     fn div(self, rhs: f64) -> AngleRadians {
@@ -187,7 +315,7 @@ impl std::ops::Div<f64> for AngleRadians {
     }
 }
 
-impl std::ops::Mul<f64> for AngleRadians {
+impl core::ops::Mul<f64> for AngleRadians {
     type Output = AngleRadians;
 
     fn mul(self, rhs: f64) -> AngleRadians {
@@ -195,7 +323,7 @@ impl std::ops::Mul<f64> for AngleRadians {
     }
 }
 
-impl std::ops::Mul<f64> for AngleDegrees {
+impl core::ops::Mul<f64> for AngleDegrees {
     type Output = AngleDegrees;
 
     fn mul(self, rhs: f64) -> AngleDegrees {
@@ -203,7 +331,7 @@ impl std::ops::Mul<f64> for AngleDegrees {
     }
 }
 
-impl std::ops::Add<AngleRadians> for AngleRadians {
+impl core::ops::Add<AngleRadians> for AngleRadians {
     type Output = AngleRadians;
 
     fn add(self, rhs: AngleRadians) -> AngleRadians {
@@ -211,7 +339,7 @@ impl std::ops::Add<AngleRadians> for AngleRadians {
     }
 }
 
-impl std::ops::Sub<AngleRadians> for AngleRadians {
+impl core::ops::Sub<AngleRadians> for AngleRadians {
     type Output = AngleRadians;
 
     fn sub(self, rhs: AngleRadians) -> AngleRadians {
@@ -219,7 +347,7 @@ impl std::ops::Sub<AngleRadians> for AngleRadians {
     }
 }
 
-impl std::ops::Neg for AngleRadians {
+impl core::ops::Neg for AngleRadians {
     type Output = AngleRadians;
 
     fn neg(self) -> AngleRadians {
@@ -227,7 +355,7 @@ impl std::ops::Neg for AngleRadians {
     }
 }
 
-impl std::ops::Neg for AngleDegrees {
+impl core::ops::Neg for AngleDegrees {
     type Output = AngleDegrees;
 
     fn neg(self) -> AngleDegrees {
@@ -235,24 +363,94 @@ impl std::ops::Neg for AngleDegrees {
     }
 }
 
-impl std::cmp::Ord for AngleRadians {
-    fn cmp(&self, rhs: &AngleRadians) -> std::cmp::Ordering {
-        self.partial_cmp(rhs).unwrap()
+impl core::iter::Sum for AngleRadians {
+    /// Sum angles, an empty iterator sums to zero
+    fn sum<I: Iterator<Item = AngleRadians>>(iter: I) -> AngleRadians {
+        AngleRadians::new(iter.map(|angle| angle.angle).sum())
+    }
+}
+
+impl<'a> core::iter::Sum<&'a AngleRadians> for AngleRadians {
+    /// Sum angles, an empty iterator sums to zero
+    fn sum<I: Iterator<Item = &'a AngleRadians>>(iter: I) -> AngleRadians {
+        AngleRadians::new(iter.map(|angle| angle.angle).sum())
+    }
+}
+
+impl core::iter::Sum for AngleDegrees {
+    /// Sum angles, an empty iterator sums to zero
+    fn sum<I: Iterator<Item = AngleDegrees>>(iter: I) -> AngleDegrees {
+        AngleDegrees::new(iter.map(|angle| angle.angle).sum())
+    }
+}
+
+impl<'a> core::iter::Sum<&'a AngleDegrees> for AngleDegrees {
+    /// Sum angles, an empty iterator sums to zero
+    fn sum<I: Iterator<Item = &'a AngleDegrees>>(iter: I) -> AngleDegrees {
+        AngleDegrees::new(iter.map(|angle| angle.angle).sum())
+    }
+}
+
+impl core::cmp::PartialEq for AngleRadians {
+    /// Angles are equal when `cmp` says so, so NaN angles equal themselves and Eq stays lawful
+    fn eq(&self, rhs: &AngleRadians) -> bool {
+        self.cmp(rhs) == core::cmp::Ordering::Equal
     }
 }
 
-impl std::cmp::Ord for AngleDegrees {
-    fn cmp(&self, rhs: &AngleDegrees) -> std::cmp::Ordering {
-        self.partial_cmp(rhs).unwrap()
+impl core::cmp::PartialEq for AngleDegrees {
+    /// Angles are equal when `cmp` says so, so NaN angles equal themselves and Eq stays lawful
+    fn eq(&self, rhs: &AngleDegrees) -> bool {
+        self.cmp(rhs) == core::cmp::Ordering::Equal
     }
 }
 
-impl std::cmp::Eq for AngleRadians {}
+impl core::cmp::PartialOrd for AngleRadians {
+    fn partial_cmp(&self, rhs: &AngleRadians) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(rhs))
+    }
+}
+
+impl core::cmp::PartialOrd for AngleDegrees {
+    fn partial_cmp(&self, rhs: &AngleDegrees) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(rhs))
+    }
+}
 
-impl std::cmp::Eq for AngleDegrees {}
+impl core::cmp::Ord for AngleRadians {
+    /// Compare two angles using a total order so NaN angles sort instead of panicking, see `f64::total_cmp`
+    /// negative NaN sorts before negative infinity and positive NaN after positive infinity
+    /// unlike `total_cmp` 0 and -0 are equal, they are next to each other in its order so this is still a total order
+    #[allow(clippy::float_cmp)]
+    fn cmp(&self, rhs: &AngleRadians) -> core::cmp::Ordering {
+        if self.angle == rhs.angle {
+            core::cmp::Ordering::Equal
+        } else {
+            self.angle.total_cmp(&rhs.angle)
+        }
+    }
+}
 
-impl std::fmt::Display for AngleRadians {
-    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+impl core::cmp::Ord for AngleDegrees {
+    /// Compare two angles using a total order so NaN angles sort instead of panicking, see `f64::total_cmp`
+    /// negative NaN sorts before negative infinity and positive NaN after positive infinity
+    /// unlike `total_cmp` 0 and -0 are equal, they are next to each other in its order so this is still a total order
+    #[allow(clippy::float_cmp)]
+    fn cmp(&self, rhs: &AngleDegrees) -> core::cmp::Ordering {
+        if self.angle == rhs.angle {
+            core::cmp::Ordering::Equal
+        } else {
+            self.angle.total_cmp(&rhs.angle)
+        }
+    }
+}
+
+impl core::cmp::Eq for AngleRadians {}
+
+impl core::cmp::Eq for AngleDegrees {}
+
+impl core::fmt::Display for AngleRadians {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
         if let Some(precision) = f.precision() {
             write!(f, "{:.1$} radians", self.angle, precision)
         } else {
@@ -261,8 +459,8 @@ impl std::fmt::Display for AngleRadians {
     }
 }
 
-impl std::fmt::Display for AngleDegrees {
-    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+impl core::fmt::Display for AngleDegrees {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
         if let Some(precision) = f.precision() {
             write!(f, "{:.1$}°", self.angle, precision)
         } else {
@@ -273,5 +471,133 @@ impl std::fmt::Display for AngleDegrees {
 
 #[cfg(test)]
 mod tests {
-// TODO
+    use super::*;
+
+    #[test]
+    fn test_const_constructors() {
+        const RIGHT_ANGLE: AngleRadians = AngleRadians::half_pi();
+        const TURN: AngleRadians = AngleRadians::two_pi();
+        const DEGREES: AngleDegrees = AngleDegrees::new(90.0);
+        assert_eq!(RIGHT_ANGLE, AngleRadians::new(PI / 2.0));
+        assert_eq!(TURN, AngleRadians::pi() * 2.0);
+        assert!((f64::from(AngleRadians::from(DEGREES)) - f64::from(RIGHT_ANGLE)).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_default() {
+        assert_eq!(AngleRadians::default(), AngleRadians::new(0.0));
+        assert_eq!(AngleDegrees::default(), AngleDegrees::new(0.0));
+    }
+
+    #[test]
+    fn test_sum() {
+        let radians = [AngleRadians::half_pi(), AngleRadians::half_pi()];
+        assert_eq!(radians.iter().sum::<AngleRadians>(), AngleRadians::pi());
+        assert_eq!(radians.into_iter().sum::<AngleRadians>(), AngleRadians::pi());
+        let degrees = [AngleDegrees::new(30.0), AngleDegrees::new(60.0)];
+        assert_eq!(degrees.iter().sum::<AngleDegrees>(), AngleDegrees::new(90.0));
+        assert_eq!(core::iter::empty::<AngleRadians>().sum::<AngleRadians>(), AngleRadians::default());
+        assert_eq!(core::iter::empty::<AngleDegrees>().sum::<AngleDegrees>(), AngleDegrees::default());
+    }
+
+    #[test]
+    fn test_rem() {
+        let turn = AngleDegrees::new(360.0);
+        assert_eq!(AngleDegrees::new(370.0) % turn, AngleDegrees::new(10.0));
+        assert_eq!(AngleDegrees::new(-10.0) % turn, AngleDegrees::new(-10.0));
+        assert_eq!(AngleRadians::new(-1.0) % AngleRadians::two_pi(), AngleRadians::new(-1.0));
+    }
+
+    #[test]
+    fn test_rem_euclid() {
+        let turn = AngleDegrees::new(360.0);
+        assert_eq!(AngleDegrees::new(-10.0).rem_euclid(turn), AngleDegrees::new(350.0));
+        assert_eq!(AngleDegrees::new(-370.0).rem_euclid(turn), AngleDegrees::new(350.0));
+        assert_eq!(AngleDegrees::new(360.0).rem_euclid(turn), AngleDegrees::new(0.0));
+        assert_eq!(AngleDegrees::new(-10.0).rem_euclid(-turn), AngleDegrees::new(350.0));
+        let wrapped = AngleRadians::new(-1.0).rem_euclid(AngleRadians::two_pi());
+        assert!((f64::from(wrapped) - (2.0 * PI - 1.0)).abs() < 1e-15);
+    }
+
+    #[test]
+    fn test_wrap() {
+        assert_eq!(AngleDegrees::new(-90.0).wrap(), AngleDegrees::new(270.0));
+        assert_eq!(AngleDegrees::new(360.0).wrap(), AngleDegrees::new(0.0));
+        assert_eq!(AngleDegrees::new(-360.0).wrap(), AngleDegrees::new(0.0));
+        assert_eq!(AngleDegrees::new(720.0).wrap(), AngleDegrees::new(0.0));
+        // A billion turns is far past where subtracting one turn at a time would stay exact
+        assert_eq!(AngleDegrees::new(360.0 * 1e9 + 30.0).wrap(), AngleDegrees::new(30.0));
+        assert_eq!(AngleDegrees::new(-360.0 * 1e9 - 30.0).wrap(), AngleDegrees::new(330.0));
+        assert_eq!(AngleRadians::two_pi().wrap(), AngleRadians::new(0.0));
+        assert_eq!(AngleRadians::new(-PI).wrap(), AngleRadians::pi());
+        let tiny = AngleRadians::new(-1e-300).wrap();
+        assert!(f64::from(tiny) >= 0.0 && f64::from(tiny) < 2.0 * PI);
+        let large = AngleRadians::new(2.0 * PI * 1e6 + 0.5).wrap();
+        assert!((f64::from(large) - 0.5).abs() < 1e-8);
+    }
+
+    #[test]
+    fn test_sort_with_nan() {
+        let mut angles = [AngleRadians::pi(), AngleRadians::new(f64::NAN), AngleRadians::new(-1.0)];
+        angles.sort();
+        assert_eq!(angles[0], AngleRadians::new(-1.0));
+        assert_eq!(angles[1], AngleRadians::pi());
+        assert!(f64::from(angles[2]).is_nan());
+        let mut degrees = [AngleDegrees::new(f64::NAN), AngleDegrees::new(10.0)];
+        degrees.sort();
+        assert_eq!(degrees[0], AngleDegrees::new(10.0));
+    }
+
+    #[test]
+    fn test_sort_with_infinities() {
+        let mut angles = [
+            AngleRadians::new(f64::INFINITY),
+            AngleRadians::new(f64::NAN),
+            AngleRadians::new(-0.0),
+            AngleRadians::new(-f64::NAN),
+            AngleRadians::new(0.0),
+            AngleRadians::new(f64::NEG_INFINITY)
+        ];
+        angles.sort();
+        let bits: Vec<u64> = angles.iter().map(|angle| f64::from(*angle).to_bits()).collect();
+        let expected = [-f64::NAN, f64::NEG_INFINITY, -0.0, 0.0, f64::INFINITY, f64::NAN].map(f64::to_bits);
+        assert_eq!(bits, expected);
+        assert_eq!(AngleDegrees::new(f64::NAN), AngleDegrees::new(f64::NAN));
+        assert_eq!(AngleRadians::new(0.0), AngleRadians::new(-0.0));
+        assert_ne!(AngleRadians::new(f64::NAN), AngleRadians::new(-f64::NAN));
+    }
+
+    #[test]
+    fn test_circular_mean() {
+        assert_eq!(AngleRadians::circular_mean(&[]), None);
+        let mean = AngleDegrees::circular_mean(&[AngleDegrees::new(350.0), AngleDegrees::new(10.0)]).unwrap();
+        assert!(f64::from(AngleRadians::from(mean)).abs() < 1e-12);
+        let mean = AngleDegrees::circular_mean(&[AngleDegrees::new(80.0), AngleDegrees::new(100.0), AngleDegrees::new(90.0)]);
+        assert!((f64::from(AngleRadians::from(mean.unwrap())) - PI / 2.0).abs() < 1e-12);
+
+        let same = [AngleRadians::new(1.0); 4];
+        assert!((f64::from(AngleRadians::circular_mean(&same).unwrap()) - 1.0).abs() < 1e-12);
+        assert!(AngleRadians::circular_variance(&same).unwrap().abs() < 1e-15);
+        assert!(f64::from(AngleRadians::circular_std(&same).unwrap()) < 1e-7);
+    }
+
+    #[test]
+    fn test_circular_spread() {
+        let variance = |spread: f64| {
+            AngleDegrees::circular_variance(&[AngleDegrees::new(-spread), AngleDegrees::new(spread)]).unwrap()
+        };
+        assert!(variance(10.0) < variance(30.0));
+        assert!(variance(30.0) < variance(60.0));
+        let std = AngleDegrees::circular_std(&[AngleDegrees::new(355.0), AngleDegrees::new(5.0)]).unwrap();
+        // Close to the linear standard deviation for a small spread
+        assert!((f64::from(AngleRadians::from(std)) - 5.0_f64.to_radians()).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_circular_opposed() {
+        let opposed = [AngleRadians::new(0.3), AngleRadians::new(0.3 + PI)];
+        assert_eq!(AngleRadians::circular_mean(&opposed), Some(AngleRadians::new(0.0)));
+        assert!((AngleRadians::circular_variance(&opposed).unwrap() - 1.0).abs() < 1e-12);
+        assert!(f64::from(AngleRadians::circular_std(&opposed).unwrap()) > 5.0);
+    }
 }
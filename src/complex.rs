@@ -1,4 +1,9 @@
+#[cfg(not(any(feature = "std", test)))]
+use crate::math::Float;
+
 /// A complex number
+#[derive(Copy, Clone, Default)]
+#[repr(C)]
 pub struct Complex {
     /// The real part of the complex number
     pub real: f64,
@@ -9,7 +14,7 @@ pub struct Complex {
 
 impl Complex {
     /// Create a new complex number
-    pub fn new(real: f64, imaginary: f64) -> Complex {
+    pub const fn new(real: f64, imaginary: f64) -> Complex {
         Complex { real, imaginary }
     }
 
@@ -36,7 +41,7 @@ impl Complex {
     }
 }
 
-impl std::ops::Add<&Complex> for &Complex {
+impl core::ops::Add<&Complex> for &Complex {
     type Output = Complex;
 
     fn add(self, other: &Complex) -> Complex {
@@ -47,50 +52,63 @@ impl std::ops::Add<&Complex> for &Complex {
     }
 }
 
-impl std::ops::Add for Complex {
+impl core::ops::Sub<&Complex> for &Complex {
     type Output = Complex;
 
-    fn add(self, other: Complex) -> Complex {
-        &self + &other
+    fn sub(self, other: &Complex) -> Complex {
+        Complex {
+            real: self.real - other.real,
+            imaginary: self.imaginary - other.imaginary
+        }
     }
 }
 
-impl std::ops::Add<&Complex> for Complex {
+impl core::ops::Mul<&Complex> for &Complex {
     type Output = Complex;
 
-    fn add(self, other: &Complex) -> Complex {
-        &self + other
+    fn mul(self, other: &Complex) -> Complex {
+        Complex {
+            real: self.real * other.real - self.imaginary * other.imaginary,
+            imaginary: self.real * other.imaginary + self.imaginary * other.real
+        }
     }
 }
 
-impl std::ops::Sub<&Complex> for &Complex {
-    type Output = Complex;
+impl_ref_ops! {
+    Add, add for Complex, Complex => Complex;
+    Sub, sub for Complex, Complex => Complex;
+    Mul, mul for Complex, Complex => Complex
+}
 
-    fn sub(self, other: &Complex) -> Complex {
-        Complex {
-            real: self.real - other.real,
-            imaginary: self.imaginary - other.imaginary
-        }
+impl core::iter::Sum for Complex {
+    /// Sum complex numbers, an empty iterator sums to zero
+    fn sum<I: Iterator<Item = Complex>>(iter: I) -> Complex {
+        iter.fold(Complex::default(), |sum, c| sum + c)
     }
 }
 
-impl std::ops::Sub for Complex {
-    type Output = Complex;
-
-    fn sub(self, other: Complex) -> Complex {
-        &self - &other
+impl<'a> core::iter::Sum<&'a Complex> for Complex {
+    /// Sum complex numbers, an empty iterator sums to zero
+    fn sum<I: Iterator<Item = &'a Complex>>(iter: I) -> Complex {
+        iter.copied().sum()
     }
 }
 
-impl std::ops::Sub<&Complex> for Complex {
-    type Output = Complex;
+impl core::iter::Product for Complex {
+    /// Multiply complex numbers, an empty iterator multiplies to one
+    fn product<I: Iterator<Item = Complex>>(iter: I) -> Complex {
+        iter.fold(Complex::new(1.0, 0.0), |product, c| product * c)
+    }
+}
 
-    fn sub(self, other: &Complex) -> Complex {
-        &self - other
+impl<'a> core::iter::Product<&'a Complex> for Complex {
+    /// Multiply complex numbers, an empty iterator multiplies to one
+    fn product<I: Iterator<Item = &'a Complex>>(iter: I) -> Complex {
+        iter.copied().product()
     }
 }
 
-impl std::ops::Index<usize> for Complex {
+impl core::ops::Index<usize> for Complex {
     type Output = f64;
 
     fn index(&self, index: usize) -> &f64 {
@@ -102,8 +120,8 @@ impl std::ops::Index<usize> for Complex {
     }
 }
 
-impl std::fmt::Display for Complex {
-    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+impl core::fmt::Display for Complex {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
         if self.imaginary < 0.0 {
             write!(f, "{} - {}i", self.real, self.imaginary.abs())
         } else {
@@ -112,6 +130,35 @@ impl std::fmt::Display for Complex {
     }
 }
 
+/// Serialize the complex number as a [real, imaginary] pair
+#[cfg(feature = "serde")]
+impl serde::Serialize for Complex {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        (self.real, self.imaginary).serialize(serializer)
+    }
+}
+
+/// Deserialize the complex number from a [real, imaginary] pair
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Complex {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let (real, imaginary) = <(f64, f64)>::deserialize(deserializer)?;
+        Ok(Complex::new(real, imaginary))
+    }
+}
+
+// SAFETY: Complex is repr(C) with only f64 fields, so it has no padding and every bit pattern is valid
+#[cfg(feature = "bytemuck")]
+unsafe impl bytemuck::Zeroable for Complex {}
+
+#[cfg(feature = "bytemuck")]
+unsafe impl bytemuck::Pod for Complex {}
+
+#[cfg(feature = "bytemuck")]
+const _: () = assert!(core::mem::size_of::<Complex>() == 2 * core::mem::size_of::<f64>());
+#[cfg(feature = "bytemuck")]
+const _: () = assert!(core::mem::align_of::<Complex>() == core::mem::align_of::<f64>());
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -161,4 +208,52 @@ mod tests {
         assert_eq!(diff.real, -2.0);
         assert_eq!(diff.imaginary, -2.0);
     }
+
+    #[cfg(feature = "bytemuck")]
+    #[test]
+    fn test_bytemuck_cast() {
+        let numbers = [Complex::new(1.0, -2.0), Complex::new(0.5, 3.0)];
+        let floats: &[f64] = bytemuck::cast_slice(&numbers);
+        assert_eq!(floats, &[1.0, -2.0, 0.5, 3.0]);
+    }
+
+    #[test]
+    fn test_const_constructor() {
+        const I: Complex = Complex::new(0.0, 1.0);
+        assert!((I.imaginary - 1.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_default() {
+        let c = Complex::default();
+        assert_eq!((c.real, c.imaginary), (0.0, 0.0));
+    }
+
+    #[test]
+    fn test_mul() {
+        let c = Complex::new(1.0, 2.0) * Complex::new(3.0, 4.0);
+        assert_eq!((c.real, c.imaginary), (-5.0, 10.0));
+    }
+
+    #[test]
+    fn test_sum_and_product() {
+        let numbers = [Complex::new(1.0, 2.0), Complex::new(3.0, 4.0), Complex::new(0.0, 1.0)];
+        let sum: Complex = numbers.iter().sum();
+        assert_eq!((sum.real, sum.imaginary), (4.0, 7.0));
+        let product: Complex = numbers.into_iter().product();
+        assert_eq!((product.real, product.imaginary), (-10.0, -5.0));
+        let empty_sum: Complex = core::iter::empty::<Complex>().sum();
+        assert_eq!((empty_sum.real, empty_sum.imaginary), (0.0, 0.0));
+        let empty_product: Complex = core::iter::empty::<Complex>().product();
+        assert_eq!((empty_product.real, empty_product.imaginary), (1.0, 0.0));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde() {
+        let json = serde_json::to_string(&Complex::new(1.5, -2.0)).unwrap();
+        assert_eq!(json, "[1.5,-2.0]");
+        let c: Complex = serde_json::from_str(&json).unwrap();
+        assert_eq!((c.real, c.imaginary), (1.5, -2.0));
+    }
 }
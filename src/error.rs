@@ -0,0 +1,123 @@
+use crate::geometry::GeometryError;
+use crate::matrix::dynamic::MatrixError;
+use crate::parse::ParseError;
+use crate::units::UnitParseError;
+
+/// Any error produced by this crate
+/// each module keeps its own error type and converts into this one so `?` works across modules
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    /// Invalid geometry such as a zero normal or a negative radius
+    Geometry(GeometryError),
+    /// A matrix had the wrong shape or was singular
+    Matrix(MatrixError),
+    /// A vector had a length of zero and cannot be normalized
+    ZeroVector,
+    /// A slice or iterator had fewer elements than needed
+    TooFewElements {
+        /// The number of elements needed
+        expected: usize,
+        /// The number of elements found
+        found: usize
+    },
+    /// An iterator had more elements than needed
+    TooManyElements {
+        /// The exact number of elements needed
+        expected: usize
+    },
+    /// A quantity could not be parsed
+    Parse(UnitParseError),
+    /// A vector or shape could not be parsed
+    ParseValue(ParseError)
+}
+
+impl core::fmt::Display for Error {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        match self {
+            Error::Geometry(error) => write!(f, "invalid geometry: {error}"),
+            Error::Matrix(error) => write!(f, "invalid matrix: {error}"),
+            Error::ZeroVector => write!(f, "vector has zero length"),
+            Error::TooFewElements { expected, found } => {
+                write!(f, "expected at least {expected} elements but found {found}")
+            }
+            Error::TooManyElements { expected } => write!(f, "expected exactly {expected} elements but found more"),
+            Error::Parse(error) => write!(f, "parse error: {error}"),
+            Error::ParseValue(error) => write!(f, "parse error: {error}")
+        }
+    }
+}
+
+impl core::error::Error for Error {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            Error::Geometry(error) => Some(error),
+            Error::Matrix(error) => Some(error),
+            Error::Parse(error) => Some(error),
+            Error::ParseValue(error) => Some(error),
+            Error::ZeroVector | Error::TooFewElements { .. } | Error::TooManyElements { .. } => None
+        }
+    }
+}
+
+impl From<GeometryError> for Error {
+    fn from(error: GeometryError) -> Error {
+        Error::Geometry(error)
+    }
+}
+
+impl From<MatrixError> for Error {
+    fn from(error: MatrixError) -> Error {
+        Error::Matrix(error)
+    }
+}
+
+impl From<UnitParseError> for Error {
+    fn from(error: UnitParseError) -> Error {
+        Error::Parse(error)
+    }
+}
+
+impl From<ParseError> for Error {
+    fn from(error: ParseError) -> Error {
+        Error::ParseValue(error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geometry::plane::Plane;
+    use crate::matrix::dynamic::DMatrix;
+    use crate::units::Length;
+    use crate::vec3d::Vec3d;
+
+    fn plane_from_parts(normal: &[f64], distance: &str) -> Result<Plane, Error> {
+        let normal = Vec3d::try_from_slice(normal)?.try_normalize()?;
+        let distance: Length = distance.parse()?;
+        Ok(Plane::try_new(normal, distance.as_meters())?)
+    }
+
+    #[test]
+    fn test_question_mark_conversion() {
+        assert!(plane_from_parts(&[0.0, 0.0, 2.0], "1 m").is_ok());
+        assert_eq!(
+            plane_from_parts(&[0.0, 0.0], "1 m"),
+            Err(Error::TooFewElements { expected: 3, found: 2 })
+        );
+        assert_eq!(plane_from_parts(&[0.0, 0.0, 0.0], "1 m"), Err(Error::ZeroVector));
+        assert!(matches!(plane_from_parts(&[0.0, 0.0, 1.0], "1 parsec"), Err(Error::Parse(_))));
+        assert_eq!(
+            plane_from_parts(&[0.0, 0.0, 1.0], "inf m"),
+            Err(Error::Geometry(GeometryError::NonFinite))
+        );
+    }
+
+    #[test]
+    fn test_matrix_conversion() {
+        fn solve() -> Result<alloc::vec::Vec<f64>, Error> {
+            let matrix = DMatrix::try_from_nested_vec(alloc::vec![alloc::vec![1.0, 2.0], alloc::vec![2.0, 4.0]])?;
+            Ok(matrix.solve(&[1.0, 2.0])?)
+        }
+        assert_eq!(solve(), Err(Error::Matrix(MatrixError::Singular)));
+    }
+}
@@ -8,5 +8,93 @@ pub mod plane;
 pub mod sphere;
 /// Circles
 pub mod circle;
+/// Arcs of circles
+pub mod arc;
 /// Intersections
 pub mod intersection;
+/// Boolean overlap tests
+pub mod query;
+/// Tetrahedrons
+pub mod tetrahedron;
+/// Triangles
+pub mod triangle;
+/// Cylinders
+pub mod cylinder;
+/// Line segments
+pub mod segment;
+/// Rays and ray casting
+pub mod ray;
+/// Axis aligned boxes
+pub mod aabb;
+/// Closed loops of points in a plane
+pub mod polygon;
+/// Writing geometry to files for viewing
+pub mod export;
+
+/// Geometry that can be uniformly scaled about the origin
+/// e.g. to convert between length units with `units::LengthScale`
+pub trait ScaleGeometry {
+    /// Get a copy of the geometry scaled about the origin by a factor
+    /// radii and heights stay positive for negative factors
+    fn scaled(&self, factor: f64) -> Self;
+}
+
+impl ScaleGeometry for crate::vec3d::Vec3d {
+    fn scaled(&self, factor: f64) -> Self {
+        *self * factor
+    }
+}
+
+impl<T: ScaleGeometry> ScaleGeometry for alloc::vec::Vec<T> {
+    fn scaled(&self, factor: f64) -> Self {
+        self.iter().map(|item| item.scaled(factor)).collect()
+    }
+}
+
+/// Errors from constructing invalid geometry
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GeometryError {
+    /// A normal vector had a length of zero and cannot be normalized
+    ZeroNormal,
+    /// A coordinate or scalar was NaN or infinite
+    NonFinite,
+    /// A radius was negative
+    NegativeRadius,
+    /// A point that should have been in a plane was not
+    PointNotInPlane
+}
+
+impl core::fmt::Display for GeometryError {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        match self {
+            GeometryError::ZeroNormal => write!(f, "normal vector has zero length"),
+            GeometryError::NonFinite => write!(f, "value is not finite"),
+            GeometryError::NegativeRadius => write!(f, "radius is negative"),
+            GeometryError::PointNotInPlane => write!(f, "point is not in the plane")
+        }
+    }
+}
+
+impl core::error::Error for GeometryError {}
+
+/// Check that a normal vector is finite and can be normalized
+fn validate_normal(normal: &crate::vec3d::Vec3d) -> Result<(), GeometryError> {
+    if !normal.is_finite() {
+        return Err(GeometryError::NonFinite);
+    }
+    if normal.magnitude() == 0.0 {
+        return Err(GeometryError::ZeroNormal);
+    }
+    Ok(())
+}
+
+/// Check that a radius is finite and not negative
+fn validate_radius(radius: f64) -> Result<(), GeometryError> {
+    if !radius.is_finite() {
+        return Err(GeometryError::NonFinite);
+    }
+    if radius < 0.0 {
+        return Err(GeometryError::NegativeRadius);
+    }
+    Ok(())
+}
@@ -0,0 +1,72 @@
+use crate::geometry::ScaleGeometry;
+use crate::vec3d::Vec3d;
+
+/// A box with faces parallel to the coordinate planes
+/// i.e. an axis aligned bounding box
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Aabb {
+    /// The corner with the smallest coordinates
+    pub min: Vec3d,
+    /// The corner with the largest coordinates
+    pub max: Vec3d
+}
+
+impl Aabb {
+    /// Create a new box from any two opposite corners
+    /// the coordinates are sorted so `min` is never greater than `max`
+    pub fn new(a: impl Into<Vec3d>, b: impl Into<Vec3d>) -> Aabb {
+        let (a, b) = (a.into(), b.into());
+        Aabb {
+            min: Vec3d::new(a.x.min(b.x), a.y.min(b.y), a.z.min(b.z)),
+            max: Vec3d::new(a.x.max(b.x), a.y.max(b.y), a.z.max(b.z))
+        }
+    }
+
+    /// Get the center of the box
+    pub fn center(&self) -> Vec3d {
+        (self.min + self.max) / 2.0
+    }
+
+    /// Check if a point is inside the box or on its surface
+    pub fn contains_point(&self, point: &Vec3d) -> bool {
+        (0..3).all(|axis| self.min[axis] <= point[axis] && point[axis] <= self.max[axis])
+    }
+
+    /// Get the point in the box closest to a point
+    pub fn closest_point(&self, point: &Vec3d) -> Vec3d {
+        Vec3d::new(
+            point.x.clamp(self.min.x, self.max.x),
+            point.y.clamp(self.min.y, self.max.y),
+            point.z.clamp(self.min.z, self.max.z)
+        )
+    }
+}
+
+impl ScaleGeometry for Aabb {
+    /// The corners are sorted again so negative factors still give a valid box
+    fn scaled(&self, factor: f64) -> Self {
+        Aabb::new(self.min.scaled(factor), self.max.scaled(factor))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new_sorts_corners() {
+        let aabb = Aabb::new((1.0, -2.0, 3.0), (-1.0, 2.0, 0.0));
+        assert_eq!(aabb.min, Vec3d::new(-1.0, -2.0, 0.0));
+        assert_eq!(aabb.max, Vec3d::new(1.0, 2.0, 3.0));
+        assert_eq!(aabb.center(), Vec3d::new(0.0, 0.0, 1.5));
+        assert_eq!(aabb.scaled(-2.0), Aabb::new((-2.0, -4.0, -6.0), (2.0, 4.0, 0.0)));
+    }
+
+    #[test]
+    fn test_contains_and_closest_point() {
+        let aabb = Aabb::new(Vec3d::zero(), (1.0, 1.0, 1.0));
+        assert!(aabb.contains_point(&Vec3d::new(0.5, 1.0, 0.0)));
+        assert!(!aabb.contains_point(&Vec3d::new(0.5, 1.5, 0.0)));
+        assert_eq!(aabb.closest_point(&Vec3d::new(2.0, 0.5, -1.0)), Vec3d::new(1.0, 0.5, 0.0));
+    }
+}
@@ -0,0 +1,100 @@
+use crate::angle::AngleRadians;
+use crate::geometry::circle::Circle;
+use crate::geometry::ScaleGeometry;
+use crate::vec3d::Vec3d;
+
+/// A part of a circle between two angles
+/// the angles are measured the same way as `Circle::radius_vector_at`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Arc {
+    /// The circle the arc is part of
+    pub circle: Circle,
+    /// The angle the arc starts at
+    pub start: AngleRadians,
+    /// The angle swept from the start, counter-clockwise about the normal of the circle when positive
+    pub sweep: AngleRadians
+}
+
+impl Arc {
+    /// Create a new arc
+    /// the start is wrapped into the range 0 to 2pi
+    pub fn new(circle: &Circle, start: impl Into<AngleRadians>, sweep: impl Into<AngleRadians>) -> Arc {
+        Arc {
+            circle: *circle,
+            start: start.into().wrap(),
+            sweep: sweep.into()
+        }
+    }
+
+    /// Get the point a fraction of the way along the arc
+    /// 0 gives the start point and 1 the end point
+    pub fn point_at(&self, t: f64) -> Vec3d {
+        self.circle.center + self.circle.radius_vector_at(self.start + self.sweep * t)
+    }
+
+    /// Get the point the arc starts at
+    pub fn start_point(&self) -> Vec3d {
+        self.point_at(0.0)
+    }
+
+    /// Get the point the arc ends at
+    pub fn end_point(&self) -> Vec3d {
+        self.point_at(1.0)
+    }
+
+    /// Get the length of the arc
+    pub fn length(&self) -> f64 {
+        self.circle.radius * f64::from(self.sweep).abs()
+    }
+
+    /// Check if the arc is a single point
+    pub fn is_degenerate(&self) -> bool {
+        f64::from(self.sweep) == 0.0 || self.circle.is_degenerate()
+    }
+}
+
+impl ScaleGeometry for Arc {
+    /// The basis of the circle only depends on its normal, which scaling keeps,
+    /// so for negative factors the start is turned by π to reflect every point through the origin instead of the center
+    fn scaled(&self, factor: f64) -> Self {
+        let start = if factor < 0.0 { (self.start + AngleRadians::pi()).wrap() } else { self.start };
+        Arc {
+            circle: self.circle.scaled(factor),
+            start,
+            sweep: self.sweep
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::f64::consts::PI;
+
+    use super::*;
+
+    #[test]
+    fn test_points_and_length() {
+        let circle = Circle::new(Vec3d::zero(), 2.0, Vec3d::k());
+        let arc = Arc::new(&circle, -PI / 2.0, PI);
+        assert!((f64::from(arc.start) - 1.5 * PI).abs() < 1e-12);
+        assert!((arc.start_point() - Vec3d::new(0.0, -2.0, 0.0)).magnitude() < 1e-12);
+        assert!((arc.point_at(0.5) - Vec3d::new(2.0, 0.0, 0.0)).magnitude() < 1e-12);
+        assert!((arc.end_point() - Vec3d::new(0.0, 2.0, 0.0)).magnitude() < 1e-12);
+        assert!((arc.length() - 2.0 * PI).abs() < 1e-12);
+        assert!(!arc.is_degenerate());
+        assert!(Arc::new(&circle, 1.0, 0.0).is_degenerate());
+    }
+
+    #[test]
+    fn test_scaled() {
+        let arc = Arc::new(&Circle::new(Vec3d::i(), 1.0, Vec3d::j()), 0.5, 1.0);
+        for factor in [3.0, -1.0, -2.5] {
+            let scaled = arc.scaled(factor);
+            for t in [0.0, 0.3, 1.0] {
+                assert!((scaled.point_at(t) - arc.point_at(t) * factor).magnitude() < 1e-12);
+            }
+        }
+        let arc = Arc::new(&Circle::new(Vec3d::zero(), 2.0, Vec3d::k()), 0.0, 1.0);
+        assert!((arc.scaled(-1.0).start_point() - Vec3d::new(-2.0, 0.0, 0.0)).magnitude() < 1e-12);
+    }
+}
@@ -1,4 +1,10 @@
+use alloc::vec::Vec;
+
+use crate::angle::AngleRadians;
 use crate::geometry::plane::Plane;
+use crate::geometry::{validate_normal, validate_radius, GeometryError, ScaleGeometry};
+use crate::parse::{Fields, ParseError};
+use crate::quat::Quat;
 use crate::vec3d::Vec3d;
 
 /// A circle in 3d space
@@ -13,35 +19,159 @@ pub struct Circle {
 }
 
 impl Circle {
+    /// The radius below which `is_degenerate` treats a circle as a single point
+    /// rounding in a squared distance is around 1e-16 and the square root taken for a radius turns that into around 1e-8
+    pub const DEGENERATE_TOLERANCE: f64 = 1e-7;
+
     /// Create a new circle
-    pub fn new(center: &Vec3d, radius: f64, normal: &Vec3d) -> Circle {
+    /// the radius is made positive and the normal is normalized without any other checks
+    /// a zero normal or non finite input produces NaN geometry, use `try_new` to reject them
+    pub fn new(center: impl Into<Vec3d>, radius: f64, normal: impl Into<Vec3d>) -> Circle {
         Circle {
-            center: *center,
+            center: center.into(),
             radius: radius.abs(),
-            normal: normal.normalize()
+            normal: normal.into().normalize()
+        }
+    }
+
+    /// Create a new circle from a normal that is already unit length and a radius that is not negative
+    /// the values are stored as given, so they must satisfy those invariants for the other methods to be correct
+    pub const fn new_unchecked(center: Vec3d, radius: f64, normal: Vec3d) -> Circle {
+        Circle {
+            center,
+            radius,
+            normal
+        }
+    }
+
+    /// Create a new circle, checking that the inputs describe a valid circle
+    ///
+    /// # Errors
+    /// Returns an error if the normal has zero length, any value is not finite, or the radius is negative
+    pub fn try_new(center: impl Into<Vec3d>, radius: f64, normal: impl Into<Vec3d>) -> Result<Circle, GeometryError> {
+        let (center, normal) = (center.into(), normal.into());
+        if !center.is_finite() {
+            return Err(GeometryError::NonFinite);
         }
+        validate_radius(radius)?;
+        validate_normal(&normal)?;
+        Ok(Circle::new(center, radius, normal))
+    }
+
+    /// Create a new circle from its center and a point on its rim
+    /// the radius is the distance between them
+    ///
+    /// # Errors
+    /// Returns an error if the normal has zero length, any value is not finite,
+    /// or the point is not in the plane through the center with the given normal, up to a relative tolerance of 1e-10
+    pub fn from_center_and_point(
+        center: impl Into<Vec3d>,
+        point_on_circle: impl Into<Vec3d>,
+        normal: impl Into<Vec3d>
+    ) -> Result<Circle, GeometryError> {
+        let (center, point_on_circle, normal) = (center.into(), point_on_circle.into(), normal.into());
+        if !center.is_finite() || !point_on_circle.is_finite() {
+            return Err(GeometryError::NonFinite);
+        }
+        validate_normal(&normal)?;
+        let offset = point_on_circle - center;
+        let radius = offset.magnitude();
+        if offset.dot(&normal.normalize()).abs() > 1e-10 * radius.max(1.0) {
+            return Err(GeometryError::PointNotInPlane);
+        }
+        Ok(Circle::new(center, radius, normal))
+    }
+
+    /// Check if the circle is valid
+    /// i.e. every value is finite, the radius is not negative, and the normal is not zero
+    pub fn is_valid(&self) -> bool {
+        self.center.is_finite() && validate_radius(self.radius).is_ok() && validate_normal(&self.normal).is_ok()
     }
 
     /// Get the plane the circle is in
     pub fn get_plane(&self) -> Plane {
-        Plane::from_point(&self.normal, &self.center)
+        Plane::from_point(self.normal, self.center)
     }
 
     /// Check if the circle is in the same plane as a second circle
+    /// circles with opposite normals can still share a plane
+    /// the planes are compared with a small tolerance so rounding in the centers or normals is ignored
     pub fn in_same_plane(&self, other: &Circle) -> bool {
-        if self.normal == other.normal || self.normal == -other.normal {
-            let self_distance = -self.normal.dot(&self.center);
-            let other_distance = -other.normal.dot(&other.center);
-            if (self_distance - other_distance).abs() < f64::EPSILON {
-                return true;
-            }
-        }
-        false
+        self.get_plane().approx_eq(&other.get_plane(), 1e-10)
+    }
+
+    /// Get the vector from the center to the point on the rim at an angle
+    /// the angle is counter-clockwise about the normal, starting from the direction of the x axis projected onto the plane
+    /// or of the y axis when the normal is close to the x axis
+    pub fn radius_vector_at(&self, angle: impl Into<AngleRadians>) -> Vec3d {
+        let angle = angle.into();
+        let (u, v) = self.get_plane().basis();
+        (u * angle.cos() + v * angle.sin()) * self.radius
+    }
+
+    /// Get evenly spaced points around the rim, starting at the angle 0 of `radius_vector_at`
+    pub fn sample(&self, count: usize) -> Vec<Vec3d> {
+        (0..count)
+            .map(|index| {
+                #[allow(clippy::cast_precision_loss)]
+                let angle = 2.0 * core::f64::consts::PI * index as f64 / count as f64;
+                self.center + self.radius_vector_at(angle)
+            })
+            .collect()
     }
 
     /// Check if the circle is degenerate with a radius of 0
+    /// the radius only has to be below `DEGENERATE_TOLERANCE` to allow for rounding
     pub fn is_degenerate(&self) -> bool {
-        self.radius == 0.0
+        self.is_degenerate_with_tolerance(Circle::DEGENERATE_TOLERANCE)
+    }
+
+    /// Check if the radius of the circle is within a tolerance of 0
+    pub fn is_degenerate_with_tolerance(&self, tolerance: f64) -> bool {
+        self.radius <= tolerance
+    }
+
+    /// Get the circle rotated about the origin by a quaternion, see `Quat::rotate`
+    /// both the center and the normal are rotated
+    pub fn rotated(&self, q: &Quat) -> Circle {
+        Circle { center: q.rotate(&self.center), radius: self.radius, normal: q.rotate(&self.normal) }
+    }
+
+    /// Get the circle moved by an offset, the normal is unchanged
+    pub fn translated(&self, offset: &Vec3d) -> Circle {
+        Circle { center: self.center + offset, radius: self.radius, normal: self.normal }
+    }
+}
+
+impl ScaleGeometry for Circle {
+    fn scaled(&self, factor: f64) -> Self {
+        Circle::new(self.center * factor, self.radius * factor, self.normal)
+    }
+}
+
+impl core::fmt::Display for Circle {
+    /// Format the circle as "circle c=(x, y, z) r=radius n=(x, y, z)"
+    /// the precision is applied to every number
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        if let Some(precision) = f.precision() {
+            write!(f, "circle c={:.3$} r={:.3$} n={:.3$}", self.center, self.radius, self.normal, precision)
+        } else {
+            write!(f, "circle c={} r={} n={}", self.center, self.radius, self.normal)
+        }
+    }
+}
+
+impl core::str::FromStr for Circle {
+    type Err = ParseError;
+
+    /// Parse a circle from its `Display` output, the values are stored as given
+    fn from_str(s: &str) -> Result<Circle, ParseError> {
+        let mut fields = Fields::new(s, "circle")?;
+        let center = fields.vector("c")?;
+        let radius = fields.number("r")?;
+        let normal = fields.vector("n")?;
+        fields.finish()?;
+        Ok(Circle::new_unchecked(center, radius, normal))
     }
 }
 
@@ -52,3 +182,160 @@ impl PartialEq for Circle {
             (self.normal == other.normal || self.normal == -other.normal)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::ToString;
+
+    use super::*;
+
+    #[test]
+    fn test_in_same_plane() {
+        let circle1 = Circle::new(Vec3d::new(0.0, 0.0, 1.0), 1.0, Vec3d::k());
+        let circle2 = Circle::new(Vec3d::new(2.0, 1.0, 1.0), 3.0, -Vec3d::k());
+        let circle3 = Circle::new(Vec3d::new(2.0, 1.0, 2.0), 3.0, -Vec3d::k());
+        assert!(circle1.in_same_plane(&circle2));
+        assert!(circle2.in_same_plane(&circle1));
+        assert!(!circle1.in_same_plane(&circle3));
+    }
+
+    #[test]
+    fn test_in_same_plane_with_rounding() {
+        let normal = Vec3d::new(1.0, 1.0, 1.0);
+        let circle1 = Circle::new(Vec3d::new(1.0, 0.0, 0.0), 1.0, normal);
+        // (0.9, 0.05, 0.05) is on the plane x + y + z = 1 but its plane offset differs in the last bit
+        let circle2 = Circle::new(Vec3d::new(0.9, 0.05, 0.05), 2.0, -normal);
+        assert!(circle1.get_plane() != circle2.get_plane());
+        assert!(circle1.in_same_plane(&circle2));
+        assert!(circle2.in_same_plane(&circle1));
+    }
+
+    #[test]
+    fn test_is_degenerate() {
+        assert!(Circle::new(Vec3d::zero(), 0.0, Vec3d::k()).is_degenerate());
+        assert!(Circle::new(Vec3d::zero(), 2.6e-9, Vec3d::k()).is_degenerate());
+        assert!(!Circle::new(Vec3d::zero(), 1e-3, Vec3d::k()).is_degenerate());
+        let small = Circle::new(Vec3d::zero(), 1e-5, Vec3d::k());
+        assert!(!small.is_degenerate());
+        assert!(small.is_degenerate_with_tolerance(1e-4));
+        assert!(!small.is_degenerate_with_tolerance(0.0));
+    }
+
+    #[test]
+    fn test_from_center_and_point() {
+        let center = Vec3d::new(1.0, 1.0, 1.0);
+        let circle = Circle::from_center_and_point(center, Vec3d::new(1.0, 4.0, 5.0), Vec3d::i()).unwrap();
+        assert!((circle.radius - 5.0).abs() < f64::EPSILON);
+        assert_eq!(circle.center, center);
+        assert_eq!(
+            Circle::from_center_and_point(center, Vec3d::new(1.1, 4.0, 5.0), Vec3d::i()),
+            Err(GeometryError::PointNotInPlane)
+        );
+        assert_eq!(
+            Circle::from_center_and_point(center, Vec3d::new(1.0, 4.0, 5.0), Vec3d::zero()),
+            Err(GeometryError::ZeroNormal)
+        );
+    }
+
+    #[test]
+    fn test_radius_vector_at() {
+        let circle = Circle::new(Vec3d::new(1.0, 2.0, 3.0), 2.0, Vec3d::k());
+        assert!(circle.radius_vector_at(0.0).distance_to(&Vec3d::new(2.0, 0.0, 0.0)) < 1e-12);
+        assert!(circle.radius_vector_at(AngleRadians::half_pi()).distance_to(&Vec3d::new(0.0, 2.0, 0.0)) < 1e-12);
+        let tilted = Circle::new(Vec3d::zero(), 1.5, Vec3d::new(1.0, -2.0, 0.5));
+        for step in 0..12 {
+            let vector = tilted.radius_vector_at(AngleRadians::two_pi() * (f64::from(step) / 12.0));
+            assert!((vector.magnitude() - 1.5).abs() < 1e-12);
+            assert!(vector.dot(&tilted.normal).abs() < 1e-12);
+        }
+        let along_x = Circle::new(Vec3d::zero(), 1.0, Vec3d::i());
+        assert!((along_x.radius_vector_at(1.0).magnitude() - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_try_new() {
+        assert!(Circle::try_new(Vec3d::zero(), 1.0, Vec3d::k()).is_ok());
+        assert_eq!(
+            Circle::try_new(Vec3d::zero(), 1.0, Vec3d::zero()),
+            Err(GeometryError::ZeroNormal)
+        );
+        assert_eq!(
+            Circle::try_new(Vec3d::zero(), -1.0, Vec3d::k()),
+            Err(GeometryError::NegativeRadius)
+        );
+        assert_eq!(
+            Circle::try_new(Vec3d::new(f64::NAN, 0.0, 0.0), 1.0, Vec3d::k()),
+            Err(GeometryError::NonFinite)
+        );
+        assert_eq!(
+            Circle::try_new(Vec3d::zero(), f64::INFINITY, Vec3d::k()),
+            Err(GeometryError::NonFinite)
+        );
+    }
+
+    #[test]
+    fn test_is_valid() {
+        assert!(Circle::new(Vec3d::zero(), -1.0, Vec3d::k()).is_valid());
+        assert!(!Circle::new(Vec3d::zero(), 1.0, Vec3d::zero()).is_valid());
+    }
+
+    #[test]
+    fn test_const_constructor() {
+        const UNIT: Circle = Circle::new_unchecked(Vec3d::zero(), 1.0, Vec3d::k());
+        assert_eq!(UNIT, Circle::new(Vec3d::zero(), -1.0, Vec3d::new(0.0, 0.0, 2.0)));
+    }
+
+    #[test]
+    fn test_sample() {
+        let circle = Circle::new(Vec3d::new(1.0, 2.0, 3.0), 2.0, Vec3d::new(1.0, 1.0, 0.0));
+        let points = circle.sample(12);
+        assert_eq!(points.len(), 12);
+        assert!((points[0] - (circle.center + circle.radius_vector_at(0.0))).magnitude() < 1e-12);
+        for point in &points {
+            assert!((point.distance_to(&circle.center) - 2.0).abs() < 1e-12);
+            assert!(circle.get_plane().distance_to_point(point).abs() < 1e-12);
+        }
+        assert!(circle.sample(0).is_empty());
+    }
+
+    #[test]
+    fn test_constructor_inputs() {
+        let expected = Circle::new(Vec3d::new(1.0, 2.0, 3.0), 2.0, Vec3d::k());
+        let (center, normal): (&Vec3d, &Vec3d) = (&expected.center, &expected.normal);
+        assert_eq!(Circle::new(center, 2.0, normal), expected);
+        assert_eq!(Circle::new_unchecked(*center, 2.0, *normal), expected);
+        assert_eq!(Circle::new([1.0, 2.0, 3.0], 2.0, (0.0, 0.0, 5.0)), expected);
+        assert_eq!(Circle::from_center_and_point((1.0, 2.0, 3.0), [3.0, 2.0, 3.0], Vec3d::k()), Ok(expected));
+        assert_eq!(Plane::from_point([0.0, 0.0, 1.0], (1.0, 2.0, 3.0)), expected.get_plane());
+    }
+
+    #[test]
+    fn test_from_str_round_trip() {
+        crate::testing::for_all(100, |circle: Circle| {
+            let parsed: Circle = circle.to_string().parse().unwrap();
+            assert_eq!((parsed.center, parsed.radius, parsed.normal), (circle.center, circle.radius, circle.normal));
+        });
+        let circle = Circle::new(Vec3d::zero(), 2.0, Vec3d::k());
+        assert_eq!(circle.to_string(), "circle c=(0, 0, 0) r=2 n=(0, 0, 1)");
+        assert_eq!(
+            "circle c=(0, 0, 0) n=(0, 0, 1)".parse::<Circle>(),
+            Err(ParseError::MissingField { field: "r", found: "n=(0,".to_string() })
+        );
+    }
+
+    #[test]
+    fn test_rotated_matches_rotated_points() {
+        let circle = Circle::new(Vec3d::new(1.0, 2.0, 0.5), 1.5, Vec3d::new(1.0, 0.0, 1.0));
+        let q = Quat::from_axis_angle(&Vec3d::k(), core::f64::consts::FRAC_PI_2);
+        let rotated = circle.rotated(&q);
+        assert!((rotated.normal - Vec3d::new(0.0, 1.0, 1.0).normalize()).magnitude() < 1e-15);
+        let plane = rotated.get_plane();
+        for point in circle.sample(12) {
+            let point = q.rotate(&point);
+            assert!(plane.distance_to_point(&point).abs() < 1e-12);
+            assert!((point.distance_to(&rotated.center) - rotated.radius).abs() < 1e-12);
+        }
+        let translated = circle.translated(&Vec3d::new(0.0, 0.0, 1.0));
+        assert_eq!((translated.center, translated.normal), (Vec3d::new(1.0, 2.0, 1.5), circle.normal));
+    }
+}
@@ -0,0 +1,160 @@
+use crate::geometry::ScaleGeometry;
+use crate::vec3d::Vec3d;
+
+/// A right circular cylinder in 3d space
+/// the cylinder can either be infinite along its axis or finite with flat end caps
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Cylinder {
+    /// A point on the axis of the cylinder, for finite cylinders this is the center of the base cap
+    pub origin: Vec3d,
+    /// The unit direction of the axis of the cylinder
+    pub axis: Vec3d,
+    /// The radius of the cylinder
+    pub radius: f64,
+    /// The height of the cylinder measured along the axis from the origin
+    /// None for an infinite cylinder
+    pub height: Option<f64>
+}
+
+impl Cylinder {
+    /// Create a new cylinder
+    /// a negative height extends the cylinder backwards along the axis
+    pub fn new(origin: impl Into<Vec3d>, axis: impl Into<Vec3d>, radius: f64, height: Option<f64>) -> Cylinder {
+        let origin = origin.into();
+        let axis = axis.into().normalize();
+        match height {
+            Some(height) if height < 0.0 => Cylinder {
+                origin: origin + axis * height,
+                axis,
+                radius: radius.abs(),
+                height: Some(-height)
+            },
+            _ => Cylinder {
+                origin,
+                axis,
+                radius: radius.abs(),
+                height
+            }
+        }
+    }
+
+    /// Create a new infinite cylinder
+    pub fn infinite(origin: &Vec3d, axis: &Vec3d, radius: f64) -> Cylinder {
+        Cylinder::new(origin, axis, radius, None)
+    }
+
+    /// Check if the cylinder is infinite
+    pub fn is_infinite(&self) -> bool {
+        self.height.is_none()
+    }
+
+    /// Get the center of the top cap of the cylinder
+    /// None for an infinite cylinder
+    pub fn top_center(&self) -> Option<Vec3d> {
+        self.height.map(|height| self.origin + self.axis * height)
+    }
+
+    /// Check if a point is inside the cylinder
+    /// points on the surface are considered inside
+    pub fn contains_point(&self, point: &Vec3d) -> bool {
+        let (height, radial) = self.decompose(point);
+        if radial.magnitude() > self.radius {
+            return false;
+        }
+        match self.height {
+            Some(max) => (0.0..=max).contains(&height),
+            None => true
+        }
+    }
+
+    /// Get the closest point on the surface of the cylinder to a point
+    /// for points on the axis of an infinite cylinder the direction to the surface is arbitrary
+    pub fn closest_point_to(&self, point: &Vec3d) -> Vec3d {
+        let (height, radial) = self.decompose(point);
+        let radial_distance = radial.magnitude();
+        let direction = if radial_distance == 0.0 {
+            self.axis.any_perpendicular()
+        } else {
+            radial / radial_distance
+        };
+        let Some(max) = self.height else {
+            return self.origin + self.axis * height + direction * self.radius;
+        };
+        if self.contains_point(point) {
+            let side_distance = self.radius - radial_distance;
+            let base_distance = height;
+            let top_distance = max - height;
+            if side_distance <= base_distance && side_distance <= top_distance {
+                self.origin + self.axis * height + direction * self.radius
+            } else if base_distance <= top_distance {
+                self.origin + radial
+            } else {
+                self.origin + self.axis * max + radial
+            }
+        } else {
+            self.origin + self.axis * height.clamp(0.0, max) + direction * radial_distance.min(self.radius)
+        }
+    }
+
+    /// Get the unsigned distance from a point to the surface of the cylinder
+    pub fn distance_to_point(&self, point: &Vec3d) -> f64 {
+        point.distance_to(&self.closest_point_to(point))
+    }
+
+    /// Split a point into its height along the axis and the radial vector from the axis to the point
+    fn decompose(&self, point: &Vec3d) -> (f64, Vec3d) {
+        let relative = point - self.origin;
+        let height = relative.dot(&self.axis);
+        (height, relative - self.axis * height)
+    }
+}
+
+impl ScaleGeometry for Cylinder {
+    fn scaled(&self, factor: f64) -> Self {
+        Cylinder::new(self.origin * factor, self.axis, self.radius * factor, self.height.map(|height| height * factor))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_contains_point() {
+        let cylinder = Cylinder::new(Vec3d::zero(), Vec3d::k(), 1.0, Some(2.0));
+        let infinite = Cylinder::infinite(&Vec3d::zero(), &Vec3d::k(), 1.0);
+        assert!(cylinder.contains_point(&Vec3d::new(0.5, 0.0, 1.0)));
+        assert!(cylinder.contains_point(&Vec3d::new(1.0, 0.0, 2.0)));
+        assert!(!cylinder.contains_point(&Vec3d::new(0.5, 0.0, 3.0)));
+        assert!(!cylinder.contains_point(&Vec3d::new(1.5, 0.0, 1.0)));
+        assert!(infinite.contains_point(&Vec3d::new(0.5, 0.0, -30.0)));
+    }
+
+    #[test]
+    fn test_negative_height() {
+        let cylinder = Cylinder::new(Vec3d::zero(), Vec3d::k(), 1.0, Some(-2.0));
+        assert_eq!(cylinder.origin, Vec3d::new(0.0, 0.0, -2.0));
+        assert_eq!(cylinder.height, Some(2.0));
+        assert!(cylinder.contains_point(&Vec3d::new(0.0, 0.0, -1.0)));
+    }
+
+    #[test]
+    fn test_closest_point_to() {
+        let cylinder = Cylinder::new(Vec3d::zero(), Vec3d::k(), 1.0, Some(2.0));
+        assert_eq!(cylinder.closest_point_to(&Vec3d::new(3.0, 0.0, 1.0)), Vec3d::new(1.0, 0.0, 1.0));
+        assert_eq!(cylinder.closest_point_to(&Vec3d::new(0.5, 0.0, 5.0)), Vec3d::new(0.5, 0.0, 2.0));
+        assert_eq!(cylinder.closest_point_to(&Vec3d::new(3.0, 0.0, -1.0)), Vec3d::new(1.0, 0.0, 0.0));
+        assert_eq!(cylinder.closest_point_to(&Vec3d::new(0.0, 0.5, 0.25)), Vec3d::new(0.0, 0.5, 0.0));
+        assert_eq!(cylinder.closest_point_to(&Vec3d::new(0.0, 0.75, 1.0)), Vec3d::new(0.0, 1.0, 1.0));
+    }
+
+    #[test]
+    fn test_distance_to_point() {
+        let cylinder = Cylinder::new(Vec3d::zero(), Vec3d::k(), 1.0, Some(2.0));
+        let infinite = Cylinder::infinite(&Vec3d::zero(), &Vec3d::k(), 1.0);
+        assert!((cylinder.distance_to_point(&Vec3d::new(4.0, 0.0, 1.0)) - 3.0).abs() < f64::EPSILON);
+        assert!((cylinder.distance_to_point(&Vec3d::new(0.0, 0.0, 1.0)) - 1.0).abs() < f64::EPSILON);
+        assert!((cylinder.distance_to_point(&Vec3d::new(0.0, 0.0, 2.5)) - 0.5).abs() < f64::EPSILON);
+        assert!((infinite.distance_to_point(&Vec3d::new(0.0, 0.0, 100.0)) - 1.0).abs() < f64::EPSILON);
+    }
+}
@@ -0,0 +1,89 @@
+//! Wavefront OBJ output so sampled geometry can be opened in any 3d viewer
+//! only vertices, points, and lines are written, there are no faces or materials
+
+use alloc::string::String;
+use core::fmt::Write;
+
+use crate::vec3d::Vec3d;
+
+/// A set of points to write as one OBJ element
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ObjElement<'a> {
+    /// Unconnected points, e.g. from `Sphere::sample_surface`
+    Points(&'a [Vec3d]),
+    /// Points joined in order by line segments
+    Polyline(&'a [Vec3d]),
+    /// Points joined in order with the last joined back to the first, e.g. from `Circle::sample`
+    ClosedPolyline(&'a [Vec3d])
+}
+
+/// Write elements as the text of an OBJ file
+/// every point is written as its own vertex, followed by a `p` or `l` statement referring to them
+/// elements with no points are left out
+pub fn to_obj(elements: &[ObjElement]) -> String {
+    let mut obj = String::new();
+    // OBJ vertex indices start at 1
+    let mut next_index = 1;
+    for element in elements {
+        let (points, statement, closed) = match element {
+            ObjElement::Points(points) => (points, "p", false),
+            ObjElement::Polyline(points) => (points, "l", false),
+            ObjElement::ClosedPolyline(points) => (points, "l", true)
+        };
+        if points.is_empty() {
+            continue;
+        }
+        for point in *points {
+            // Writing to a String cannot fail
+            let _ = writeln!(obj, "v {} {} {}", point.x, point.y, point.z);
+        }
+        obj.push_str(statement);
+        for index in next_index..next_index + points.len() {
+            let _ = write!(obj, " {index}");
+        }
+        if closed {
+            let _ = write!(obj, " {next_index}");
+        }
+        obj.push('\n');
+        next_index += points.len();
+    }
+    obj
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use super::*;
+    use crate::geometry::circle::Circle;
+    use crate::geometry::sphere::Sphere;
+
+    fn parse_vertices(obj: &str) -> Vec<Vec3d> {
+        obj.lines()
+            .filter_map(|line| line.strip_prefix("v "))
+            .map(|line| {
+                let values: Vec<f64> = line.split_whitespace().map(|value| value.parse().unwrap()).collect();
+                Vec3d::new(values[0], values[1], values[2])
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_point_cloud_round_trip() {
+        let points = Sphere::new(Vec3d::new(0.5, 0.0, -1.0), 2.0).sample_surface(50);
+        let obj = to_obj(&[ObjElement::Points(&points)]);
+        assert_eq!(parse_vertices(&obj), points);
+        assert_eq!(obj.lines().filter(|line| line.starts_with("p ")).count(), 1);
+    }
+
+    #[test]
+    fn test_polylines() {
+        let rim = Circle::new(Vec3d::zero(), 1.0, Vec3d::k()).sample(4);
+        let path = [Vec3d::zero(), Vec3d::k()];
+        let obj = to_obj(&[ObjElement::ClosedPolyline(&rim), ObjElement::Points(&[]), ObjElement::Polyline(&path)]);
+        assert_eq!(parse_vertices(&obj).len(), 6);
+        let lines: Vec<&str> = obj.lines().filter(|line| line.starts_with("l ")).collect();
+        assert_eq!(lines, ["l 1 2 3 4 1", "l 5 6"]);
+        assert_eq!(to_obj(&[]), "");
+    }
+}
@@ -1,66 +1,129 @@
+use alloc::vec::Vec;
+
+use crate::geometry::arc::Arc;
 use crate::geometry::circle::Circle;
+use crate::geometry::cylinder::Cylinder;
 use crate::geometry::plane::Plane;
+use crate::geometry::segment::Segment;
 use crate::geometry::sphere::Sphere;
+use crate::geometry::triangle::Triangle;
+#[cfg(not(any(feature = "std", test)))]
+use crate::math::Float;
 use crate::vec3d::Vec3d;
 
 /// Calculate the intersection of two spheres
 /// Returns the circle of intersection
 /// if the spheres are identical None is returned
 /// or None if the spheres do not intersect either because they are too far apart or one is contained within the other
-/// if the circles touch at a single point a degenerate circle is returned
+/// concentric spheres never intersect in a circle so they return None even when the radii match
+/// if the spheres touch at a single point, from outside or inside, a degenerate circle is returned
+/// radii below `Circle::DEGENERATE_TOLERANCE` are snapped to exactly 0 so spheres that touch up to rounding still give a point
 pub fn sphere_sphere(sphere1: &Sphere, sphere2: &Sphere) -> Option<Circle> {
     if sphere1 == sphere2 {
         return None;
     }
     let center_distance = sphere1.center.distance_to(&sphere2.center);
-    let radius_sum = sphere1.radius + sphere2.radius;
-    if center_distance > radius_sum {
+    // Concentric spheres would divide by zero below
+    if center_distance < f64::EPSILON {
         return None;
     }
+    let radius_sum = sphere1.radius + sphere2.radius;
     let radius_diff = (sphere1.radius - sphere2.radius).abs();
-    if center_distance < radius_diff {
+    let circle_normal = (sphere2.center - sphere1.center) / center_distance;
+    // Tangency is checked first so spheres that touch up to rounding are not rejected as apart or contained
+    let tolerance = f64::EPSILON * radius_sum;
+    let outside_tangent = (center_distance - radius_sum).abs() <= tolerance;
+    let inside_tangent = (center_distance - radius_diff).abs() <= tolerance;
+    if outside_tangent || inside_tangent {
+        // The spheres touch on the line through the centers, on the far side of the first sphere when it is the inner one
+        let side = if inside_tangent && sphere1.radius < sphere2.radius { -1.0 } else { 1.0 };
+        let point = sphere1.center + circle_normal * (side * sphere1.radius);
+        return Some(Circle::new(point, 0.0, circle_normal));
+    }
+    if center_distance > radius_sum || center_distance < radius_diff {
         return None;
     }
-    // let circle_radius = (sphere1.radius.powi(2) - sphere2.radius.powi(2) + center_distance.powi(2)) / (2.0 * center_distance);
-    // let circle_center = sphere1.center + (sphere2.center - sphere1.center) * (circle_radius / center_distance);
-
-    let h = 0.5 + (sphere1.radius.powi(2) - sphere2.radius.powi(2)) / (2.0 * center_distance.powi(2));
-    let radius_of_intersection = (sphere1.radius.powi(2) - h.powi(2) * center_distance.powi(2)).sqrt();
+    let h: f64 = 0.5 + (sphere1.radius.powi(2) - sphere2.radius.powi(2)) / (2.0 * center_distance.powi(2));
+    let radius_of_intersection = snap_radius(sphere1.radius.powi(2) - h.powi(2) * center_distance.powi(2));
     let center_of_intersection = sphere1.center + h * (sphere2.center - sphere1.center);
-    let circle_normal = (sphere2.center - sphere1.center).normalize();
-    Some(Circle::new(&center_of_intersection, radius_of_intersection, &circle_normal))
+    Some(Circle::new(center_of_intersection, radius_of_intersection, circle_normal))
 }
 
 /// Calculate the intersection of a sphere and a plane
 /// Returns the circle of intersection
 /// or None if the sphere does not intersect the plane
+/// a sphere that only touches the plane gives a degenerate circle at the touching point
+/// radii below `Circle::DEGENERATE_TOLERANCE` are snapped to exactly 0
 pub fn sphere_plane(sphere: &Sphere, plane: &Plane) -> Option<Circle> {
+    // Signed so the center is moved back along the normal from either side of the plane
     let distance = plane.distance_to_point(&sphere.center);
     if distance.abs() > sphere.radius {
         return None;
     }
-    if (distance.abs() - sphere.radius).abs() < f64::EPSILON {
-        let circle_center = sphere.center - plane.normal * distance;
-        return Some(Circle::new(&circle_center, 0.0, &plane.normal));
+    if distance.abs() < f64::EPSILON {
+        return Some(Circle::new(sphere.center, sphere.radius, plane.normal));
     }
-    let circle_radius = (sphere.radius.powi(2) - distance.powi(2)).sqrt();
-    // WARN: idk why this needs to be the way it is
     let circle_center = sphere.center - plane.normal * distance;
-    // dbg!(sphere, circle_radius, circle_center, plane.normal * distance, plane, distance);
-    Some(Circle::new(&circle_center, circle_radius, &plane.normal))
+    let circle_radius = snap_radius(sphere.radius.powi(2) - distance.powi(2));
+    Some(Circle::new(circle_center, circle_radius, plane.normal))
+}
+
+/// Take the square root of a squared radius, snapping radii within the degenerate tolerance of 0 to exactly 0
+/// rounding can also leave the square slightly negative for touching shapes
+fn snap_radius(radius_squared: f64) -> f64 {
+    let radius = radius_squared.max(0.0).sqrt();
+    if radius < Circle::DEGENERATE_TOLERANCE {
+        0.0
+    } else {
+        radius
+    }
+}
+
+/// The two points where a pair of circles cross
+/// for circles that only touch both points are the same
+///
+/// the points are ordered relative to the line from the center of the first circle to the center of the second,
+/// viewed with the normal of the first circle pointing towards the viewer
+/// `first` is on the right of that line and `second` is on the left
+/// so the order only depends on the centers and the first normal, never on the normal of the second circle
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct IntersectionPoints {
+    first: Vec3d,
+    second: Vec3d
+}
+
+impl IntersectionPoints {
+    /// Get the point on the right of the line between the centers
+    pub fn first(&self) -> Vec3d {
+        self.first
+    }
+
+    /// Get the point on the left of the line between the centers
+    pub fn second(&self) -> Vec3d {
+        self.second
+    }
+
+    /// Check if the circles only touch at a single point
+    pub fn is_tangent(&self) -> bool {
+        self.first == self.second
+    }
+}
+
+impl From<IntersectionPoints> for (Vec3d, Vec3d) {
+    fn from(points: IntersectionPoints) -> (Vec3d, Vec3d) {
+        (points.first, points.second)
+    }
 }
 
 /// Calculate the intersection of two circles
 /// DOES NOT CALCULATE INTERSECTION BETWEEN OUT OF PLANE CIRCLES
 /// Returns one, two, or no points of intersection
-/// The points are returned as a tuple of two Vec3d
+/// the points are ordered as described on `IntersectionPoints`
 /// If the circles do not intersect, None is returned
 /// If the circles intersect at one point, the same point is returned twice
-/// If the circles intersect at two points,
 /// if the circles are identical and have infinite points of intersection, None is returned
-pub fn circle_circle(circle1: &Circle, circle2: &Circle) -> Option<(Vec3d, Vec3d)> {
-    // dbg!(circle1);
-    // dbg!(circle2);
+/// concentric circles never cross so they return None even when the radii match
+pub fn circle_circle(circle1: &Circle, circle2: &Circle) -> Option<IntersectionPoints> {
     if circle1 == circle2 {
         return None;
     }
@@ -68,6 +131,10 @@ pub fn circle_circle(circle1: &Circle, circle2: &Circle) -> Option<(Vec3d, Vec3d
         return None;
     }
     let center_distance = circle1.center.distance_to(&circle2.center);
+    // Concentric circles would divide by zero below
+    if center_distance < f64::EPSILON {
+        return None;
+    }
     let radius_sum = circle1.radius + circle2.radius;
     if center_distance > radius_sum {
         return None;
@@ -76,30 +143,181 @@ pub fn circle_circle(circle1: &Circle, circle2: &Circle) -> Option<(Vec3d, Vec3d
     if center_distance < radius_diff {
         return None;
     }
-    let h = 0.5 + (circle1.radius.powi(2) - circle2.radius.powi(2)) / (2.0 * center_distance.powi(2));
-    let radius_of_intersection = (circle1.radius.powi(2) - h.powi(2) * center_distance.powi(2)).sqrt();
-    let t = (circle2.center - circle1.center).cross(&circle2.normal).normalize();
+    let h: f64 = 0.5 + (circle1.radius.powi(2) - circle2.radius.powi(2)) / (2.0 * center_distance.powi(2));
+    let radius_of_intersection = snap_radius(circle1.radius.powi(2) - h.powi(2) * center_distance.powi(2));
+    // Points to the right of the center line when looking down the first normal
+    let t = (circle2.center - circle1.center).cross(&circle1.normal).try_normalize().ok()?;
     let center_of_intersection = circle1.center + h * (circle2.center - circle1.center);
-    let point1 = center_of_intersection + t * radius_of_intersection;
-    let point2 = center_of_intersection - t * radius_of_intersection;
-    Some((point1, point2))
+    if radius_of_intersection == 0.0 {
+        return Some(IntersectionPoints { first: center_of_intersection, second: center_of_intersection });
+    }
+    Some(IntersectionPoints {
+        first: center_of_intersection + t * radius_of_intersection,
+        second: center_of_intersection - t * radius_of_intersection
+    })
 }
 
 /// Calculate the intersection of a sphere and a circle
 /// Returns none if there is no intersection or the intersection is the entire circle
 /// if there is one point of intersection it is returned twice
-pub fn sphere_circle(sphere: &Sphere, circle: &Circle) -> Option<(Vec3d, Vec3d)> {
+/// a sphere that touches the plane of the circle within `Circle::DEGENERATE_TOLERANCE` is treated as touching at one point
+/// the points are ordered as in `circle_circle` with the given circle first
+/// and the circle where the sphere cuts its plane second
+pub fn sphere_circle(sphere: &Sphere, circle: &Circle) -> Option<IntersectionPoints> {
     let circle_plane = circle.get_plane();
     let sphere_circle = sphere_plane(sphere, &circle_plane)?;
     if sphere_circle.is_degenerate() {
         let intersection_distance = circle.center.distance_to(&sphere_circle.center);
-        return if (intersection_distance - circle.radius).abs() < f64::EPSILON {
-            Some((sphere_circle.center, sphere_circle.center))
+        return if (intersection_distance - circle.radius).abs() < Circle::DEGENERATE_TOLERANCE {
+            Some(IntersectionPoints { first: sphere_circle.center, second: sphere_circle.center })
         } else {
             None
         }
     }
-    circle_circle(&sphere_circle, circle)
+    circle_circle(circle, &sphere_circle)
+}
+
+/// How a pair of spheres, or a pair of coplanar circles, are arranged
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Classification {
+    /// The surfaces cross
+    Intersecting,
+    /// The surfaces touch at a single point, from outside or inside
+    Tangent,
+    /// The shapes are separated
+    Apart,
+    /// One shape is inside the other without touching it
+    Contained,
+    /// The centers are the same, including identical shapes
+    Concentric
+}
+
+/// Closest approach information for a pair of spheres or circles
+/// the classification always agrees with whether the plain intersection function finds an intersection
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct IntersectionAnalysis {
+    /// How the shapes are arranged
+    pub classification: Classification,
+    /// The distance between the centers minus the sum of the radii
+    /// positive when the shapes are apart and negative by the overlap depth otherwise
+    pub gap: f64,
+    /// The closest points on the first and second surface along the line through the centers
+    /// for overlapping shapes these are the deepest points, each inside the other shape
+    /// None for concentric shapes as there is no line through the centers
+    pub closest_points: Option<(Vec3d, Vec3d)>,
+    /// For one shape inside the other, how far the inner surface is from the containing surface at its closest
+    /// None otherwise
+    pub containment_margin: Option<f64>
+}
+
+/// Build the analysis for two spheres or circles once they have been classified
+fn analyze(
+    center1: &Vec3d,
+    radius1: f64,
+    center2: &Vec3d,
+    radius2: f64,
+    classification: Classification
+) -> IntersectionAnalysis {
+    let center_distance = center1.distance_to(center2);
+    let radius_sum = radius1 + radius2;
+    let radius_diff = (radius1 - radius2).abs();
+    let gap = center_distance - radius_sum;
+    if classification == Classification::Concentric {
+        return IntersectionAnalysis { classification, gap, closest_points: None, containment_margin: Some(radius_diff) };
+    }
+    let direction = (center2 - center1) / center_distance;
+    let internal = match classification {
+        Classification::Contained => true,
+        Classification::Tangent => (center_distance - radius_diff).abs() < (center_distance - radius_sum).abs(),
+        _ => false
+    };
+    if !internal {
+        return IntersectionAnalysis {
+            classification,
+            gap,
+            closest_points: Some((center1 + direction * radius1, center2 - direction * radius2)),
+            containment_margin: None
+        };
+    }
+    // Both closest points are on the side of the inner shape away from the outer center
+    let side = if radius1 < radius2 { -1.0 } else { 1.0 };
+    IntersectionAnalysis {
+        classification,
+        gap,
+        closest_points: Some((center1 + direction * (side * radius1), center2 + direction * (side * radius2))),
+        containment_margin: Some(radius_diff - center_distance)
+    }
+}
+
+/// Classify a pair that did not intersect by how far apart the centers are
+fn classify_miss(center1: &Vec3d, radius1: f64, center2: &Vec3d, radius2: f64) -> Classification {
+    let center_distance = center1.distance_to(center2);
+    if center_distance < f64::EPSILON {
+        Classification::Concentric
+    } else if center_distance > radius1 + radius2 {
+        Classification::Apart
+    } else {
+        Classification::Contained
+    }
+}
+
+/// Analyze how two spheres are arranged, see `sphere_sphere` for the intersection itself
+pub fn sphere_sphere_verbose(sphere1: &Sphere, sphere2: &Sphere) -> IntersectionAnalysis {
+    let classification = match sphere_sphere(sphere1, sphere2) {
+        Some(circle) if circle.is_degenerate() => Classification::Tangent,
+        Some(_) => Classification::Intersecting,
+        None => classify_miss(&sphere1.center, sphere1.radius, &sphere2.center, sphere2.radius)
+    };
+    analyze(&sphere1.center, sphere1.radius, &sphere2.center, sphere2.radius, classification)
+}
+
+/// Analyze how two circles are arranged in their shared plane, see `circle_circle` for the intersection itself
+/// Returns None if the circles are not in the same plane
+pub fn circle_circle_verbose(circle1: &Circle, circle2: &Circle) -> Option<IntersectionAnalysis> {
+    if !circle1.in_same_plane(circle2) {
+        return None;
+    }
+    let classification = match circle_circle(circle1, circle2) {
+        Some(points) if points.is_tangent() => Classification::Tangent,
+        Some(_) => Classification::Intersecting,
+        None => classify_miss(&circle1.center, circle1.radius, &circle2.center, circle2.radius)
+    };
+    Some(analyze(&circle1.center, circle1.radius, &circle2.center, circle2.radius, classification))
+}
+
+/// The part of a circle on the positive side of a plane
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CircleClipResult {
+    /// The whole circle is on the positive side, or touches the plane from it
+    Whole,
+    /// No part of the circle is on the positive side
+    Empty,
+    /// The circle crosses the plane and the arc between the crossings is on the positive side
+    /// a circle that touches the plane from the negative side gives a degenerate arc at the touching point
+    Arc(Arc)
+}
+
+/// Calculate the part of a circle on the side of a plane its normal points towards
+/// points within 1e-12 of the plane, relative to the radius of the circle, are treated as on it
+pub fn circle_halfspace(circle: &Circle, plane: &Plane) -> CircleClipResult {
+    let (u, v) = circle.get_plane().basis();
+    // The signed distance around the circle is center + amplitude * cos(angle - phase)
+    let center = plane.distance_to_point(&circle.center);
+    let (a, b) = (plane.normal.dot(&u) * circle.radius, plane.normal.dot(&v) * circle.radius);
+    let amplitude = a.hypot(b);
+    let tolerance = 1e-12 * circle.radius.max(1.0);
+    if center - amplitude >= -tolerance {
+        return CircleClipResult::Whole;
+    }
+    if center + amplitude < -tolerance {
+        return CircleClipResult::Empty;
+    }
+    let phase = b.atan2(a);
+    if center + amplitude <= tolerance {
+        return CircleClipResult::Arc(Arc::new(circle, phase, 0.0));
+    }
+    let half_width = (-center / amplitude).clamp(-1.0, 1.0).acos();
+    CircleClipResult::Arc(Arc::new(circle, phase - half_width, 2.0 * half_width))
 }
 
 /// Calculate the intersection of a line and a plane
@@ -107,9 +325,110 @@ pub fn sphere_circle(sphere: &Sphere, circle: &Circle) -> Option<(Vec3d, Vec3d)>
 /// Line is defined by two points
 pub fn plane_line(plane: &Plane, a: &Vec3d, b: &Vec3d) -> Option<Vec3d> {
     let ba = b - a;
-    let t = (plane.distance - plane.normal.x * a.x + plane.normal.y * a.y + plane.normal.z * a.z) /
-        (plane.normal.x * ba.x + plane.normal.y * ba.y + plane.normal.z * ba.z);
-    Some(a + t * (b - a))
+    let denominator = plane.normal.dot(&ba);
+    if denominator.abs() < f64::EPSILON {
+        return None;
+    }
+    let t = -plane.distance_to_point(a) / denominator;
+    Some(a + t * ba)
+}
+
+/// The intersection of a plane and a triangle
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PlaneTriangleResult {
+    /// The triangle is entirely on one side of the plane
+    None,
+    /// The plane crosses the triangle along a segment
+    /// a triangle that only touches the plane at a vertex gives a segment with the same start and end
+    Segment(Segment),
+    /// The triangle lies in the plane
+    Coplanar
+}
+
+/// Calculate the intersection of a plane and a triangle
+/// vertices within 1e-12 of the plane are treated as exactly on it,
+/// so a vertex on the plane is an endpoint of the segment rather than producing a tiny extra crossing
+pub fn plane_triangle(plane: &Plane, triangle: &Triangle) -> PlaneTriangleResult {
+    const ON_PLANE: f64 = 1e-12;
+    let vertices = [triangle.a, triangle.b, triangle.c];
+    let distances = vertices.map(|vertex| {
+        let distance = plane.distance_to_point(&vertex);
+        if distance.abs() <= ON_PLANE { 0.0 } else { distance }
+    });
+    if distances.iter().all(|distance| *distance == 0.0) {
+        return PlaneTriangleResult::Coplanar;
+    }
+    if distances.iter().all(|distance| *distance > 0.0) || distances.iter().all(|distance| *distance < 0.0) {
+        return PlaneTriangleResult::None;
+    }
+    // At most two points are found, vertices on the plane and edges whose ends are on opposite sides
+    let mut points = [Vec3d::zero(); 2];
+    let mut count = 0;
+    for index in 0..3 {
+        let next = (index + 1) % 3;
+        if distances[index] == 0.0 {
+            points[count] = vertices[index];
+            count += 1;
+        } else if distances[index] * distances[next] < 0.0 {
+            let t = distances[index] / (distances[index] - distances[next]);
+            points[count] = vertices[index] + (vertices[next] - vertices[index]) * t;
+            count += 1;
+        }
+    }
+    let end = if count == 1 { points[0] } else { points[1] };
+    PlaneTriangleResult::Segment(Segment::new(points[0], end))
+}
+
+/// Check if a sphere overlaps a triangle, including its interior
+/// a sphere that only touches the triangle counts as overlapping
+pub fn sphere_triangle(sphere: &Sphere, triangle: &Triangle) -> bool {
+    triangle.closest_point(&sphere.center).distance_to(&sphere.center) <= sphere.radius
+}
+
+/// Calculate the intersection of a cylinder and a line
+/// Line is defined by two points
+/// Returns the points where the line crosses the surface of the cylinder, including the end caps of finite cylinders
+/// The points are ordered along the line from a towards b
+/// If the line touches the cylinder at one point, the same point is returned twice
+/// Returns none if there is no intersection or the line lies in the curved surface of the cylinder
+pub fn cylinder_line(cylinder: &Cylinder, a: &Vec3d, b: &Vec3d) -> Option<(Vec3d, Vec3d)> {
+    let direction = b - a;
+    let relative = a - cylinder.origin;
+    let direction_radial = direction - cylinder.axis * direction.dot(&cylinder.axis);
+    let relative_radial = relative - cylinder.axis * relative.dot(&cylinder.axis);
+    let mut hits: Vec<f64> = Vec::new();
+
+    let quad_a = direction_radial.dot(&direction_radial);
+    if quad_a > f64::EPSILON {
+        let quad_b = 2.0 * relative_radial.dot(&direction_radial);
+        let quad_c = relative_radial.dot(&relative_radial) - cylinder.radius.powi(2);
+        let discriminant = quad_b.powi(2) - 4.0 * quad_a * quad_c;
+        if discriminant >= 0.0 {
+            let root = discriminant.sqrt();
+            for t in [(-quad_b - root) / (2.0 * quad_a), (-quad_b + root) / (2.0 * quad_a)] {
+                let height = (relative + direction * t).dot(&cylinder.axis);
+                if cylinder.height.is_none_or(|max| (0.0..=max).contains(&height)) {
+                    hits.push(t);
+                }
+            }
+        }
+    }
+
+    if let Some(top_center) = cylinder.top_center() {
+        for cap_center in [cylinder.origin, top_center] {
+            let cap = Circle::new(cap_center, cylinder.radius, cylinder.axis);
+            if let Some(point) = plane_line(&cap.get_plane(), a, b) {
+                if point.distance_to(&cap.center) <= cap.radius {
+                    hits.push((point - a).dot(&direction) / direction.dot(&direction));
+                }
+            }
+        }
+    }
+
+    hits.sort_by(f64::total_cmp);
+    let first = *hits.first()?;
+    let last = *hits.last()?;
+    Some((a + direction * first, a + direction * last))
 }
 
 #[cfg(test)]
@@ -125,25 +444,25 @@ mod tests {
         let center1 = Vec3d::new(1.0, 1.0, 1.0);
         let center2 = Vec3d::new(-1.0, 1.0, 1.0);
         let center3 = Vec3d::new(0.0, 0.0, -1.0);
-        let sphere1 = Sphere::new(&center1, 1.0);
-        let sphere2 = Sphere::new(&center2, 1.0);
-        let sphere3 = Sphere::new(&center3, 2.0);
-        let sphere4 = Sphere::new(&-center3, 2.0);
+        let sphere1 = Sphere::new(center1, 1.0);
+        let sphere2 = Sphere::new(center2, 1.0);
+        let sphere3 = Sphere::new(center3, 2.0);
+        let sphere4 = Sphere::new(-center3, 2.0);
         let floating_point_error = 0.0000000000000002;
         assert_eq!(
             sphere_sphere(&sphere1, &sphere2).unwrap(),
             Circle::new(
-                &Vec3d::new(0.0, 1.0, 1.0),
+                Vec3d::new(0.0, 1.0, 1.0),
                 0.0,
-                &Vec3d::i()
+                Vec3d::i()
             )
         );
         assert_eq!(
             sphere_sphere(&sphere3, &sphere4).unwrap(),
             Circle::new(
-                &Vec3d::zero(),
+                Vec3d::zero(),
                 2.0 * AngleRadians::sixth_pi().cos() - floating_point_error,
-                &Vec3d::k()
+                Vec3d::k()
             )
         );
     }
@@ -151,36 +470,65 @@ mod tests {
     #[test]
     fn test_sphere_plane_intersection() {
         let center = Vec3d::new(0.0, 0.0, 1.0);
-        let sphere = Sphere::new(&center, 1.0);
-        let plane1 = Plane::new(&Vec3d::k(), 0.0);
-        let plane2 = Plane::new(&Vec3d::k(), 1.0);
+        let sphere = Sphere::new(center, 1.0);
+        let plane1 = Plane::new(Vec3d::k(), 0.0);
+        // The plane z = 1 through the center of the sphere
+        let plane2 = Plane::new(Vec3d::k(), -1.0);
         assert_eq!(
             sphere_plane(&sphere, &plane1).unwrap(),
             Circle::new(
-                &Vec3d::new(0.0, 0.0, 0.0),
+                Vec3d::new(0.0, 0.0, 0.0),
                 0.0,
-                &Vec3d::k()
+                Vec3d::k()
             )
         );
         assert_eq!(
             sphere_plane(&sphere, &plane2).unwrap(),
             Circle::new(
-                &Vec3d::new(0.0, 0.0, 1.0),
+                Vec3d::new(0.0, 0.0, 1.0),
                 1.0,
-                &Vec3d::k()
+                Vec3d::k()
             )
         );
     }
 
+    #[test]
+    fn test_sphere_plane_oblique() {
+        let point = Vec3d::new(1.0, -1.0, 2.0);
+        let plane = Plane::from_point(Vec3d::new(1.0, 2.0, 2.0), point);
+        for offset in [0.6, -0.6] {
+            let sphere = Sphere::new(point + Vec3d::k() * offset, 1.0);
+            let distance = plane.distance_to_point(&sphere.center);
+            let circle = sphere_plane(&sphere, &plane).unwrap();
+            assert!(plane.distance_to_point(&circle.center).abs() < 1e-12);
+            assert!(circle.center.distance_to(&(sphere.center - plane.normal * distance)) < 1e-12);
+            assert!((circle.radius - (1.0 - distance.powi(2)).sqrt()).abs() < 1e-12);
+            assert!((circle.center.distance_to(&sphere.center).powi(2) + circle.radius.powi(2) - 1.0).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_sphere_plane_below() {
+        let plane = Plane::new(Vec3d::k(), 0.0);
+        let below = Sphere::new(Vec3d::new(0.0, 0.0, -0.5), 1.0);
+        let circle = sphere_plane(&below, &plane).unwrap();
+        assert!(circle.center.distance_to(&Vec3d::zero()) < 1e-12);
+        assert!((circle.radius - 0.75_f64.sqrt()).abs() < 1e-12);
+        assert!(sphere_plane(&Sphere::new(Vec3d::new(0.0, 0.0, -2.0), 1.0), &plane).is_none());
+        let tangent = sphere_plane(&Sphere::new(Vec3d::new(0.0, 0.0, -1.0), 1.0), &plane).unwrap();
+        assert!(tangent.is_degenerate());
+        assert_eq!(tangent.center, Vec3d::zero());
+    }
+
     #[test]
     fn test_circle_circle_intersection() {
         let center1 = Vec3d::new(0.0, 0.0, 1.0);
         let center2 = Vec3d::new(0.0, 0.0, 0.0);
-        let circle1 = Circle::new(&center1, 1.0, &Vec3d::i());
-        let circle2 = Circle::new(&center2, 1.0, &Vec3d::i());
-        let circle3 = Circle::new(&center2, 1.0, &Vec3d::j());
+        let circle1 = Circle::new(center1, 1.0, Vec3d::i());
+        let circle2 = Circle::new(center2, 1.0, Vec3d::i());
+        let circle3 = Circle::new(center2, 1.0, Vec3d::j());
         assert_eq!(
-            circle_circle(&circle1, &circle2).unwrap(),
+            <(Vec3d, Vec3d)>::from(circle_circle(&circle1, &circle2).unwrap()),
             (
                 Vec3d::new(0.0, 3.0_f64.sqrt() / -2.0, 0.5),
                 Vec3d::new(0.0, 3.0_f64.sqrt() / 2.0, 0.5)
@@ -192,22 +540,411 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_circle_circle_concentric() {
+        let circle1 = Circle::new(Vec3d::zero(), 1.0, Vec3d::k());
+        let circle2 = Circle::new(Vec3d::zero(), 2.0, Vec3d::k());
+        let flipped = Circle::new(Vec3d::zero(), 1.0, -Vec3d::k());
+        assert_eq!(circle_circle(&circle1, &circle2), None);
+        assert_eq!(circle_circle(&circle2, &circle1), None);
+        assert_eq!(circle_circle(&circle1, &flipped), None);
+    }
+
+    #[test]
+    fn test_circle_circle_externally_tangent() {
+        let circle1 = Circle::new(Vec3d::zero(), 1.0, Vec3d::k());
+        let circle2 = Circle::new(Vec3d::new(3.0, 0.0, 0.0), 2.0, Vec3d::k());
+        let (point1, point2) = circle_circle(&circle1, &circle2).unwrap().into();
+        assert!(point1.distance_to(&Vec3d::i()) < 1e-12);
+        assert!(point2.distance_to(&Vec3d::i()) < 1e-12);
+        // 0.1 + 0.2 rounds so the tangent point is computed from a slightly negative square
+        let circle3 = Circle::new(Vec3d::new(0.3, 0.0, 0.0), 0.2, Vec3d::k());
+        let circle4 = Circle::new(Vec3d::zero(), 0.1, Vec3d::k());
+        let (point1, point2) = circle_circle(&circle4, &circle3).unwrap().into();
+        assert!(point1.is_finite() && point2.is_finite());
+        assert!(point1.distance_to(&point2) < 1e-7);
+    }
+
+    #[test]
+    fn test_circle_circle_flipped_normals() {
+        let circle1 = Circle::new(Vec3d::new(0.0, 0.0, 2.0), 1.0, Vec3d::k());
+        let circle2 = Circle::new(Vec3d::new(1.0, 0.0, 2.0), 1.0, -Vec3d::k());
+        let (point1, point2) = circle_circle(&circle1, &circle2).unwrap().into();
+        let half_root_three = 3.0_f64.sqrt() / 2.0;
+        let mut ys = [point1.y, point2.y];
+        ys.sort_by(f64::total_cmp);
+        assert!((ys[0] + half_root_three).abs() < 1e-12);
+        assert!((ys[1] - half_root_three).abs() < 1e-12);
+        for point in [point1, point2] {
+            assert!((point.x - 0.5).abs() < 1e-12);
+            assert!((point.z - 2.0).abs() < 1e-12);
+        }
+        let (flipped1, flipped2) = circle_circle(&circle2, &circle1).unwrap().into();
+        assert!(flipped1.distance_to(&point1) < 1e-12 || flipped1.distance_to(&point2) < 1e-12);
+        assert!(flipped2.distance_to(&point1) < 1e-12 || flipped2.distance_to(&point2) < 1e-12);
+    }
+
+    #[test]
+    fn test_circle_circle_ordering() {
+        let half_root_three = 3.0_f64.sqrt() / 2.0;
+        let lower = Vec3d::new(0.5, -half_root_three, 0.0);
+        let upper = Vec3d::new(0.5, half_root_three, 0.0);
+        let circle1 = Circle::new(Vec3d::zero(), 1.0, Vec3d::k());
+        let circle2 = Circle::new(Vec3d::i(), 1.0, Vec3d::k());
+        let flipped1 = Circle::new(Vec3d::zero(), 1.0, -Vec3d::k());
+        let flipped2 = Circle::new(Vec3d::i(), 1.0, -Vec3d::k());
+        assert_points_close(circle_circle(&circle1, &circle2).unwrap().into(), (lower, upper));
+        assert_points_close(circle_circle(&circle1, &flipped2).unwrap().into(), (lower, upper));
+        assert_points_close(circle_circle(&circle2, &circle1).unwrap().into(), (upper, lower));
+        assert_points_close(circle_circle(&flipped1, &circle2).unwrap().into(), (upper, lower));
+    }
+
+    #[test]
+    fn test_circle_circle_ordering_symmetric() {
+        let circle1 = Circle::new(Vec3d::zero(), 2.0_f64.sqrt(), Vec3d::k());
+        let circle2 = Circle::new(Vec3d::new(0.0, 2.0, 0.0), 2.0_f64.sqrt(), Vec3d::k());
+        let points = circle_circle(&circle1, &circle2).unwrap();
+        assert!(points.first().distance_to(&Vec3d::new(1.0, 1.0, 0.0)) < 1e-12);
+        assert!(points.second().distance_to(&Vec3d::new(-1.0, 1.0, 0.0)) < 1e-12);
+        assert!(!points.is_tangent());
+        let normal = Vec3d::new(1.0, 1.0, 1.0);
+        let oblique1 = Circle::new(Vec3d::zero(), 1.0, normal);
+        let oblique2 = Circle::new(Vec3d::new(1.0, -1.0, 0.0), 1.0, normal);
+        let offset = Vec3d::new(-1.0, -1.0, 2.0) / 12.0_f64.sqrt();
+        let midpoint = Vec3d::new(0.5, -0.5, 0.0);
+        assert_points_close(circle_circle(&oblique1, &oblique2).unwrap().into(), (midpoint + offset, midpoint - offset));
+    }
+
+    #[test]
+    fn test_circle_circle_tangent_points() {
+        let circle1 = Circle::new(Vec3d::zero(), 1.0, Vec3d::k());
+        let circle2 = Circle::new(Vec3d::new(2.0, 0.0, 0.0), 1.0, Vec3d::k());
+        let points = circle_circle(&circle1, &circle2).unwrap();
+        assert!(points.is_tangent());
+        assert_eq!(points.first(), Vec3d::i());
+    }
+
+    #[test]
+    fn test_sphere_circle_ordering() {
+        let sphere = Sphere::new(Vec3d::i(), 1.0);
+        let circle = Circle::new(Vec3d::zero(), 1.0, Vec3d::k());
+        let half_root_three = 3.0_f64.sqrt() / 2.0;
+        assert_points_close(
+            sphere_circle(&sphere, &circle).unwrap().into(),
+            (
+                Vec3d::new(0.5, -half_root_three, 0.0),
+                Vec3d::new(0.5, half_root_three, 0.0)
+            )
+        );
+    }
+
     #[test]
     fn test_sphere_circle_intersection() {
         let center = Vec3d::new(0.0, 0.0, 1.0);
-        let sphere = Sphere::new(&center, 1.0);
-        let circle1 = Circle::new(&Vec3d::new(1.0, 0.0, 0.0), 1.0, &Vec3d::k());
-        let circle2 = Circle::new(&Vec3d::new(1.0, 0.0, 1.0), 1.0, &Vec3d::k());
+        let sphere = Sphere::new(center, 1.0);
+        let circle1 = Circle::new(Vec3d::new(1.0, 0.0, 0.0), 1.0, Vec3d::k());
+        let circle2 = Circle::new(Vec3d::new(1.0, 0.0, 1.0), 1.0, Vec3d::k());
         assert_eq!(
-            sphere_circle(&sphere, &circle1).unwrap(),
+            <(Vec3d, Vec3d)>::from(sphere_circle(&sphere, &circle1).unwrap()),
             (
                 Vec3d::new(0.0, 0.0, 0.0),
                 Vec3d::new(0.0, 0.0, 0.0)
             )
         );
+        // circle2 lies in the plane through the center of the sphere and crosses its great circle twice
+        let half_root_three = 3.0_f64.sqrt() / 2.0;
+        assert_points_close(
+            sphere_circle(&sphere, &circle2).unwrap().into(),
+            (
+                Vec3d::new(0.5, half_root_three, 1.0),
+                Vec3d::new(0.5, -half_root_three, 1.0)
+            )
+        );
+        let far = Circle::new(Vec3d::new(3.0, 0.0, 1.0), 1.0, Vec3d::k());
+        assert_eq!(sphere_circle(&sphere, &far), None);
+    }
+
+    #[test]
+    fn test_sphere_sphere_concentric() {
+        let sphere1 = Sphere::new(Vec3d::new(1.0, 2.0, 3.0), 1.0);
+        let sphere2 = Sphere::new(Vec3d::new(1.0, 2.0, 3.0), 2.0);
+        assert_eq!(sphere_sphere(&sphere1, &sphere1), None);
+        assert_eq!(sphere_sphere(&sphere1, &sphere2), None);
+        assert_eq!(sphere_sphere(&sphere2, &sphere1), None);
+        let rounded = Sphere::new(Vec3d::new(1.0, 2.0, 3.0), 0.1 + 0.2);
+        assert_eq!(sphere_sphere(&rounded, &Sphere::new(Vec3d::new(1.0, 2.0, 3.0), 0.3)), None);
+    }
+
+    #[test]
+    fn test_sphere_sphere_tangent() {
+        let sphere1 = Sphere::new(Vec3d::zero(), 1.0);
+        let outside = Sphere::new(Vec3d::new(3.0, 0.0, 0.0), 2.0);
+        let circle = sphere_sphere(&sphere1, &outside).unwrap();
+        assert_eq!(circle, Circle::new(Vec3d::i(), 0.0, Vec3d::i()));
+        let inside = Sphere::new(Vec3d::new(0.0, 0.5, 0.0), 0.5);
+        assert_eq!(sphere_sphere(&sphere1, &inside).unwrap(), Circle::new(Vec3d::j(), 0.0, Vec3d::j()));
+        assert_eq!(sphere_sphere(&inside, &sphere1).unwrap(), Circle::new(Vec3d::j(), 0.0, Vec3d::j()));
+    }
+
+    #[test]
+    fn test_sphere_sphere_tangent_with_rounding() {
+        // 0.1 + 0.2 is slightly more than 0.3 so the spheres overlap by a rounding error
+        let sphere1 = Sphere::new(Vec3d::zero(), 0.1);
+        let sphere2 = Sphere::new(Vec3d::new(0.3, 0.0, 0.0), 0.2);
+        let circle = sphere_sphere(&sphere1, &sphere2).unwrap();
+        assert!(circle.is_degenerate());
+        assert!(circle.is_degenerate_with_tolerance(0.0));
+        assert!(circle.center.distance_to(&Vec3d::new(0.1, 0.0, 0.0)) < 1e-12);
+    }
+
+    #[test]
+    fn test_sphere_circle_tangent_with_rounding() {
+        // The radius of 0.1 + 0.2 reaches slightly past the plane of the circle 0.3 below the center
+        let sphere = Sphere::new(Vec3d::new(0.0, 0.0, 0.3), 0.1 + 0.2);
+        let circle = Circle::new(Vec3d::new(1.0, 0.0, 0.0), 1.0, Vec3d::k());
+        let points = sphere_circle(&sphere, &circle).unwrap();
+        assert!(points.is_tangent());
+        assert!(points.first().distance_to(&Vec3d::zero()) < 1e-12);
+    }
+
+    fn assert_points_close(actual: (Vec3d, Vec3d), expected: (Vec3d, Vec3d)) {
+        assert!(actual.0.distance_to(&expected.0) < 1e-12, "{} != {}", actual.0, expected.0);
+        assert!(actual.1.distance_to(&expected.1) < 1e-12, "{} != {}", actual.1, expected.1);
+    }
+
+    #[test]
+    fn test_plane_line_intersection() {
+        let plane = Plane::from_point(Vec3d::k(), Vec3d::new(0.0, 0.0, 2.0));
+        assert_eq!(
+            plane_line(&plane, &Vec3d::new(1.0, 1.0, 0.0), &Vec3d::new(1.0, 1.0, 1.0)),
+            Some(Vec3d::new(1.0, 1.0, 2.0))
+        );
+        assert_eq!(
+            plane_line(&plane, &Vec3d::zero(), &Vec3d::i()),
+            None
+        );
+    }
+
+    #[test]
+    fn test_cylinder_line_axis_parallel() {
+        let cylinder = Cylinder::new(Vec3d::zero(), Vec3d::k(), 1.0, Some(2.0));
+        let infinite = Cylinder::infinite(&Vec3d::zero(), &Vec3d::k(), 1.0);
+        assert_points_close(
+            cylinder_line(&cylinder, &Vec3d::new(0.5, 0.0, -1.0), &Vec3d::new(0.5, 0.0, 3.0)).unwrap(),
+            (
+                Vec3d::new(0.5, 0.0, 0.0),
+                Vec3d::new(0.5, 0.0, 2.0)
+            )
+        );
+        assert_eq!(
+            cylinder_line(&cylinder, &Vec3d::new(2.0, 0.0, -1.0), &Vec3d::new(2.0, 0.0, 3.0)),
+            None
+        );
+        assert_eq!(
+            cylinder_line(&infinite, &Vec3d::new(0.5, 0.0, -1.0), &Vec3d::new(0.5, 0.0, 3.0)),
+            None
+        );
+    }
+
+    #[test]
+    fn test_cylinder_line_curved_surface() {
+        let cylinder = Cylinder::new(Vec3d::zero(), Vec3d::k(), 1.0, Some(2.0));
+        assert_points_close(
+            cylinder_line(&cylinder, &Vec3d::new(-2.0, 0.0, 1.0), &Vec3d::new(2.0, 0.0, 1.0)).unwrap(),
+            (
+                Vec3d::new(-1.0, 0.0, 1.0),
+                Vec3d::new(1.0, 0.0, 1.0)
+            )
+        );
+        assert_points_close(
+            cylinder_line(&cylinder, &Vec3d::new(1.0, -2.0, 1.0), &Vec3d::new(1.0, 2.0, 1.0)).unwrap(),
+            (
+                Vec3d::new(1.0, 0.0, 1.0),
+                Vec3d::new(1.0, 0.0, 1.0)
+            )
+        );
         assert_eq!(
-            sphere_circle(&sphere, &circle2),
+            cylinder_line(&cylinder, &Vec3d::new(-2.0, 0.0, 3.0), &Vec3d::new(2.0, 0.0, 3.0)),
             None
         );
     }
+
+    #[test]
+    fn test_cylinder_line_end_cap() {
+        let cylinder = Cylinder::new(Vec3d::zero(), Vec3d::k(), 1.0, Some(2.0));
+        assert_points_close(
+            cylinder_line(&cylinder, &Vec3d::new(0.0, 0.0, 3.0), &Vec3d::new(2.0, 0.0, -1.0)).unwrap(),
+            (
+                Vec3d::new(0.5, 0.0, 2.0),
+                Vec3d::new(1.0, 0.0, 1.0)
+            )
+        );
+    }
+
+    #[test]
+    fn test_sphere_sphere_verbose_apart() {
+        let sphere1 = Sphere::new(Vec3d::zero(), 1.0);
+        let sphere2 = Sphere::new(Vec3d::new(5.0, 0.0, 0.0), 2.0);
+        let analysis = sphere_sphere_verbose(&sphere1, &sphere2);
+        assert_eq!(analysis.classification, Classification::Apart);
+        assert!((analysis.gap - 2.0).abs() < 1e-12);
+        assert_points_close(analysis.closest_points.unwrap(), (Vec3d::i(), Vec3d::new(3.0, 0.0, 0.0)));
+        assert_eq!(analysis.containment_margin, None);
+    }
+
+    #[test]
+    fn test_sphere_sphere_verbose_overlapping() {
+        let sphere1 = Sphere::new(Vec3d::zero(), 2.0);
+        let sphere2 = Sphere::new(Vec3d::new(0.0, 3.0, 0.0), 2.0);
+        let analysis = sphere_sphere_verbose(&sphere1, &sphere2);
+        assert_eq!(analysis.classification, Classification::Intersecting);
+        assert!((analysis.gap + 1.0).abs() < 1e-12);
+        assert_points_close(
+            analysis.closest_points.unwrap(),
+            (Vec3d::new(0.0, 2.0, 0.0), Vec3d::new(0.0, 1.0, 0.0))
+        );
+
+        let touching = Sphere::new(Vec3d::new(0.0, 4.0, 0.0), 2.0);
+        let analysis = sphere_sphere_verbose(&sphere1, &touching);
+        assert_eq!(analysis.classification, Classification::Tangent);
+        assert!(analysis.gap.abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_sphere_sphere_verbose_concentric() {
+        let sphere1 = Sphere::new(Vec3d::zero(), 1.0);
+        let sphere2 = Sphere::new(Vec3d::zero(), 3.0);
+        let analysis = sphere_sphere_verbose(&sphere1, &sphere2);
+        assert_eq!(analysis.classification, Classification::Concentric);
+        assert_eq!(analysis.closest_points, None);
+        assert!((analysis.containment_margin.unwrap() - 2.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_circle_circle_verbose_contained() {
+        let outer = Circle::new(Vec3d::zero(), 5.0, Vec3d::k());
+        let inner = Circle::new(Vec3d::new(1.0, 0.0, 0.0), 2.0, Vec3d::k());
+        let analysis = circle_circle_verbose(&inner, &outer).unwrap();
+        assert_eq!(analysis.classification, Classification::Contained);
+        assert!((analysis.gap + 6.0).abs() < 1e-12);
+        assert!((analysis.containment_margin.unwrap() - 2.0).abs() < 1e-12);
+        assert_points_close(
+            analysis.closest_points.unwrap(),
+            (Vec3d::new(3.0, 0.0, 0.0), Vec3d::new(5.0, 0.0, 0.0))
+        );
+        let reversed = circle_circle_verbose(&outer, &inner).unwrap();
+        assert_eq!(reversed.containment_margin, analysis.containment_margin);
+        assert_points_close(
+            reversed.closest_points.unwrap(),
+            (Vec3d::new(5.0, 0.0, 0.0), Vec3d::new(3.0, 0.0, 0.0))
+        );
+
+        let tilted = Circle::new(Vec3d::zero(), 1.0, Vec3d::i());
+        assert_eq!(circle_circle_verbose(&outer, &tilted), None);
+    }
+
+    #[test]
+    fn test_verbose_agrees_with_plain() {
+        crate::testing::for_all(500, |(sphere1, sphere2): (Sphere, Sphere)| {
+            let analysis = sphere_sphere_verbose(&sphere1, &sphere2);
+            let meets = matches!(analysis.classification, Classification::Intersecting | Classification::Tangent);
+            assert_eq!(meets, sphere_sphere(&sphere1, &sphere2).is_some());
+            if analysis.classification == Classification::Apart {
+                assert!(analysis.gap > 0.0);
+            }
+            if let Some((point1, point2)) = analysis.closest_points {
+                assert!((point1.distance_to(&sphere1.center) - sphere1.radius).abs() < 1e-9);
+                assert!((point2.distance_to(&sphere2.center) - sphere2.radius).abs() < 1e-9);
+            }
+        });
+    }
+
+    #[test]
+    fn test_plane_triangle() {
+        let triangle = Triangle::new(Vec3d::zero(), Vec3d::new(2.0, 0.0, 0.0), Vec3d::new(0.0, 0.0, 2.0));
+        let plane = Plane::from_point(Vec3d::k(), Vec3d::new(0.0, 0.0, 1.0));
+        let PlaneTriangleResult::Segment(segment) = plane_triangle(&plane, &triangle) else {
+            panic!("expected a segment");
+        };
+        assert_points_close(
+            (segment.start, segment.end),
+            (Vec3d::new(1.0, 0.0, 1.0), Vec3d::new(0.0, 0.0, 1.0))
+        );
+        let above = Plane::from_point(Vec3d::k(), Vec3d::new(0.0, 0.0, 3.0));
+        assert_eq!(plane_triangle(&above, &triangle), PlaneTriangleResult::None);
+        assert_eq!(plane_triangle(&Plane::xz(), &triangle), PlaneTriangleResult::Coplanar);
+    }
+
+    #[test]
+    fn test_plane_triangle_vertex_on_plane() {
+        let triangle = Triangle::new(Vec3d::new(0.0, 0.0, 1.0), Vec3d::new(1.0, 0.0, 0.0), Vec3d::new(1.0, 0.0, 2.0));
+        let plane = Plane::from_point(Vec3d::k(), Vec3d::new(0.0, 0.0, 1.0));
+        assert_eq!(
+            plane_triangle(&plane, &triangle),
+            PlaneTriangleResult::Segment(Segment::new(Vec3d::new(0.0, 0.0, 1.0), Vec3d::new(1.0, 0.0, 1.0)))
+        );
+        // Only touching at a vertex
+        let touching = Plane::from_point(Vec3d::k(), Vec3d::new(0.0, 0.0, 2.0));
+        let point = Vec3d::new(1.0, 0.0, 2.0);
+        assert_eq!(plane_triangle(&touching, &triangle), PlaneTriangleResult::Segment(Segment::new(point, point)));
+        // An edge in the plane
+        let edge = Plane::from_point(Vec3d::i(), Vec3d::new(1.0, 0.0, 0.0));
+        assert_eq!(
+            plane_triangle(&edge, &triangle),
+            PlaneTriangleResult::Segment(Segment::new(Vec3d::new(1.0, 0.0, 0.0), Vec3d::new(1.0, 0.0, 2.0)))
+        );
+    }
+
+    #[test]
+    fn test_sphere_triangle() {
+        let triangle = Triangle::new(Vec3d::zero(), Vec3d::new(2.0, 0.0, 0.0), Vec3d::new(0.0, 2.0, 0.0));
+        // Beyond the hypotenuse at a distance of sqrt(2) from its midpoint
+        let center = Vec3d::new(2.0, 2.0, 0.0);
+        assert!(sphere_triangle(&Sphere::new(center, 2.0_f64.sqrt() + 1e-9), &triangle));
+        assert!(!sphere_triangle(&Sphere::new(center, 2.0_f64.sqrt() - 1e-9), &triangle));
+        assert!(sphere_triangle(&Sphere::new(Vec3d::new(0.5, 0.5, 0.5), 0.6), &triangle));
+        assert!(!sphere_triangle(&Sphere::new(Vec3d::new(0.5, 0.5, 0.5), 0.4), &triangle));
+    }
+
+    #[test]
+    fn test_circle_halfspace_through_center() {
+        let circle = Circle::new(Vec3d::new(1.0, 1.0, 0.0), 2.0, Vec3d::k());
+        let plane = Plane::from_point(Vec3d::new(1.0, 1.0, 1.0), circle.center);
+        let CircleClipResult::Arc(arc) = circle_halfspace(&circle, &plane) else {
+            panic!("expected an arc");
+        };
+        assert!((arc.length() - 2.0 * core::f64::consts::PI).abs() < 1e-12);
+        assert!(plane.distance_to_point(&arc.start_point()).abs() < 1e-12);
+        assert!(plane.distance_to_point(&arc.end_point()).abs() < 1e-12);
+        assert!(plane.distance_to_point(&arc.point_at(0.5)) > 0.0);
+    }
+
+    #[test]
+    fn test_circle_halfspace_missing() {
+        let circle = Circle::new(Vec3d::zero(), 1.0, Vec3d::k());
+        let plane = Plane::from_point(Vec3d::i(), Vec3d::new(-3.0, 0.0, 0.0));
+        assert_eq!(circle_halfspace(&circle, &plane), CircleClipResult::Whole);
+        assert_eq!(circle_halfspace(&circle, &plane.flip()), CircleClipResult::Empty);
+        // Parallel planes keep or remove the whole circle
+        assert_eq!(circle_halfspace(&circle, &Plane::from_point(Vec3d::k(), [0.0, 0.0, -1.0])), CircleClipResult::Whole);
+        assert_eq!(circle_halfspace(&circle, &Plane::from_point(Vec3d::k(), [0.0, 0.0, 1.0])), CircleClipResult::Empty);
+        // A chord off the center keeps the smaller part
+        let CircleClipResult::Arc(arc) = circle_halfspace(&circle, &Plane::from_point(Vec3d::i(), [0.5, 0.0, 0.0])) else {
+            panic!("expected an arc");
+        };
+        assert!((arc.length() - 2.0 * core::f64::consts::PI / 3.0).abs() < 1e-12);
+        assert!((arc.point_at(0.5) - Vec3d::i()).magnitude() < 1e-12);
+    }
+
+    #[test]
+    fn test_circle_halfspace_tangent() {
+        let circle = Circle::new(Vec3d::zero(), 1.0, Vec3d::k());
+        let plane = Plane::from_point(Vec3d::i(), Vec3d::i());
+        let CircleClipResult::Arc(arc) = circle_halfspace(&circle, &plane) else {
+            panic!("expected an arc");
+        };
+        assert!(arc.is_degenerate());
+        assert!((arc.start_point() - Vec3d::i()).magnitude() < 1e-12);
+        assert_eq!(circle_halfspace(&circle, &plane.flip()), CircleClipResult::Whole);
+    }
 }
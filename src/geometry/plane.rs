@@ -1,55 +1,538 @@
+use crate::geometry::segment::Segment;
+use crate::geometry::{validate_normal, GeometryError, ScaleGeometry};
+use crate::parse::{Fields, ParseError};
+use crate::quat::Quat;
 use crate::vec3d::Vec3d;
 
+/// Which side of a plane a point is on
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Side {
+    /// The point is on the side the normal points towards
+    Above,
+    /// The point is on the side opposite the normal
+    Below,
+    /// The point is on the plane
+    On
+}
+
 /// A plane in 3D space
 #[derive(Copy, Clone, Debug)]
 pub struct Plane {
     /// The normal vector of the plane
     pub normal: Vec3d,
     /// The distance from the origin to the plane
+    /// points on the plane satisfy normal · point + distance = 0
     pub distance: f64
 }
 
 impl Plane {
     /// Create a new plane
-    pub fn new(normal: &Vec3d, distance: f64) -> Plane {
+    /// the normal is normalized and the distance is scaled with it
+    /// so that scaled representations of the same plane are stored identically
+    /// a zero normal or non finite input produces NaN geometry, use `try_new` to reject them
+    pub fn new(normal: impl Into<Vec3d>, distance: f64) -> Plane {
+        let normal = normal.into();
+        let magnitude = normal.magnitude();
+        Plane {
+            normal: normal / magnitude,
+            distance: distance / magnitude
+        }
+    }
+
+    /// Create a new plane from a normal that is already unit length
+    /// the normal and distance are stored as given, so the normal must be normalized for the other methods to be correct
+    pub const fn new_unchecked(normal: Vec3d, distance: f64) -> Plane {
         Plane {
-            normal: normal.normalize(),
+            normal,
             distance
         }
     }
 
+    /// Create a new plane, checking that the inputs describe a valid plane
+    ///
+    /// # Errors
+    /// Returns an error if the normal has zero length or any value is not finite
+    pub fn try_new(normal: impl Into<Vec3d>, distance: f64) -> Result<Plane, GeometryError> {
+        let normal = normal.into();
+        validate_normal(&normal)?;
+        if !distance.is_finite() {
+            return Err(GeometryError::NonFinite);
+        }
+        Ok(Plane::new(normal, distance))
+    }
+
+    /// Check if the plane is valid
+    /// i.e. every value is finite and the normal is not zero
+    pub fn is_valid(&self) -> bool {
+        validate_normal(&self.normal).is_ok() && self.distance.is_finite()
+    }
+
     /// Create a new plane from a normal and a point on the plane
-    pub fn from_point(normal: &Vec3d, point: &Vec3d) -> Plane {
-        let distance = -normal.dot(point);
+    pub fn from_point(normal: impl Into<Vec3d>, point: impl Into<Vec3d>) -> Plane {
+        let normal = normal.into().normalize();
+        let distance = -normal.dot(&point.into());
         Plane {
-            normal: normal.normalize(),
+            normal,
             distance
         }
     }
 
     /// The XY plane
     pub fn xy() -> Plane {
-        Plane::new(&Vec3d::k(), 0.0)
+        Plane::new(Vec3d::k(), 0.0)
     }
 
     /// The XZ plane
     pub fn xz() -> Plane {
-        Plane::new(&Vec3d::j(), 0.0)
+        Plane::new(Vec3d::j(), 0.0)
     }
 
     /// The YZ plane
     pub fn yz() -> Plane {
-        Plane::new(&Vec3d::i(), 0.0)
+        Plane::new(Vec3d::i(), 0.0)
     }
 
     /// Create a plane from three points
-    pub fn from_points(point1: &Vec3d, point2: &Vec3d, point3: &Vec3d) -> Plane {
-        let normal = (point2 - point1).cross(&(point3 - point1));
-        Plane::from_point(&normal, point1)
+    pub fn from_points(point1: impl Into<Vec3d>, point2: impl Into<Vec3d>, point3: impl Into<Vec3d>) -> Plane {
+        let point1 = point1.into();
+        let normal = (point2.into() - point1).cross(&(point3.into() - point1));
+        Plane::from_point(normal, point1)
     }
 
-    /// Get the unsigned distance from a point to the plane
+    /// Get the signed distance from a point to the plane
+    /// the distance is positive on the side the normal points towards
     pub fn distance_to_point(&self, point: &Vec3d) -> f64 {
         self.normal.x * point.x + self.normal.y * point.y + self.normal.z * point.z + self.distance
     }
+
+    /// Get an orthonormal basis (u, v) of directions in the plane
+    /// u is the x axis projected onto the plane, or the y axis when the normal is close to the x axis,
+    /// so the basis changes smoothly with the normal away from that switch
+    /// u, v, and the normal form a right handed system
+    pub fn basis(&self) -> (Vec3d, Vec3d) {
+        let u = self.normal.any_perpendicular();
+        (u, self.normal.cross(&u))
+    }
+
+    /// Get the point on the plane closest to the origin
+    /// this is the origin of the coordinates used by `to_local` and `from_local`
+    pub fn origin(&self) -> Vec3d {
+        self.normal * -self.distance
+    }
+
+    /// Get the coordinates of a point along the plane basis and its signed height above the plane
+    /// measured from `origin`
+    pub fn to_local(&self, point: &Vec3d) -> (f64, f64, f64) {
+        let (u, v) = self.basis();
+        let relative = point - self.origin();
+        (relative.dot(&u), relative.dot(&v), self.distance_to_point(point))
+    }
+
+    /// Get the point on the plane at coordinates along the plane basis, see `to_local`
+    pub fn from_local(&self, u: f64, v: f64) -> Vec3d {
+        let (u_axis, v_axis) = self.basis();
+        self.origin() + u_axis * u + v_axis * v
+    }
+
+    /// Get which side of the plane a point is on
+    /// points within the tolerance of the plane are considered on the plane
+    pub fn side_of(&self, point: &Vec3d, tolerance: f64) -> Side {
+        let distance = self.distance_to_point(point);
+        if distance > tolerance {
+            Side::Above
+        } else if distance < -tolerance {
+            Side::Below
+        } else {
+            Side::On
+        }
+    }
+
+    /// Check if a point is on the plane
+    pub fn contains_point(&self, point: &Vec3d) -> bool {
+        self.contains_point_with_tolerance(point, f64::EPSILON)
+    }
+
+    /// Check if a point is on the plane within a tolerance
+    pub fn contains_point_with_tolerance(&self, point: &Vec3d, tolerance: f64) -> bool {
+        self.side_of(point, tolerance) == Side::On
+    }
+
+    /// Clip a segment to the positive side of the plane
+    /// Returns the portion of the segment on the side the normal points towards
+    /// or None if the segment is entirely below the plane or only touches it
+    /// a segment lying in the plane is returned unchanged
+    pub fn clip_segment(&self, segment: &Segment) -> Option<Segment> {
+        let start_distance = self.distance_to_point(&segment.start);
+        let end_distance = self.distance_to_point(&segment.end);
+        if start_distance >= 0.0 && end_distance >= 0.0 {
+            return Some(*segment);
+        }
+        if start_distance <= 0.0 && end_distance <= 0.0 {
+            return None;
+        }
+        let crossing = segment.point_at(start_distance / (start_distance - end_distance));
+        if start_distance > 0.0 {
+            Some(Segment::new(segment.start, crossing))
+        } else {
+            Some(Segment::new(crossing, segment.end))
+        }
+    }
+
+    /// Get the plane moved along its normal by a distance
+    /// a negative distance moves it the other way
+    pub fn offset(&self, distance: f64) -> Plane {
+        Plane {
+            normal: self.normal,
+            distance: self.distance - distance
+        }
+    }
+
+    /// Get the plane with the same normal through a point
+    pub fn parallel_through_point(&self, point: &Vec3d) -> Plane {
+        Plane {
+            normal: self.normal,
+            distance: -self.normal.dot(point)
+        }
+    }
+
+    /// Get the plane with the opposite orientation
+    /// the flipped plane contains the same points but its normal points the other way
+    pub fn flip(&self) -> Plane {
+        Plane {
+            normal: -self.normal,
+            distance: -self.distance
+        }
+    }
+
+    /// Get the canonical representation of the plane
+    /// the orientation is chosen so that the first non zero component of the normal is positive
+    pub fn canonicalize(&self) -> Plane {
+        let first = [self.normal.x, self.normal.y, self.normal.z]
+            .into_iter()
+            .find(|component| *component != 0.0)
+            .unwrap_or(0.0);
+        if first < 0.0 {
+            self.flip()
+        } else {
+            *self
+        }
+    }
+
+    /// Check if two planes are the same plane within a tolerance
+    /// the orientation of the planes is ignored, the same as with ==
+    pub fn approx_eq(&self, other: &Plane, epsilon: f64) -> bool {
+        let close = |normal: &Vec3d, distance: f64| {
+            self.normal.distance_to(normal) <= epsilon && (self.distance - distance).abs() <= epsilon
+        };
+        close(&other.normal, other.distance) || close(&-other.normal, -other.distance)
+    }
+
+    /// Get the plane rotated about the origin by a quaternion, see `Quat::rotate`
+    /// the distance from the origin is unchanged so only the normal rotates
+    pub fn rotated(&self, q: &Quat) -> Plane {
+        Plane { normal: q.rotate(&self.normal), distance: self.distance }
+    }
+
+    /// Get the plane moved by an offset
+    /// only the part of the offset along the normal moves the plane, so the distance changes by normal·offset
+    pub fn translated(&self, offset: &Vec3d) -> Plane {
+        Plane { normal: self.normal, distance: self.distance - self.normal.dot(offset) }
+    }
+}
+
+/// The region within a distance on either side of a plane
+/// e.g. a flatness tolerance zone
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Slab {
+    /// The plane through the middle of the slab
+    pub plane: Plane,
+    /// The distance from the middle plane to each face
+    pub half_thickness: f64
+}
+
+impl Slab {
+    /// Create a new slab
+    /// the half thickness is made positive
+    pub fn new(plane: &Plane, half_thickness: f64) -> Slab {
+        Slab {
+            plane: *plane,
+            half_thickness: half_thickness.abs()
+        }
+    }
+
+    /// Check if a point is inside the slab, points on a face count as inside
+    pub fn contains_point(&self, point: &Vec3d) -> bool {
+        self.plane.distance_to_point(point).abs() <= self.half_thickness
+    }
+
+    /// Clip a segment to the part inside the slab
+    /// or None if the segment misses the slab or only touches a face
+    pub fn clip_segment(&self, segment: &Segment) -> Option<Segment> {
+        let above_lower = self.plane.offset(-self.half_thickness).clip_segment(segment)?;
+        self.plane.offset(self.half_thickness).flip().clip_segment(&above_lower)
+    }
+}
+
+impl core::fmt::Display for Plane {
+    /// Format the plane as "plane n=(x, y, z) d=distance"
+    /// the precision is applied to every number
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        if let Some(precision) = f.precision() {
+            write!(f, "plane n={:.2$} d={:.2$}", self.normal, self.distance, precision)
+        } else {
+            write!(f, "plane n={} d={}", self.normal, self.distance)
+        }
+    }
+}
+
+impl core::str::FromStr for Plane {
+    type Err = ParseError;
+
+    /// Parse a plane from its `Display` output, the values are stored as given
+    fn from_str(s: &str) -> Result<Plane, ParseError> {
+        let mut fields = Fields::new(s, "plane")?;
+        let normal = fields.vector("n")?;
+        let distance = fields.number("d")?;
+        fields.finish()?;
+        Ok(Plane::new_unchecked(normal, distance))
+    }
+}
+
+impl ScaleGeometry for Plane {
+    fn scaled(&self, factor: f64) -> Self {
+        Plane {
+            normal: self.normal,
+            distance: self.distance * factor
+        }
+    }
+}
+
+impl PartialEq for Plane {
+    /// Planes are equal if they contain the same points
+    /// the orientation of the planes is ignored so a plane is equal to its flip
+    fn eq(&self, other: &Self) -> bool {
+        (self.normal == other.normal && self.distance == other.distance) ||
+            (self.normal == -other.normal && self.distance == -other.distance)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::ToString;
+
+    use super::*;
+
+    #[test]
+    fn test_new_normalizes() {
+        let plane = Plane::new(Vec3d::new(0.0, 0.0, 2.0), -4.0);
+        assert_eq!(plane.normal, Vec3d::k());
+        assert!((plane.distance + 2.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_equality() {
+        let plane1 = Plane::new(Vec3d::k(), -2.0);
+        let plane2 = Plane::new(Vec3d::new(0.0, 0.0, -3.0), 6.0);
+        let plane3 = Plane::from_point(Vec3d::new(0.0, 0.0, 5.0), Vec3d::new(1.0, 2.0, 2.0));
+        let plane4 = Plane::from_points(
+            Vec3d::new(0.0, 0.0, 2.0),
+            Vec3d::new(1.0, 0.0, 2.0),
+            Vec3d::new(0.0, 1.0, 2.0)
+        );
+        assert_eq!(plane1, plane2);
+        assert_eq!(plane1, plane3);
+        assert_eq!(plane1, plane4);
+        assert_ne!(plane1, Plane::new(Vec3d::k(), 2.0));
+    }
+
+    #[test]
+    fn test_flip() {
+        let plane = Plane::new(Vec3d::new(1.0, 1.0, 0.0), 3.0);
+        let flipped = plane.flip();
+        assert_eq!(flipped.normal, -plane.normal);
+        assert_eq!(plane, flipped);
+        assert_eq!(flipped.flip().normal, plane.normal);
+    }
+
+    #[test]
+    fn test_canonicalize() {
+        let plane = Plane::new(Vec3d::new(0.0, -1.0, 1.0), 1.0);
+        let canonical = plane.canonicalize();
+        assert!(canonical.normal.y > 0.0);
+        assert_eq!(canonical, plane);
+        assert_eq!(canonical.canonicalize().normal, canonical.normal);
+        assert_eq!(plane.flip().canonicalize().normal, canonical.normal);
+    }
+
+    #[test]
+    fn test_approx_eq() {
+        let plane1 = Plane::new(Vec3d::new(1.0, 2.0, 3.0), 4.0);
+        let plane2 = Plane::new(Vec3d::new(-1.0, -2.0, -3.000_000_000_1), -4.0);
+        assert!(plane1.approx_eq(&plane2, 1e-9));
+        assert!(!plane1.approx_eq(&plane2, 1e-15));
+        assert!(!plane1.approx_eq(&Plane::xy(), 1e-9));
+    }
+
+    #[test]
+    fn test_side_of() {
+        let plane = Plane::new(Vec3d::new(1.0, 1.0, 1.0), -1.0);
+        assert_eq!(plane.side_of(&Vec3d::new(1.0, 1.0, 1.0), 1e-9), Side::Above);
+        assert_eq!(plane.side_of(&Vec3d::zero(), 1e-9), Side::Below);
+        assert_eq!(plane.side_of(&Vec3d::new(0.0, 1.0, 0.0), 1e-9), Side::On);
+        assert_eq!(plane.side_of(&Vec3d::new(0.0, 1.0 + 1e-6, 0.0), 1e-3), Side::On);
+        assert_eq!(plane.side_of(&Vec3d::new(0.0, 1.0 + 1e-6, 0.0), 1e-9), Side::Above);
+    }
+
+    #[test]
+    fn test_contains_point() {
+        let plane = Plane::xy();
+        assert!(plane.contains_point(&Vec3d::new(4.0, -2.0, 0.0)));
+        assert!(!plane.contains_point(&Vec3d::new(4.0, -2.0, 1e-6)));
+        assert!(plane.contains_point_with_tolerance(&Vec3d::new(4.0, -2.0, 1e-6), 1e-5));
+    }
+
+    #[test]
+    fn test_clip_segment() {
+        let plane = Plane::xy();
+        let above = Segment::new(Vec3d::new(0.0, 0.0, 1.0), Vec3d::new(1.0, 1.0, 2.0));
+        let below = Segment::new(Vec3d::new(0.0, 0.0, -1.0), Vec3d::new(1.0, 1.0, -2.0));
+        let crossing = Segment::new(Vec3d::new(0.0, 0.0, -1.0), Vec3d::new(2.0, 0.0, 1.0));
+        assert_eq!(plane.clip_segment(&above), Some(above));
+        assert_eq!(plane.clip_segment(&below), None);
+        assert_eq!(
+            plane.clip_segment(&crossing),
+            Some(Segment::new(Vec3d::new(1.0, 0.0, 0.0), Vec3d::new(2.0, 0.0, 1.0)))
+        );
+        assert_eq!(
+            plane.flip().clip_segment(&crossing),
+            Some(Segment::new(Vec3d::new(0.0, 0.0, -1.0), Vec3d::new(1.0, 0.0, 0.0)))
+        );
+    }
+
+    #[test]
+    fn test_try_new() {
+        assert!(Plane::try_new(Vec3d::k(), 1.0).is_ok());
+        assert_eq!(Plane::try_new(Vec3d::zero(), 1.0), Err(GeometryError::ZeroNormal));
+        assert_eq!(Plane::try_new(Vec3d::k(), f64::NAN), Err(GeometryError::NonFinite));
+        assert_eq!(
+            Plane::try_new(Vec3d::new(f64::NAN, 0.0, 1.0), 1.0),
+            Err(GeometryError::NonFinite)
+        );
+    }
+
+    #[test]
+    fn test_is_valid() {
+        assert!(Plane::xy().is_valid());
+        assert!(!Plane::new(Vec3d::zero(), 1.0).is_valid());
+    }
+
+    #[test]
+    fn test_const_constructor() {
+        const GROUND: Plane = Plane::new_unchecked(Vec3d::k(), -1.0);
+        assert_eq!(GROUND, Plane::new(Vec3d::new(0.0, 0.0, 3.0), -3.0));
+    }
+
+    #[test]
+    fn test_basis_is_orthonormal() {
+        for plane in [
+            Plane::xy(),
+            Plane::new(Vec3d::new(1.0, 2.0, -3.0), 4.0),
+            Plane::new(Vec3d::new(-1.0, 0.1, 0.0), 0.0)
+        ] {
+            let (u, v) = plane.basis();
+            assert!(u.is_unit() && v.is_unit());
+            assert!(u.dot(&v).abs() < 1e-12);
+            assert!(u.dot(&plane.normal).abs() < 1e-12);
+            assert!((u.cross(&v) - plane.normal).magnitude() < 1e-12);
+        }
+        assert_eq!(Plane::xy().basis(), (Vec3d::i(), Vec3d::j()));
+    }
+
+    #[test]
+    fn test_basis_is_stable() {
+        let normal = Vec3d::new(0.3, -0.5, 0.8);
+        let (u1, v1) = Plane::new(normal, 1.0).basis();
+        let (u2, v2) = Plane::new(normal + Vec3d::new(1e-12, -1e-12, 1e-12), 1.0).basis();
+        assert!((u1 - u2).magnitude() < 1e-9);
+        assert!((v1 - v2).magnitude() < 1e-9);
+    }
+
+    #[test]
+    fn test_local_round_trip() {
+        let plane = Plane::from_point(Vec3d::new(1.0, 1.0, 1.0), Vec3d::new(2.0, -1.0, 3.0));
+        let on_plane = plane.from_local(1.5, -2.5);
+        assert!(plane.distance_to_point(&on_plane).abs() < 1e-12);
+        let (u, v, height) = plane.to_local(&on_plane);
+        assert!((u - 1.5).abs() < 1e-12 && (v + 2.5).abs() < 1e-12 && height.abs() < 1e-12);
+
+        let point = Vec3d::new(-4.0, 5.0, 0.5);
+        let (u, v, height) = plane.to_local(&point);
+        let rebuilt = plane.from_local(u, v) + plane.normal * height;
+        assert!((rebuilt - point).magnitude() < 1e-12);
+        assert!((height - plane.distance_to_point(&point)).abs() < 1e-12);
+        assert!((plane.from_local(0.0, 0.0) - plane.origin()).magnitude() < 1e-12);
+    }
+
+    #[test]
+    fn test_offset() {
+        let plane = Plane::from_point(Vec3d::new(1.0, 2.0, 2.0), Vec3d::new(1.0, 0.0, 0.0));
+        let moved = plane.offset(3.0);
+        let point = Vec3d::new(1.0, 0.0, 0.0) + plane.normal * 3.0;
+        assert!(moved.distance_to_point(&point).abs() < 1e-12);
+        assert!(moved.offset(-3.0).approx_eq(&plane, 1e-12));
+        let through = plane.parallel_through_point(&Vec3d::new(5.0, 5.0, 5.0));
+        assert_eq!(through.normal, plane.normal);
+        assert!(through.distance_to_point(&Vec3d::new(5.0, 5.0, 5.0)).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_slab() {
+        let plane = Plane::xy();
+        let point = Vec3d::new(3.0, -2.0, 0.4);
+        assert!(Slab::new(&plane, 0.5).contains_point(&point));
+        assert!(!Slab::new(&plane, 0.3).contains_point(&point));
+        assert!(Slab::new(&plane, -0.5).contains_point(&-point));
+    }
+
+    #[test]
+    fn test_slab_clip_segment() {
+        let slab = Slab::new(&Plane::from_point(Vec3d::k(), Vec3d::new(0.0, 0.0, 1.0)), 0.5);
+        let crossing = Segment::new(Vec3d::new(0.0, 0.0, -1.0), Vec3d::new(4.0, 0.0, 3.0));
+        let clipped = slab.clip_segment(&crossing).unwrap();
+        assert!((clipped.start - Vec3d::new(1.5, 0.0, 0.5)).magnitude() < 1e-12);
+        assert!((clipped.end - Vec3d::new(2.5, 0.0, 1.5)).magnitude() < 1e-12);
+        let inside = Segment::new(Vec3d::new(0.0, 0.0, 0.8), Vec3d::new(1.0, 0.0, 1.2));
+        assert_eq!(slab.clip_segment(&inside), Some(inside));
+        let outside = Segment::new(Vec3d::new(0.0, 0.0, 2.0), Vec3d::new(1.0, 0.0, 3.0));
+        assert_eq!(slab.clip_segment(&outside), None);
+    }
+
+    #[test]
+    fn test_from_str_round_trip() {
+        crate::testing::for_all(100, |plane: Plane| {
+            assert_eq!(plane.to_string().parse::<Plane>(), Ok(plane));
+        });
+        let plane = Plane::new(Vec3d::k(), -2.0);
+        assert_eq!(plane.to_string(), "plane n=(0, 0, 1) d=-2");
+        assert_eq!("plane n=(0,0,1)  d = -2".parse::<Plane>(), Ok(plane));
+        assert_eq!(
+            "plane n=(0, 0) d=1".parse::<Plane>(),
+            Err(ParseError::InvalidValue { field: "n", found: "(0, 0)".to_string() })
+        );
+    }
+
+    #[test]
+    fn test_rotated_and_translated() {
+        let plane = Plane::new(Vec3d::new(1.0, 1.0, 0.0), -2.0);
+        let q = Quat::from_axis_angle(&Vec3d::k(), core::f64::consts::FRAC_PI_4);
+        let rotated = plane.rotated(&q);
+        assert!((rotated.normal - Vec3d::j()).magnitude() < 1e-15);
+        assert!((rotated.origin() - q.rotate(&plane.origin())).magnitude() < 1e-12);
+        let offset = Vec3d::new(3.0, -1.0, 4.0);
+        let translated = plane.translated(&offset);
+        let expected = plane.origin() + plane.normal * plane.normal.dot(&offset);
+        assert!((translated.origin() - expected).magnitude() < 1e-12);
+        let point = plane.from_local(0.5, -2.0);
+        assert!(translated.distance_to_point(&(point + offset)).abs() < 1e-12);
+    }
 }
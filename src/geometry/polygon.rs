@@ -0,0 +1,121 @@
+use crate::geometry::plane::Plane;
+use crate::geometry::GeometryError;
+#[cfg(not(any(feature = "std", test)))]
+use crate::math::Float;
+use crate::vec3d::Vec3d;
+
+/// How far a vertex may be from the plane of a loop before it is rejected
+pub const PLANE_TOLERANCE: f64 = 1e-9;
+
+/// The direction a closed loop of points turns in
+/// viewed from the side of the plane the normal points towards
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Winding {
+    /// The loop turns clockwise, its signed area is negative
+    Clockwise,
+    /// The loop turns counter-clockwise, its signed area is positive
+    CounterClockwise,
+    /// The loop has no area, e.g. it has fewer than 3 points or they are collinear
+    Degenerate
+}
+
+/// Calculate the signed area of a closed loop of points in a plane
+/// the last point joins back up to the first, so it should not be repeated
+/// the area is positive when the loop is counter-clockwise viewed from the side the normal points towards
+///
+/// # Errors
+/// Returns `GeometryError::PointNotInPlane` if any point is further than `PLANE_TOLERANCE` from the plane
+/// and `GeometryError::NonFinite` if any point is not finite
+pub fn signed_area(points: &[Vec3d], plane: &Plane) -> Result<f64, GeometryError> {
+    for point in points {
+        if !point.is_finite() {
+            return Err(GeometryError::NonFinite);
+        }
+        if !plane.contains_point_with_tolerance(point, PLANE_TOLERANCE) {
+            return Err(GeometryError::PointNotInPlane);
+        }
+    }
+    let Some(first) = points.first() else {
+        return Ok(0.0);
+    };
+    // Newell's method, taken relative to the first point so a loop far from the origin keeps its precision
+    let twice_area: Vec3d = points
+        .iter()
+        .zip(points.iter().cycle().skip(1))
+        .map(|(a, b)| (a - first).cross(&(b - first)))
+        .sum();
+    Ok(twice_area.dot(&plane.normal) / 2.0)
+}
+
+/// Get the winding direction of a closed loop of points in a plane
+/// loops with an area that is negligible compared to the square of their perimeter are degenerate
+///
+/// # Errors
+/// Returns the same errors as `signed_area`
+pub fn winding(points: &[Vec3d], plane: &Plane) -> Result<Winding, GeometryError> {
+    let area = signed_area(points, plane)?;
+    let perimeter: f64 = points
+        .iter()
+        .zip(points.iter().cycle().skip(1))
+        .map(|(a, b)| a.distance_to(b))
+        .sum();
+    Ok(if area.abs() <= 1e-12 * perimeter.powi(2) {
+        Winding::Degenerate
+    } else if area > 0.0 {
+        Winding::CounterClockwise
+    } else {
+        Winding::Clockwise
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec::Vec;
+
+    /// A unit square in the oblique plane through (1, 2, 3) with normal (1, 1, 1)
+    /// counter-clockwise viewed from the side the normal points towards
+    fn oblique_square() -> (Vec<Vec3d>, Plane) {
+        let normal = Vec3d::new(1.0, 1.0, 1.0).normalize();
+        let u = Vec3d::new(1.0, -1.0, 0.0).normalize();
+        let v = normal.cross(&u);
+        let origin = Vec3d::new(1.0, 2.0, 3.0);
+        let points = alloc::vec![origin, origin + u, origin + u + v, origin + v];
+        (points, Plane::from_point(normal, origin))
+    }
+
+    #[test]
+    fn test_signed_area() {
+        let (mut points, plane) = oblique_square();
+        assert!((signed_area(&points, &plane).unwrap() - 1.0).abs() < 1e-12);
+        assert_eq!(winding(&points, &plane), Ok(Winding::CounterClockwise));
+        points.reverse();
+        assert!((signed_area(&points, &plane).unwrap() + 1.0).abs() < 1e-12);
+        assert_eq!(winding(&points, &plane), Ok(Winding::Clockwise));
+    }
+
+    #[test]
+    fn test_flipped_plane() {
+        let (points, plane) = oblique_square();
+        let flipped = Plane::from_point(-plane.normal, points[0]);
+        assert_eq!(winding(&points, &flipped), Ok(Winding::Clockwise));
+    }
+
+    #[test]
+    fn test_degenerate() {
+        let plane = Plane::xy();
+        let collinear = [Vec3d::zero(), Vec3d::i(), Vec3d::new(2.0, 0.0, 0.0), Vec3d::new(3.0, 0.0, 0.0)];
+        assert_eq!(winding(&collinear, &plane), Ok(Winding::Degenerate));
+        assert_eq!(winding(&[Vec3d::zero(), Vec3d::i()], &plane), Ok(Winding::Degenerate));
+        assert_eq!(signed_area(&[], &plane), Ok(0.0));
+        assert_eq!(winding(&[], &plane), Ok(Winding::Degenerate));
+    }
+
+    #[test]
+    fn test_off_plane_vertex() {
+        let (mut points, plane) = oblique_square();
+        points[2] = points[2] + plane.normal * 1e-3;
+        assert_eq!(signed_area(&points, &plane), Err(GeometryError::PointNotInPlane));
+        assert_eq!(winding(&points, &plane), Err(GeometryError::PointNotInPlane));
+    }
+}
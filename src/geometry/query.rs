@@ -0,0 +1,115 @@
+//! Boolean overlap tests that only answer whether shapes meet
+//! they skip building the intersection, compare squared distances instead of taking square roots,
+//! and use no tolerance so touching shapes only count when they touch exactly
+
+use crate::geometry::aabb::Aabb;
+use crate::geometry::circle::Circle;
+use crate::geometry::plane::Plane;
+use crate::geometry::sphere::Sphere;
+
+/// Check if the surfaces of two spheres meet
+/// this is when `intersection::sphere_sphere` finds a circle or a touching point,
+/// except that coincident spheres also count since their surfaces meet everywhere
+/// a sphere strictly inside another does not overlap its surface
+pub fn sphere_sphere_overlap(sphere1: &Sphere, sphere2: &Sphere) -> bool {
+    let offset = sphere2.center - sphere1.center;
+    let distance_squared = offset.dot(&offset);
+    let radius_sum = sphere1.radius + sphere2.radius;
+    let radius_diff = sphere1.radius - sphere2.radius;
+    radius_diff * radius_diff <= distance_squared && distance_squared <= radius_sum * radius_sum
+}
+
+/// Check if a sphere meets a plane
+/// this is when `intersection::sphere_plane` finds a circle
+pub fn sphere_plane_overlap(sphere: &Sphere, plane: &Plane) -> bool {
+    plane.distance_to_point(&sphere.center).abs() <= sphere.radius
+}
+
+/// Check if the rims of two circles meet
+/// this is when `intersection::circle_circle` finds points, except that coincident circles also count
+/// circles in different planes never overlap, the planes are compared the same way as `Circle::in_same_plane`
+pub fn circle_circle_overlap(circle1: &Circle, circle2: &Circle) -> bool {
+    if !circle1.in_same_plane(circle2) {
+        return false;
+    }
+    let offset = circle2.center - circle1.center;
+    let distance_squared = offset.dot(&offset);
+    let radius_sum = circle1.radius + circle2.radius;
+    let radius_diff = circle1.radius - circle2.radius;
+    radius_diff * radius_diff <= distance_squared && distance_squared <= radius_sum * radius_sum
+}
+
+/// Check if two boxes share any point, including boxes that only touch on a face, edge, or corner
+pub fn aabb_overlap(aabb1: &Aabb, aabb2: &Aabb) -> bool {
+    (0..3).all(|axis| aabb1.min[axis] <= aabb2.max[axis] && aabb2.min[axis] <= aabb1.max[axis])
+}
+
+/// Check if a solid sphere and a solid box share any point
+pub fn sphere_aabb_overlap(sphere: &Sphere, aabb: &Aabb) -> bool {
+    let offset = aabb.closest_point(&sphere.center) - sphere.center;
+    offset.dot(&offset) <= sphere.radius * sphere.radius
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geometry::intersection::{circle_circle, sphere_plane, sphere_sphere};
+    use crate::vec3d::Vec3d;
+
+    // Half steps are exact in binary so the tangent configurations are hit exactly
+    fn grid() -> impl Iterator<Item = f64> {
+        (0..=16).map(|step| f64::from(step) * 0.5)
+    }
+
+    #[test]
+    fn test_sphere_sphere_matches_constructive() {
+        for distance in grid().filter(|distance| *distance > 0.0) {
+            for radius in grid().filter(|radius| *radius > 0.0) {
+                let sphere1 = Sphere::new(Vec3d::zero(), 2.0);
+                let sphere2 = Sphere::new(Vec3d::new(distance, 0.0, 0.0), radius);
+                let expected = sphere_sphere(&sphere1, &sphere2).is_some();
+                assert_eq!(sphere_sphere_overlap(&sphere1, &sphere2), expected, "d={distance} r={radius}");
+            }
+        }
+        let sphere = Sphere::new(Vec3d::i(), 1.0);
+        assert!(sphere_sphere_overlap(&sphere, &sphere));
+        assert!(!sphere_sphere_overlap(&sphere, &Sphere::new(Vec3d::i(), 0.5)));
+    }
+
+    #[test]
+    fn test_sphere_plane_matches_constructive() {
+        let plane = Plane::new(Vec3d::k(), -1.0);
+        for height in grid().map(|height| height - 4.0) {
+            for radius in grid() {
+                let sphere = Sphere::new(Vec3d::new(0.5, -1.0, height), radius);
+                let expected = sphere_plane(&sphere, &plane).is_some();
+                assert_eq!(sphere_plane_overlap(&sphere, &plane), expected, "h={height} r={radius}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_circle_circle_matches_constructive() {
+        for distance in grid().filter(|distance| *distance > 0.0) {
+            for radius in grid().filter(|radius| *radius > 0.0) {
+                let circle1 = Circle::new(Vec3d::zero(), 2.0, Vec3d::k());
+                let circle2 = Circle::new(Vec3d::new(0.0, distance, 0.0), radius, -Vec3d::k());
+                let expected = circle_circle(&circle1, &circle2).is_some();
+                assert_eq!(circle_circle_overlap(&circle1, &circle2), expected, "d={distance} r={radius}");
+            }
+        }
+        let circle = Circle::new(Vec3d::zero(), 1.0, Vec3d::k());
+        assert!(!circle_circle_overlap(&circle, &Circle::new(Vec3d::new(0.5, 0.0, 1.0), 1.0, Vec3d::k())));
+    }
+
+    #[test]
+    fn test_aabb_overlap() {
+        let aabb = Aabb::new(Vec3d::zero(), (1.0, 1.0, 1.0));
+        assert!(aabb_overlap(&aabb, &Aabb::new((0.5, 0.5, 0.5), (2.0, 2.0, 2.0))));
+        assert!(aabb_overlap(&aabb, &Aabb::new((1.0, 1.0, 1.0), (2.0, 2.0, 2.0))));
+        assert!(!aabb_overlap(&aabb, &Aabb::new((1.0, 1.5, 0.0), (2.0, 2.0, 2.0))));
+        assert!(sphere_aabb_overlap(&Sphere::new((2.0, 0.5, 0.5), 1.0), &aabb));
+        assert!(!sphere_aabb_overlap(&Sphere::new((2.0, 2.0, 0.5), 1.0), &aabb));
+        assert!(sphere_aabb_overlap(&Sphere::new((0.5, 0.5, 0.5), 0.1), &aabb));
+    }
+}
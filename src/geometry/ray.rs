@@ -0,0 +1,279 @@
+use crate::geometry::aabb::Aabb;
+use crate::geometry::cylinder::Cylinder;
+use crate::geometry::intersection::cylinder_line;
+use crate::geometry::plane::Plane;
+use crate::geometry::sphere::Sphere;
+use crate::geometry::triangle::Triangle;
+use crate::geometry::ScaleGeometry;
+#[cfg(not(any(feature = "std", test)))]
+use crate::math::Float;
+use crate::quat::Quat;
+use crate::vec3d::Vec3d;
+
+/// A half line in 3d space starting at an origin and extending forever in one direction
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Ray {
+    /// The point the ray starts from
+    pub origin: Vec3d,
+    /// The unit direction of the ray
+    pub direction: Vec3d
+}
+
+impl Ray {
+    /// Create a new ray
+    /// the direction is normalized so distances along the ray are in the same units as the origin
+    pub fn new(origin: impl Into<Vec3d>, direction: impl Into<Vec3d>) -> Ray {
+        Ray {
+            origin: origin.into(),
+            direction: direction.into().normalize()
+        }
+    }
+
+    /// Get the point a distance t along the ray
+    pub fn point_at(&self, t: f64) -> Vec3d {
+        self.origin + self.direction * t
+    }
+
+    /// Get the ray rotated about the origin by a quaternion, see `Quat::rotate`
+    pub fn rotated(&self, q: &Quat) -> Ray {
+        Ray { origin: q.rotate(&self.origin), direction: q.rotate(&self.direction) }
+    }
+
+    /// Get the ray moved by an offset, the direction is unchanged
+    pub fn translated(&self, offset: &Vec3d) -> Ray {
+        Ray { origin: self.origin + offset, direction: self.direction }
+    }
+}
+
+impl ScaleGeometry for Ray {
+    /// The direction is reversed for negative factors so the ray still covers the scaled points
+    fn scaled(&self, factor: f64) -> Self {
+        Ray::new(self.origin.scaled(factor), self.direction * factor.signum())
+    }
+}
+
+/// Geometry that a ray can hit
+/// implement this for your own shapes to use them with `first_hit`
+pub trait RayIntersectable {
+    /// Get the distance along the ray to the first point where it hits the geometry
+    /// or None if the ray misses, hits at the origin of the ray count with a distance of 0
+    fn ray_intersect(&self, ray: &Ray) -> Option<f64>;
+}
+
+impl<T: RayIntersectable + ?Sized> RayIntersectable for &T {
+    fn ray_intersect(&self, ray: &Ray) -> Option<f64> {
+        (**self).ray_intersect(ray)
+    }
+}
+
+impl RayIntersectable for Sphere {
+    /// a ray starting inside the sphere hits it where it leaves
+    fn ray_intersect(&self, ray: &Ray) -> Option<f64> {
+        let offset = ray.origin - self.center;
+        let b = offset.dot(&ray.direction);
+        let c = offset.dot(&offset) - self.radius.powi(2);
+        let discriminant = b.powi(2) - c;
+        if discriminant < 0.0 {
+            return None;
+        }
+        let root = discriminant.sqrt();
+        [-b - root, -b + root].into_iter().find(|t| *t >= 0.0)
+    }
+}
+
+impl RayIntersectable for Plane {
+    /// a ray parallel to the plane never hits it, even when it lies in the plane
+    fn ray_intersect(&self, ray: &Ray) -> Option<f64> {
+        let denominator = self.normal.dot(&ray.direction);
+        if denominator.abs() < f64::EPSILON {
+            return None;
+        }
+        let t = -self.distance_to_point(&ray.origin) / denominator;
+        (t >= 0.0).then_some(t)
+    }
+}
+
+impl RayIntersectable for Cylinder {
+    /// the end caps of finite cylinders count as part of the surface
+    fn ray_intersect(&self, ray: &Ray) -> Option<f64> {
+        let (first, last) = cylinder_line(self, &ray.origin, &ray.point_at(1.0))?;
+        [first, last]
+            .into_iter()
+            .map(|point| (point - ray.origin).dot(&ray.direction))
+            .find(|t| *t >= 0.0)
+    }
+}
+
+impl RayIntersectable for Aabb {
+    /// the slab test, a ray starting inside the box hits it at a distance of 0
+    /// rays parallel to a face only hit if they lie between its pair of faces
+    fn ray_intersect(&self, ray: &Ray) -> Option<f64> {
+        let mut near = 0.0_f64;
+        let mut far = f64::INFINITY;
+        for axis in 0..3 {
+            let (origin, direction) = (ray.origin[axis], ray.direction[axis]);
+            if direction == 0.0 {
+                if origin < self.min[axis] || origin > self.max[axis] {
+                    return None;
+                }
+                continue;
+            }
+            let first = (self.min[axis] - origin) / direction;
+            let second = (self.max[axis] - origin) / direction;
+            near = near.max(first.min(second));
+            far = far.min(first.max(second));
+            if near > far {
+                return None;
+            }
+        }
+        Some(near)
+    }
+}
+
+impl RayIntersectable for Triangle {
+    /// the Möller–Trumbore test, hits on the edges count and a ray in the plane of the triangle never hits it
+    fn ray_intersect(&self, ray: &Ray) -> Option<f64> {
+        let ab = self.b - self.a;
+        let ac = self.c - self.a;
+        let p = ray.direction.cross(&ac);
+        let determinant = ab.dot(&p);
+        if determinant.abs() < f64::EPSILON * ab.magnitude() * ac.magnitude() {
+            return None;
+        }
+        let offset = ray.origin - self.a;
+        let u = offset.dot(&p) / determinant;
+        if !(0.0..=1.0).contains(&u) {
+            return None;
+        }
+        let q = offset.cross(&ab);
+        let v = ray.direction.dot(&q) / determinant;
+        if v < 0.0 || u + v > 1.0 {
+            return None;
+        }
+        let t = ac.dot(&q) / determinant;
+        (t >= 0.0).then_some(t)
+    }
+}
+
+/// Find the first geometry a ray hits
+/// returns the index of the geometry and the distance along the ray, or None if nothing is hit
+/// when several geometries are hit at the same distance the one with the lowest index is returned
+/// a mix of shapes can be passed as a slice of `&dyn RayIntersectable`
+pub fn first_hit<'a, T: RayIntersectable + ?Sized + 'a>(ray: &Ray, geometries: impl IntoIterator<Item = &'a T>) -> Option<(usize, f64)> {
+    geometries
+        .into_iter()
+        .enumerate()
+        .filter_map(|(index, geometry)| geometry.ray_intersect(ray).map(|t| (index, t)))
+        .fold(None, |nearest, (index, t)| match nearest {
+            Some((_, nearest_t)) if nearest_t <= t => nearest,
+            _ => Some((index, t))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn x_ray() -> Ray {
+        Ray::new(Vec3d::zero(), Vec3d::new(2.0, 0.0, 0.0))
+    }
+
+    #[test]
+    fn test_point_at() {
+        assert_eq!(x_ray().point_at(3.0), Vec3d::new(3.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn test_scaled() {
+        let ray = Ray::new(Vec3d::i(), Vec3d::new(1.0, 1.0, 0.0));
+        for factor in [2.5, -1.0, -3.0] {
+            let scaled = ray.scaled(factor);
+            for t in [0.0, 0.5, 2.0] {
+                assert!((scaled.point_at(t * factor.abs()) - ray.point_at(t) * factor).magnitude() < 1e-12);
+            }
+        }
+    }
+
+    #[test]
+    fn test_sphere_ray_intersect() {
+        let sphere = Sphere::new(Vec3d::new(3.0, 0.0, 0.0), 1.0);
+        assert!((sphere.ray_intersect(&x_ray()).unwrap() - 2.0).abs() < 1e-12);
+        let inside = Ray::new(Vec3d::new(3.0, 0.0, 0.0), Vec3d::j());
+        assert!((sphere.ray_intersect(&inside).unwrap() - 1.0).abs() < 1e-12);
+        let behind = Ray::new(Vec3d::new(5.0, 0.0, 0.0), Vec3d::i());
+        assert_eq!(sphere.ray_intersect(&behind), None);
+        let miss = Ray::new(Vec3d::new(0.0, 2.0, 0.0), Vec3d::i());
+        assert_eq!(sphere.ray_intersect(&miss), None);
+    }
+
+    #[test]
+    fn test_plane_ray_intersect() {
+        let plane = Plane::from_point(Vec3d::new(-1.0, 0.0, 0.0), Vec3d::new(5.0, 0.0, 0.0));
+        assert!((plane.ray_intersect(&x_ray()).unwrap() - 5.0).abs() < 1e-12);
+        assert_eq!(plane.ray_intersect(&Ray::new(Vec3d::zero(), -Vec3d::i())), None);
+        assert_eq!(plane.ray_intersect(&Ray::new(Vec3d::zero(), Vec3d::j())), None);
+    }
+
+    #[test]
+    fn test_cylinder_ray_intersect() {
+        let cylinder = Cylinder::new(Vec3d::new(4.0, 0.0, -1.0), Vec3d::k(), 1.0, Some(2.0));
+        assert!((cylinder.ray_intersect(&x_ray()).unwrap() - 3.0).abs() < 1e-12);
+        let inside = Ray::new(Vec3d::new(4.0, 0.0, 0.0), Vec3d::k());
+        assert!((cylinder.ray_intersect(&inside).unwrap() - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_aabb_ray_intersect() {
+        let aabb = Aabb::new((2.0, -1.0, -1.0), (4.0, 1.0, 1.0));
+        assert!((aabb.ray_intersect(&x_ray()).unwrap() - 2.0).abs() < 1e-12);
+        let inside = Ray::new(Vec3d::new(3.0, 0.0, 0.0), Vec3d::new(-1.0, 1.0, 0.0));
+        assert_eq!(aabb.ray_intersect(&inside), Some(0.0));
+        let diagonal = Ray::new(Vec3d::new(0.0, -2.0, 0.0), Vec3d::new(1.0, 1.0, 0.0));
+        assert!((aabb.ray_intersect(&diagonal).unwrap() - 2.0 * 2.0_f64.sqrt()).abs() < 1e-12);
+        assert_eq!(aabb.ray_intersect(&Ray::new(Vec3d::new(0.0, 2.0, 0.0), Vec3d::i())), None);
+        assert_eq!(aabb.ray_intersect(&Ray::new(Vec3d::zero(), -Vec3d::i())), None);
+        assert_eq!(aabb.ray_intersect(&Ray::new(Vec3d::new(0.0, 0.0, 3.0), Vec3d::new(1.0, 0.0, -0.2))), None);
+    }
+
+    #[test]
+    fn test_triangle_ray_intersect() {
+        let triangle = Triangle::new((2.0, -1.0, -1.0), (2.0, 1.0, -1.0), (2.0, 0.0, 1.0));
+        assert!((triangle.ray_intersect(&x_ray()).unwrap() - 2.0).abs() < 1e-12);
+        let reversed = Triangle::new(triangle.c, triangle.b, triangle.a);
+        assert!((reversed.ray_intersect(&x_ray()).unwrap() - 2.0).abs() < 1e-12);
+        assert_eq!(triangle.ray_intersect(&Ray::new(Vec3d::new(0.0, 2.0, 0.0), Vec3d::i())), None);
+        assert_eq!(triangle.ray_intersect(&Ray::new(Vec3d::zero(), -Vec3d::i())), None);
+        assert_eq!(triangle.ray_intersect(&Ray::new(Vec3d::new(2.0, -3.0, 0.0), Vec3d::j())), None);
+    }
+
+    #[test]
+    fn test_first_hit() {
+        let sphere = Sphere::new(Vec3d::new(3.0, 0.0, 0.0), 1.0);
+        let plane = Plane::from_point(Vec3d::i(), Vec3d::new(5.0, 0.0, 0.0));
+        let scene: [&dyn RayIntersectable; 2] = [&plane, &sphere];
+        let (index, t) = first_hit(&x_ray(), scene).unwrap();
+        assert_eq!(index, 1);
+        assert!((t - 2.0).abs() < 1e-12);
+        assert_eq!(first_hit(&Ray::new(Vec3d::zero(), -Vec3d::i()), scene), None);
+        let spheres = [Sphere::new(Vec3d::new(0.0, 9.0, 0.0), 1.0), sphere];
+        assert_eq!(first_hit(&x_ray(), &spheres).map(|(index, _)| index), Some(1));
+        assert_eq!(first_hit::<Sphere>(&x_ray(), &[]), None);
+        let triangle = Triangle::new((2.5, -1.0, -1.0), (2.5, 1.0, -1.0), (2.5, 0.0, 1.0));
+        let scene: [&dyn RayIntersectable; 3] = [&plane, &sphere, &triangle];
+        let (index, t) = first_hit(&x_ray(), scene).unwrap();
+        assert_eq!(index, 1);
+        assert!((t - 2.0).abs() < 1e-12);
+        let scene: [&dyn RayIntersectable; 2] = [&plane, &triangle];
+        assert_eq!(first_hit(&x_ray(), scene).map(|(index, _)| index), Some(1));
+    }
+
+    #[test]
+    fn test_first_hit_ties() {
+        let plane = Plane::from_point(Vec3d::i(), Vec3d::new(5.0, 0.0, 0.0));
+        let touching = Sphere::new(Vec3d::new(6.0, 0.0, 0.0), 1.0);
+        let scene: [&dyn RayIntersectable; 3] = [&touching, &plane, &plane];
+        assert_eq!(first_hit(&x_ray(), scene).map(|(index, _)| index), Some(0));
+        let scene: [&dyn RayIntersectable; 2] = [&plane, &touching];
+        assert_eq!(first_hit(&x_ray(), scene).map(|(index, _)| index), Some(0));
+    }
+}
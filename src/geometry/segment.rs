@@ -0,0 +1,59 @@
+use crate::geometry::ScaleGeometry;
+use crate::quat::Quat;
+use crate::vec3d::Vec3d;
+
+/// A line segment in 3d space between two points
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Segment {
+    /// The start point of the segment
+    pub start: Vec3d,
+    /// The end point of the segment
+    pub end: Vec3d
+}
+
+impl Segment {
+    /// Create a new segment
+    pub fn new(start: impl Into<Vec3d>, end: impl Into<Vec3d>) -> Segment {
+        Segment {
+            start: start.into(),
+            end: end.into()
+        }
+    }
+
+    /// Get the vector from the start to the end of the segment
+    pub fn vector(&self) -> Vec3d {
+        self.end - self.start
+    }
+
+    /// Get the length of the segment
+    pub fn length(&self) -> f64 {
+        self.start.distance_to(&self.end)
+    }
+
+    /// Get the midpoint of the segment
+    pub fn midpoint(&self) -> Vec3d {
+        (self.start + self.end) / 2.0
+    }
+
+    /// Get the point a fraction t of the way along the segment
+    /// t = 0 is the start and t = 1 is the end
+    pub fn point_at(&self, t: f64) -> Vec3d {
+        self.start + self.vector() * t
+    }
+
+    /// Get the segment rotated about the origin by a quaternion, see `Quat::rotate`
+    pub fn rotated(&self, q: &Quat) -> Segment {
+        Segment { start: q.rotate(&self.start), end: q.rotate(&self.end) }
+    }
+
+    /// Get the segment moved by an offset
+    pub fn translated(&self, offset: &Vec3d) -> Segment {
+        Segment { start: self.start + offset, end: self.end + offset }
+    }
+}
+
+impl ScaleGeometry for Segment {
+    fn scaled(&self, factor: f64) -> Self {
+        Segment::new(self.start.scaled(factor), self.end.scaled(factor))
+    }
+}
@@ -1,3 +1,10 @@
+use alloc::vec::Vec;
+
+use crate::geometry::{validate_radius, GeometryError, ScaleGeometry};
+#[cfg(not(any(feature = "std", test)))]
+use crate::math::Float;
+use crate::parse::{Fields, ParseError};
+use crate::quat::Quat;
 use crate::vec3d::Vec3d;
 
 /// A sphere in space
@@ -11,15 +18,323 @@ pub struct Sphere {
 
 impl Sphere {
     /// Create a new sphere
-    pub fn new(center: &Vec3d, radius: f64) -> Sphere {
+    /// the radius is made positive without any other checks, use `try_new` to reject invalid input
+    pub fn new(center: impl Into<Vec3d>, radius: f64) -> Sphere {
+        Sphere::new_unchecked(center.into(), radius.abs())
+    }
+
+    /// Create a new sphere with a radius that is not negative
+    /// the values are stored as given, so the radius must satisfy that invariant for the other methods to be correct
+    pub const fn new_unchecked(center: Vec3d, radius: f64) -> Sphere {
         Sphere {
-            center: *center,
-            radius: radius.abs()
+            center,
+            radius
         }
     }
 
+    /// Create a new sphere, checking that the inputs describe a valid sphere
+    ///
+    /// # Errors
+    /// Returns an error if any value is not finite or the radius is negative
+    pub fn try_new(center: impl Into<Vec3d>, radius: f64) -> Result<Sphere, GeometryError> {
+        let center = center.into();
+        if !center.is_finite() {
+            return Err(GeometryError::NonFinite);
+        }
+        validate_radius(radius)?;
+        Ok(Sphere::new(center, radius))
+    }
+
+    /// Check if the sphere is valid
+    /// i.e. every value is finite and the radius is not negative
+    pub fn is_valid(&self) -> bool {
+        self.center.is_finite() && validate_radius(self.radius).is_ok()
+    }
+
     /// Get the volume of the sphere
     pub fn volume(&self) -> f64 {
-        4.0 / 3.0 * std::f64::consts::PI * self.radius.powi(3)
+        4.0 / 3.0 * core::f64::consts::PI * self.radius.powi(3)
+    }
+
+    /// Get the smallest sphere containing both this sphere and a point
+    /// a point that is already inside leaves the sphere unchanged
+    pub fn expanded_to_include_point(&self, point: &Vec3d) -> Sphere {
+        let distance = self.center.distance_to(point);
+        if distance <= self.radius {
+            return *self;
+        }
+        let radius = f64::midpoint(self.radius, distance);
+        let center = self.center + (point - self.center) * ((radius - self.radius) / distance);
+        Sphere::new(center, radius)
+    }
+
+    /// Get the smallest sphere containing both spheres
+    /// if one sphere already contains the other the larger one is returned
+    pub fn merge(&self, other: &Sphere) -> Sphere {
+        let distance = self.center.distance_to(&other.center);
+        // Also covers coincident centers, where the larger sphere always contains the smaller
+        if distance + other.radius <= self.radius {
+            return *self;
+        }
+        if distance + self.radius <= other.radius {
+            return *other;
+        }
+        let radius = (distance + self.radius + other.radius) / 2.0;
+        let center = self.center + (other.center - self.center) * ((radius - self.radius) / distance);
+        Sphere::new(center, radius)
+    }
+
+    /// Get points spread evenly over the surface with a fibonacci spiral
+    /// the points go from near the +z pole to near the -z pole, each covering about the same area
+    #[allow(clippy::cast_precision_loss)]
+    pub fn sample_surface(&self, count: usize) -> Vec<Vec3d> {
+        // The golden angle, so successive points never line up
+        let step = core::f64::consts::PI * (3.0 - 5.0_f64.sqrt());
+        (0..count)
+            .map(|index| {
+                let z = 1.0 - (2.0 * index as f64 + 1.0) / count as f64;
+                let ring = (1.0 - z * z).sqrt();
+                let theta = step * index as f64;
+                self.center + Vec3d::new(ring * theta.cos(), ring * theta.sin(), z) * self.radius
+            })
+            .collect()
+    }
+
+    /// Get a grid of points on the surface at evenly spaced azimuths and polar angles
+    /// the polar angles are at the middle of `n_phi` equal bands so neither pole is repeated
+    /// the points are ordered ring by ring from +z, with `n_theta` points in each ring
+    #[allow(clippy::cast_precision_loss)]
+    pub fn sample_uv(&self, n_theta: usize, n_phi: usize) -> Vec<Vec3d> {
+        let mut points = Vec::with_capacity(n_theta * n_phi);
+        for ring in 0..n_phi {
+            let phi = core::f64::consts::PI * (ring as f64 + 0.5) / n_phi as f64;
+            for index in 0..n_theta {
+                let theta = 2.0 * core::f64::consts::PI * index as f64 / n_theta as f64;
+                let direction = Vec3d::new(phi.sin() * theta.cos(), phi.sin() * theta.sin(), phi.cos());
+                points.push(self.center + direction * self.radius);
+            }
+        }
+        points
+    }
+
+    /// Get the sphere with the same center and the radius grown by a margin
+    /// a negative margin shrinks the sphere, down to a radius of 0
+    pub fn expanded_by(&self, margin: f64) -> Sphere {
+        Sphere::new(self.center, (self.radius + margin).max(0.0))
+    }
+
+    /// Get the sphere rotated about the origin by a quaternion, see `Quat::rotate`
+    pub fn rotated(&self, q: &Quat) -> Sphere {
+        Sphere { center: q.rotate(&self.center), radius: self.radius }
+    }
+
+    /// Get the sphere moved by an offset
+    pub fn translated(&self, offset: &Vec3d) -> Sphere {
+        Sphere { center: self.center + offset, radius: self.radius }
+    }
+}
+
+impl core::fmt::Display for Sphere {
+    /// Format the sphere as "sphere c=(x, y, z) r=radius"
+    /// the precision is applied to every number
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        if let Some(precision) = f.precision() {
+            write!(f, "sphere c={:.2$} r={:.2$}", self.center, self.radius, precision)
+        } else {
+            write!(f, "sphere c={} r={}", self.center, self.radius)
+        }
+    }
+}
+
+impl core::str::FromStr for Sphere {
+    type Err = ParseError;
+
+    /// Parse a sphere from its `Display` output, the values are stored as given
+    fn from_str(s: &str) -> Result<Sphere, ParseError> {
+        let mut fields = Fields::new(s, "sphere")?;
+        let center = fields.vector("c")?;
+        let radius = fields.number("r")?;
+        fields.finish()?;
+        Ok(Sphere::new_unchecked(center, radius))
+    }
+}
+
+impl ScaleGeometry for Sphere {
+    fn scaled(&self, factor: f64) -> Self {
+        Sphere::new(self.center * factor, self.radius * factor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::format;
+    use alloc::string::ToString;
+
+    use super::*;
+    use crate::geometry::intersection::{sphere_plane, sphere_sphere};
+    use crate::geometry::plane::Plane;
+    use crate::units::{LengthScale, LengthUnit};
+
+    #[test]
+    fn test_try_new() {
+        assert!(Sphere::try_new(Vec3d::zero(), 1.0).is_ok());
+        assert_eq!(
+            Sphere::try_new(Vec3d::zero(), -1.0),
+            Err(GeometryError::NegativeRadius)
+        );
+        assert_eq!(
+            Sphere::try_new(Vec3d::new(0.0, f64::INFINITY, 0.0), 1.0),
+            Err(GeometryError::NonFinite)
+        );
+    }
+
+    #[test]
+    fn test_nan_caught_at_construction() {
+        let nan_center = Vec3d::new(0.0, 0.0, f64::NAN);
+        assert_eq!(Sphere::try_new(nan_center, 1.0), Err(GeometryError::NonFinite));
+        let unchecked = Sphere::new(nan_center, 1.0);
+        assert!(!unchecked.is_valid());
+        let circle = sphere_sphere(&unchecked, &Sphere::new(Vec3d::zero(), 1.0));
+        assert!(circle.is_none_or(|circle| !circle.is_valid()));
+    }
+
+    #[test]
+    fn test_scaled_intersection() {
+        let scale = LengthScale::from_to(LengthUnit::Inches, LengthUnit::Millimeters);
+        let sphere = Sphere::new(Vec3d::new(1.0, 2.0, 0.0), 2.0);
+        let plane = Plane::new(Vec3d::k(), -1.0);
+        let circle = sphere_plane(&sphere, &plane).unwrap();
+        let scaled = sphere_plane(&scale.apply(&sphere), &scale.apply(&plane)).unwrap();
+        let expected = circle.scaled(25.4);
+        assert!(scaled.center.distance_to(&expected.center) < 1e-12);
+        assert!((scaled.radius - expected.radius).abs() < 1e-12);
+        assert_eq!(scaled.normal, expected.normal);
+        assert!((expected.radius - 3.0_f64.sqrt() * 25.4).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_expanded_to_include_point() {
+        let sphere = Sphere::new(Vec3d::zero(), 1.0);
+        assert_eq!(sphere.expanded_to_include_point(&Vec3d::new(0.5, 0.0, 0.0)), sphere);
+        let expanded = sphere.expanded_to_include_point(&Vec3d::new(3.0, 0.0, 0.0));
+        assert!(expanded.center.distance_to(&Vec3d::i()) < 1e-12);
+        assert!((expanded.radius - 2.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_merge() {
+        let sphere1 = Sphere::new(Vec3d::zero(), 1.0);
+        let sphere2 = Sphere::new(Vec3d::new(0.0, 5.0, 0.0), 2.0);
+        let merged = sphere1.merge(&sphere2);
+        assert!((merged.radius - (5.0 + 1.0 + 2.0) / 2.0).abs() < 1e-12);
+        assert!(merged.center.distance_to(&Vec3d::new(0.0, 3.0, 0.0)) < 1e-12);
+        let reversed = sphere2.merge(&sphere1);
+        assert!(reversed.center.distance_to(&merged.center) < 1e-12);
+        assert!((reversed.radius - merged.radius).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_merge_nested() {
+        let outer = Sphere::new(Vec3d::zero(), 3.0);
+        let inner = Sphere::new(Vec3d::new(1.0, 0.0, 0.0), 1.0);
+        assert_eq!(outer.merge(&inner), outer);
+        assert_eq!(inner.merge(&outer), outer);
+        let concentric = Sphere::new(Vec3d::zero(), 2.0);
+        assert_eq!(concentric.merge(&outer), outer);
+        assert_eq!(outer.merge(&outer), outer);
+    }
+
+    #[test]
+    fn test_expanded_by() {
+        let sphere = Sphere::new(Vec3d::i(), 1.0);
+        assert_eq!(sphere.expanded_by(0.5), Sphere::new(Vec3d::i(), 1.5));
+        assert_eq!(sphere.expanded_by(-2.0), Sphere::new(Vec3d::i(), 0.0));
+    }
+
+    #[test]
+    fn test_const_constructor() {
+        const BALL: Sphere = Sphere::new_unchecked(Vec3d::zero(), 2.0);
+        assert_eq!(BALL, Sphere::new(Vec3d::zero(), -2.0));
+    }
+
+    #[test]
+    fn test_sample_surface() {
+        let sphere = Sphere::new(Vec3d::new(1.0, -2.0, 0.5), 3.0);
+        let points = sphere.sample_surface(200);
+        assert_eq!(points.len(), 200);
+        for point in &points {
+            assert!((point.distance_to(&sphere.center) - 3.0).abs() < 1e-12);
+        }
+        // Spread evenly so the centroid is close to the center
+        let centroid = points.iter().fold(Vec3d::zero(), |sum, point| sum + *point) / 200.0;
+        assert!(centroid.distance_to(&sphere.center) < 0.05);
+        assert!(sphere.sample_surface(0).is_empty());
+    }
+
+    #[test]
+    fn test_sample_uv() {
+        let sphere = Sphere::new(Vec3d::zero(), 2.0);
+        let points = sphere.sample_uv(8, 5);
+        assert_eq!(points.len(), 40);
+        for point in &points {
+            assert!((point.magnitude() - 2.0).abs() < 1e-12);
+        }
+        // The middle ring is the equator
+        assert!(points[16].z.abs() < 1e-12);
+        assert!((points[16] - Vec3d::new(2.0, 0.0, 0.0)).magnitude() < 1e-12);
+    }
+
+    #[test]
+    fn test_constructor_inputs() {
+        let center = Vec3d::new(0.0, 0.0, 1.0);
+        let expected = Sphere::new(center, 1.0);
+        let center_ref: &Vec3d = &expected.center;
+        assert_eq!(Sphere::new(center_ref, 1.0), expected);
+        assert_eq!(Sphere::new_unchecked(center, 1.0), expected);
+        assert_eq!(Sphere::new([0.0, 0.0, 1.0], 1.0), expected);
+        assert_eq!(Sphere::new((0.0, 0.0, 1.0), 1.0), expected);
+        assert_eq!(Sphere::new(Vec3d::k() * 2.0 - Vec3d::k(), 1.0), expected);
+        assert_eq!(Sphere::try_new([0.0, 0.0, 1.0], 1.0), Ok(expected));
+    }
+
+    #[test]
+    fn test_from_str_round_trip() {
+        crate::testing::for_all(100, |sphere: Sphere| {
+            assert_eq!(sphere.to_string().parse::<Sphere>(), Ok(sphere));
+        });
+        let sphere = Sphere::new((1.0, 2.0, 3.0), 0.5);
+        assert_eq!(sphere.to_string(), "sphere c=(1, 2, 3) r=0.5");
+        assert_eq!(format!("{sphere:.1}"), "sphere c=(1.0, 2.0, 3.0) r=0.5");
+        assert_eq!("  sphere   c = (1,2,3)\tr=  0.5 ".parse::<Sphere>(), Ok(sphere));
+    }
+
+    #[test]
+    fn test_from_str_errors() {
+        assert_eq!(
+            "ball c=(0, 0, 0) r=1".parse::<Sphere>(),
+            Err(ParseError::Expected { expected: "sphere", found: "ball".to_string() })
+        );
+        assert_eq!(
+            "sphere r=1".parse::<Sphere>(),
+            Err(ParseError::MissingField { field: "c", found: "r=1".to_string() })
+        );
+        assert_eq!(
+            "sphere c=(0, 0, 0) r=big".parse::<Sphere>(),
+            Err(ParseError::InvalidValue { field: "r", found: "big".to_string() })
+        );
+        assert_eq!(
+            "sphere c=(0, 0, 0) r=1 extra".parse::<Sphere>(),
+            Err(ParseError::TrailingText("extra".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_rotated_and_translated() {
+        let sphere = Sphere::new((1.0, 0.0, 2.0), 0.5);
+        let q = Quat::from_axis_angle(&Vec3d::k(), core::f64::consts::FRAC_PI_2);
+        let rotated = sphere.rotated(&q);
+        assert!((rotated.center - Vec3d::new(0.0, 1.0, 2.0)).magnitude() < 1e-15);
+        assert!((rotated.radius - 0.5).abs() < f64::EPSILON);
+        assert_eq!(sphere.translated(&Vec3d::new(-1.0, 1.0, 0.0)), Sphere::new((0.0, 1.0, 2.0), 0.5));
     }
 }
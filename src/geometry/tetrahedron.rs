@@ -0,0 +1,126 @@
+use crate::geometry::ScaleGeometry;
+use crate::vec3d::Vec3d;
+
+/// A tetrahedron in 3d space defined by its four vertices
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Tetrahedron {
+    /// The first vertex of the tetrahedron
+    pub a: Vec3d,
+    /// The second vertex of the tetrahedron
+    pub b: Vec3d,
+    /// The third vertex of the tetrahedron
+    pub c: Vec3d,
+    /// The fourth vertex of the tetrahedron
+    pub d: Vec3d
+}
+
+impl Tetrahedron {
+    /// Create a new tetrahedron
+    pub fn new(a: impl Into<Vec3d>, b: impl Into<Vec3d>, c: impl Into<Vec3d>, d: impl Into<Vec3d>) -> Tetrahedron {
+        Tetrahedron {
+            a: a.into(),
+            b: b.into(),
+            c: c.into(),
+            d: d.into()
+        }
+    }
+
+    /// Get the signed volume of the tetrahedron
+    /// the volume is positive when b - a, c - a, and d - a form a right handed system
+    /// swapping any two vertices flips the sign
+    pub fn signed_volume(&self) -> f64 {
+        signed_volume(&self.a, &self.b, &self.c, &self.d)
+    }
+
+    /// Get the volume of the tetrahedron
+    pub fn volume(&self) -> f64 {
+        self.signed_volume().abs()
+    }
+
+    /// Get the centroid of the tetrahedron
+    /// i.e. the average of the four vertices
+    pub fn centroid(&self) -> Vec3d {
+        (self.a + self.b + self.c + self.d) / 4.0
+    }
+
+    /// Check if a point is inside the tetrahedron
+    /// points on the boundary are considered inside
+    /// a degenerate tetrahedron with no volume contains no points
+    pub fn contains_point(&self, point: &Vec3d) -> bool {
+        let volume = self.signed_volume();
+        if volume == 0.0 {
+            return false;
+        }
+        // Each sub volume is the barycentric weight of the vertex it replaces scaled by the total volume
+        let weights = [
+            signed_volume(point, &self.b, &self.c, &self.d),
+            signed_volume(&self.a, point, &self.c, &self.d),
+            signed_volume(&self.a, &self.b, point, &self.d),
+            signed_volume(&self.a, &self.b, &self.c, point)
+        ];
+        weights.iter().all(|weight| weight / volume >= 0.0)
+    }
+}
+
+impl ScaleGeometry for Tetrahedron {
+    fn scaled(&self, factor: f64) -> Self {
+        Tetrahedron::new(self.a.scaled(factor), self.b.scaled(factor), self.c.scaled(factor), self.d.scaled(factor))
+    }
+}
+
+/// Calculate the volume of the parallelepiped spanned by three edge vectors
+/// the result is signed and is positive for a right handed set of edges
+pub fn parallelepiped_volume(a: &Vec3d, b: &Vec3d, c: &Vec3d) -> f64 {
+    Vec3d::scalar_triple_product(a, b, c)
+}
+
+fn signed_volume(a: &Vec3d, b: &Vec3d, c: &Vec3d, d: &Vec3d) -> f64 {
+    parallelepiped_volume(&(b - a), &(c - a), &(d - a)) / 6.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn unit_tetrahedron() -> Tetrahedron {
+        Tetrahedron::new(Vec3d::zero(), Vec3d::i(), Vec3d::j(), Vec3d::k())
+    }
+
+    #[test]
+    fn test_volume() {
+        let tetrahedron = unit_tetrahedron();
+        assert!((tetrahedron.volume() - 1.0 / 6.0).abs() < f64::EPSILON);
+        assert!((tetrahedron.signed_volume() - 1.0 / 6.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_signed_volume_orientation() {
+        let tetrahedron = unit_tetrahedron();
+        let flipped = Tetrahedron::new(tetrahedron.a, tetrahedron.c, tetrahedron.b, tetrahedron.d);
+        assert!((flipped.signed_volume() + tetrahedron.signed_volume()).abs() < f64::EPSILON);
+        assert!((flipped.volume() - tetrahedron.volume()).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_centroid() {
+        let tetrahedron = unit_tetrahedron();
+        assert_eq!(tetrahedron.centroid(), Vec3d::new(0.25, 0.25, 0.25));
+    }
+
+    #[test]
+    fn test_contains_point() {
+        let tetrahedron = unit_tetrahedron();
+        let flipped = Tetrahedron::new(tetrahedron.b, tetrahedron.a, tetrahedron.c, tetrahedron.d);
+        assert!(tetrahedron.contains_point(&tetrahedron.centroid()));
+        assert!(flipped.contains_point(&flipped.centroid()));
+        assert!(tetrahedron.contains_point(&Vec3d::i()));
+        assert!(!tetrahedron.contains_point(&Vec3d::new(1.0, 1.0, 1.0)));
+        assert!(!flipped.contains_point(&Vec3d::new(-0.1, 0.2, 0.2)));
+    }
+
+    #[test]
+    fn test_parallelepiped_volume() {
+        let volume = parallelepiped_volume(&Vec3d::i(), &Vec3d::j(), &(Vec3d::k() * 2.0));
+        assert!((volume - 2.0).abs() < f64::EPSILON);
+    }
+}
@@ -0,0 +1,144 @@
+use crate::geometry::plane::Plane;
+use crate::geometry::ScaleGeometry;
+use crate::quat::Quat;
+use crate::vec3d::Vec3d;
+
+/// A triangle in 3d space defined by its three vertices
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Triangle {
+    /// The first vertex of the triangle
+    pub a: Vec3d,
+    /// The second vertex of the triangle
+    pub b: Vec3d,
+    /// The third vertex of the triangle
+    pub c: Vec3d
+}
+
+impl Triangle {
+    /// Create a new triangle
+    pub fn new(a: impl Into<Vec3d>, b: impl Into<Vec3d>, c: impl Into<Vec3d>) -> Triangle {
+        Triangle {
+            a: a.into(),
+            b: b.into(),
+            c: c.into()
+        }
+    }
+
+    /// Get the vector normal to the triangle with a length of twice its area
+    /// it points towards the side the vertices appear counter-clockwise from
+    pub fn area_vector(&self) -> Vec3d {
+        (self.b - self.a).cross(&(self.c - self.a))
+    }
+
+    /// Get the area of the triangle
+    pub fn area(&self) -> f64 {
+        self.area_vector().magnitude() / 2.0
+    }
+
+    /// Get the plane containing the triangle
+    /// the normal follows `area_vector`, a degenerate triangle with no area produces NaN geometry
+    pub fn plane(&self) -> Plane {
+        Plane::from_points(self.a, self.b, self.c)
+    }
+
+    /// Get the centroid of the triangle
+    /// i.e. the average of the three vertices
+    pub fn centroid(&self) -> Vec3d {
+        (self.a + self.b + self.c) / 3.0
+    }
+
+    /// Get the point on the triangle, including its interior, closest to a point
+    /// the point is found by checking which vertex, edge, or face region of the triangle it projects into
+    pub fn closest_point(&self, point: &Vec3d) -> Vec3d {
+        let ab = self.b - self.a;
+        let ac = self.c - self.a;
+        let ap = point - self.a;
+        let d1 = ab.dot(&ap);
+        let d2 = ac.dot(&ap);
+        if d1 <= 0.0 && d2 <= 0.0 {
+            return self.a;
+        }
+        let bp = point - self.b;
+        let d3 = ab.dot(&bp);
+        let d4 = ac.dot(&bp);
+        if d3 >= 0.0 && d4 <= d3 {
+            return self.b;
+        }
+        let vc = d1 * d4 - d3 * d2;
+        if vc <= 0.0 && d1 >= 0.0 && d3 <= 0.0 {
+            return self.a + ab * (d1 / (d1 - d3));
+        }
+        let cp = point - self.c;
+        let d5 = ab.dot(&cp);
+        let d6 = ac.dot(&cp);
+        if d6 >= 0.0 && d5 <= d6 {
+            return self.c;
+        }
+        let vb = d5 * d2 - d1 * d6;
+        if vb <= 0.0 && d2 >= 0.0 && d6 <= 0.0 {
+            return self.a + ac * (d2 / (d2 - d6));
+        }
+        let va = d3 * d6 - d5 * d4;
+        if va <= 0.0 && d4 - d3 >= 0.0 && d5 - d6 >= 0.0 {
+            return self.b + (self.c - self.b) * ((d4 - d3) / ((d4 - d3) + (d5 - d6)));
+        }
+        let denominator = va + vb + vc;
+        self.a + ab * (vb / denominator) + ac * (vc / denominator)
+    }
+
+    /// Get the triangle rotated about the origin by a quaternion, see `Quat::rotate`
+    pub fn rotated(&self, q: &Quat) -> Triangle {
+        Triangle { a: q.rotate(&self.a), b: q.rotate(&self.b), c: q.rotate(&self.c) }
+    }
+
+    /// Get the triangle moved by an offset
+    pub fn translated(&self, offset: &Vec3d) -> Triangle {
+        Triangle { a: self.a + offset, b: self.b + offset, c: self.c + offset }
+    }
+}
+
+impl ScaleGeometry for Triangle {
+    fn scaled(&self, factor: f64) -> Self {
+        Triangle::new(self.a.scaled(factor), self.b.scaled(factor), self.c.scaled(factor))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn unit_triangle() -> Triangle {
+        Triangle::new(Vec3d::zero(), Vec3d::i(), Vec3d::j())
+    }
+
+    #[test]
+    fn test_area_and_plane() {
+        let triangle = unit_triangle();
+        assert!((triangle.area() - 0.5).abs() < f64::EPSILON);
+        assert_eq!(triangle.plane(), Plane::xy());
+        assert_eq!(triangle.area_vector(), Vec3d::k());
+    }
+
+    #[test]
+    fn test_closest_point() {
+        let triangle = unit_triangle();
+        let above = triangle.closest_point(&Vec3d::new(0.2, 0.2, 3.0));
+        assert!((above - Vec3d::new(0.2, 0.2, 0.0)).magnitude() < 1e-15);
+        assert_eq!(triangle.closest_point(&Vec3d::new(-1.0, -1.0, 1.0)), Vec3d::zero());
+        assert_eq!(triangle.closest_point(&Vec3d::new(2.0, -0.5, 0.0)), Vec3d::i());
+        assert_eq!(triangle.closest_point(&Vec3d::new(0.5, -2.0, 0.0)), Vec3d::new(0.5, 0.0, 0.0));
+        let hypotenuse = triangle.closest_point(&Vec3d::new(1.0, 1.0, 0.0));
+        assert!((hypotenuse - Vec3d::new(0.5, 0.5, 0.0)).magnitude() < 1e-15);
+    }
+
+    #[test]
+    fn test_rotated_and_translated() {
+        let triangle = unit_triangle();
+        let q = Quat::from_axis_angle(&Vec3d::i(), core::f64::consts::FRAC_PI_2);
+        let rotated = triangle.rotated(&q);
+        assert!((rotated.area_vector() - q.rotate(&triangle.area_vector())).magnitude() < 1e-15);
+        assert!((rotated.area() - triangle.area()).abs() < 1e-15);
+        let offset = Vec3d::new(1.0, 2.0, 3.0);
+        assert_eq!(triangle.translated(&offset).centroid(), triangle.centroid() + offset);
+    }
+}
@@ -1,3 +1,4 @@
+#![cfg_attr(not(feature = "std"), no_std)]
 #![deny(missing_docs)]
 #![warn(clippy::pedantic)]
 #![allow(clippy::must_use_candidate, clippy::many_single_char_names, clippy::return_self_not_must_use)]
@@ -7,6 +8,17 @@
 //! this library is not focused on performance
 //! I have since added geometry stuff too!
 
+#[cfg(not(any(feature = "std", feature = "libm")))]
+compile_error!("either the std or libm feature must be enabled for float math");
+
+extern crate alloc;
+
+#[macro_use]
+mod macros;
+// Tests always link std, so its inherent f64 methods are used there instead of the libm ones
+#[cfg(not(any(feature = "std", test)))]
+mod math;
+
 /// 3D vector operations and functions
 pub mod vec3d;
 /// quaternion operations and functions
@@ -14,6 +26,7 @@ pub mod quat;
 /// Functions for working with matrices
 /// currently only 2x2, 3x3, and 4x4 matrices are supported
 /// with functions for calculating the determinant, minor, and cofactor
+/// along with a dynamically sized matrix for dimensions only known at runtime
 pub mod matrix;
 /// Complex number operations and functions
 pub mod complex;
@@ -23,3 +36,12 @@ pub mod units;
 pub mod angle;
 /// 3d geometry operations and functions
 pub mod geometry;
+/// The crate wide error type
+pub mod error;
+/// Parsing vectors and shapes from text
+pub mod parse;
+/// Random generators and invariant checks for property tests
+#[cfg(any(test, feature = "testing"))]
+pub mod testing;
+
+pub use error::Error;
@@ -0,0 +1,323 @@
+/// Define a quantity stored as a single `f64` in its base unit
+/// generates the newtype, its unit enum, the unit constructors and accessors, `new`, `as_unit`, `format_in`,
+/// `Display`, `FromStr`, the `Quantity` impl, and the serde glue
+///
+/// each unit is listed as `Variant => constructor, accessor, "unit name", ["abbreviation", aliases...], factor[, offset]`
+/// a value in the unit is converted to the base unit as `value * factor + offset`
+/// the first abbreviation is the one used by `abbreviation` and `format_in`
+///
+/// `linear` quantities also get addition, subtraction, negation, and scaling by `f64`
+/// `affine` quantities such as absolute temperatures get no arithmetic
+/// `use Unit` in place of `enum Unit` reuses an existing unit enum, in which case the abbreviations are left out
+macro_rules! define_unit {
+    (
+        $(#[$meta:meta])*
+        $kind:ident $name:ident($noun:literal, $base:ident, $symbol:literal);
+        $(#[$unit_meta:meta])*
+        enum $unit:ident {
+            $($variant:ident => $ctor:ident, $accessor:ident, $words:literal, [$abbreviation:literal $(, $alias:literal)*], $factor:expr $(, $offset:expr)?;)+
+        }
+    ) => {
+        $(#[$unit_meta])*
+        #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+        pub enum $unit {
+            $(
+                #[doc = $words]
+                $variant
+            ),+
+        }
+
+        impl $unit {
+            #[doc = concat!("Every ", $noun, " unit")]
+            pub const ALL: [$unit; [$(stringify!($variant)),+].len()] = [$($unit::$variant),+];
+
+            /// The recognized abbreviations of each unit when parsing
+            const SUFFIXES: &'static [(&'static str, $unit)] = &[$(($abbreviation, $unit::$variant), $(($alias, $unit::$variant),)*)+];
+
+            /// Get the abbreviation of the unit
+            pub fn abbreviation(&self) -> &'static str {
+                match self {
+                    $($unit::$variant => $abbreviation),+
+                }
+            }
+        }
+
+        define_unit! {
+            $(#[$meta])*
+            $kind $name($noun, $base, $symbol);
+            use $unit {
+                $($variant => $ctor, $accessor, $words, $factor $(, $offset)?;)+
+            }
+        }
+    };
+    (
+        $(#[$meta:meta])*
+        linear $name:ident($noun:literal, $base:ident, $symbol:literal);
+        use $unit:ident {
+            $($variant:ident => $ctor:ident, $accessor:ident, $words:literal, $factor:expr $(, $offset:expr)?;)+
+        }
+    ) => {
+        define_unit! {
+            $(#[$meta])*
+            affine $name($noun, $base, $symbol);
+            use $unit {
+                $($variant => $ctor, $accessor, $words, $factor $(, $offset)?;)+
+            }
+        }
+
+        impl core::ops::Add for $name {
+            type Output = $name;
+
+            fn add(self, rhs: $name) -> $name {
+                $name { $base: self.$base + rhs.$base }
+            }
+        }
+
+        impl core::ops::Sub for $name {
+            type Output = $name;
+
+            fn sub(self, rhs: $name) -> $name {
+                $name { $base: self.$base - rhs.$base }
+            }
+        }
+
+        impl core::ops::Mul<f64> for $name {
+            type Output = $name;
+
+            fn mul(self, rhs: f64) -> $name {
+                $name { $base: self.$base * rhs }
+            }
+        }
+
+        $crate::impl_commutative_op!(Mul, mul for f64, $name);
+
+        impl core::ops::Div<f64> for $name {
+            type Output = $name;
+
+            fn div(self, rhs: f64) -> $name {
+                $name { $base: self.$base / rhs }
+            }
+        }
+
+        impl core::ops::Neg for $name {
+            type Output = $name;
+
+            fn neg(self) -> $name {
+                $name { $base: -self.$base }
+            }
+        }
+    };
+    (
+        $(#[$meta:meta])*
+        affine $name:ident($noun:literal, $base:ident, $symbol:literal);
+        use $unit:ident {
+            $($variant:ident => $ctor:ident, $accessor:ident, $words:literal, $factor:expr $(, $offset:expr)?;)+
+        }
+    ) => {
+        $(#[$meta])*
+        #[derive(Debug, PartialOrd, PartialEq, Clone, Copy)]
+        pub struct $name {
+            $base: f64
+        }
+
+        impl $name {
+            $(
+                #[doc = concat!("Create a new ", $noun, " in ", $words)]
+                pub fn $ctor(value: f64) -> Self {
+                    Self { $base: value * $factor $(+ $offset)? }
+                }
+            )+
+
+            $(
+                #[doc = concat!("Get the ", $noun, " in ", $words)]
+                pub fn $accessor(&self) -> f64 {
+                    (self.$base $(- $offset)?) / $factor
+                }
+            )+
+
+            #[doc = concat!("Create a new ", $noun, " from a value in the given unit")]
+            pub fn new(value: f64, unit: $unit) -> Self {
+                match unit {
+                    $($unit::$variant => Self::$ctor(value)),+
+                }
+            }
+
+            #[doc = concat!("Get the ", $noun, " in the given unit")]
+            pub fn as_unit(&self, unit: $unit) -> f64 {
+                match unit {
+                    $($unit::$variant => self.$accessor()),+
+                }
+            }
+
+            #[doc = concat!("Format the ", $noun, " in the given unit with a fixed number of decimal places")]
+            pub fn format_in(&self, unit: $unit, precision: usize) -> alloc::string::String {
+                alloc::format!("{:.1$} {2}", self.as_unit(unit), precision, unit.abbreviation())
+            }
+        }
+
+        impl $crate::units::Quantity for $name {
+            type Unit = $unit;
+
+            fn from_base(value: f64) -> Self {
+                Self { $base: value }
+            }
+
+            fn base_value(&self) -> f64 {
+                self.$base
+            }
+
+            fn new(value: f64, unit: $unit) -> Self {
+                $name::new(value, unit)
+            }
+
+            fn as_unit(&self, unit: $unit) -> f64 {
+                $name::as_unit(self, unit)
+            }
+        }
+
+        impl core::str::FromStr for $name {
+            type Err = $crate::units::UnitParseError;
+
+            /// Parse a number followed by a unit abbreviation such as "12.7 mm"
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                let (value, unit) = $crate::units::parse_with_unit(s, $unit::SUFFIXES)?;
+                Ok($name::new(value, unit))
+            }
+        }
+
+        impl core::fmt::Display for $name {
+            fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+                if let Some(precision) = f.precision() {
+                    write!(f, concat!("{:.1$} ", $symbol), self.$base, precision)
+                } else {
+                    write!(f, concat!("{} ", $symbol), self.$base)
+                }
+            }
+        }
+
+        #[cfg(feature = "serde")]
+        impl serde::Serialize for $name {
+            fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.serialize_f64(self.$base)
+            }
+        }
+
+        #[cfg(feature = "serde")]
+        impl<'de> serde::Deserialize<'de> for $name {
+            fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                deserializer.deserialize_any($crate::units::QuantityVisitor::<$name>(core::marker::PhantomData))
+            }
+        }
+    };
+}
+
+/// Implement the owned and mixed reference variants of a binary operator
+/// from an existing implementation on two references
+///
+/// `impl_ref_ops!(Add, add for Lhs, Rhs => Output)` needs `&Lhs: Add<&Rhs, Output = Output>`
+/// and adds `Lhs + Rhs`, `Lhs + &Rhs`, and `&Lhs + Rhs` on top of it
+/// several operators can be listed at once separated by `;`
+///
+/// ```
+/// use vec_utils::impl_ref_ops;
+///
+/// #[derive(Debug, Clone, Copy, PartialEq)]
+/// struct Meters(f64);
+///
+/// impl core::ops::Add<&Meters> for &Meters {
+///     type Output = Meters;
+///
+///     fn add(self, other: &Meters) -> Meters {
+///         Meters(self.0 + other.0)
+///     }
+/// }
+///
+/// impl_ref_ops!(Add, add for Meters, Meters => Meters);
+///
+/// let a = Meters(1.0);
+/// let b = Meters(2.0);
+/// assert_eq!(a + b, Meters(3.0));
+/// assert_eq!(&a + b, Meters(3.0));
+/// assert_eq!(a + &b, Meters(3.0));
+/// assert_eq!(&a + &b, Meters(3.0));
+/// ```
+///
+/// the reference implementation has to exist, the generated impls do not compile without it
+///
+/// ```compile_fail
+/// use vec_utils::impl_ref_ops;
+///
+/// #[derive(Debug, Clone, Copy, PartialEq)]
+/// struct Meters(f64);
+///
+/// impl_ref_ops!(Add, add for Meters, Meters => Meters);
+/// ```
+#[macro_export]
+macro_rules! impl_ref_ops {
+    ($($trait:ident, $method:ident for $lhs:ty, $rhs:ty => $output:ty);+ $(;)?) => {
+        $(
+            impl ::core::ops::$trait<$rhs> for $lhs {
+                type Output = $output;
+
+                fn $method(self, other: $rhs) -> $output {
+                    ::core::ops::$trait::$method(&self, &other)
+                }
+            }
+
+            impl ::core::ops::$trait<&$rhs> for $lhs {
+                type Output = $output;
+
+                fn $method(self, other: &$rhs) -> $output {
+                    ::core::ops::$trait::$method(&self, other)
+                }
+            }
+
+            impl ::core::ops::$trait<$rhs> for &$lhs {
+                type Output = $output;
+
+                fn $method(self, other: $rhs) -> $output {
+                    ::core::ops::$trait::$method(self, &other)
+                }
+            }
+        )+
+    };
+}
+
+/// Implement a commutative operator with the operands swapped
+///
+/// `impl_commutative_op!(Mul, mul for f64, Vector)` needs `Vector: Mul<f64>`
+/// and adds `f64 * Vector` by evaluating `Vector * f64`
+/// so it should only be used for operators that really are commutative
+///
+/// ```
+/// use vec_utils::impl_commutative_op;
+///
+/// #[derive(Debug, Clone, Copy, PartialEq)]
+/// struct Meters(f64);
+///
+/// impl core::ops::Mul<f64> for Meters {
+///     type Output = Meters;
+///
+///     fn mul(self, other: f64) -> Meters {
+///         Meters(self.0 * other)
+///     }
+/// }
+///
+/// impl_commutative_op!(Mul, mul for f64, Meters);
+///
+/// assert_eq!(2.0 * Meters(1.5), Meters(3.0));
+/// ```
+#[macro_export]
+macro_rules! impl_commutative_op {
+    ($($trait:ident, $method:ident for $lhs:ty, $rhs:ty);+ $(;)?) => {
+        $(
+            impl ::core::ops::$trait<$rhs> for $lhs {
+                type Output = <$rhs as ::core::ops::$trait<$lhs>>::Output;
+
+                fn $method(self, other: $rhs) -> Self::Output {
+                    ::core::ops::$trait::$method(other, self)
+                }
+            }
+        )+
+    };
+}
@@ -0,0 +1,73 @@
+//! Float math for builds without the standard library
+//! the f64 methods below are only inherent with std, so without it they are provided by libm
+
+/// The std only f64 methods used by the crate
+pub(crate) trait Float {
+    fn sqrt(self) -> f64;
+    fn powi(self, n: i32) -> f64;
+    fn sin(self) -> f64;
+    fn cos(self) -> f64;
+    fn tan(self) -> f64;
+    fn acos(self) -> f64;
+    fn atan2(self, other: f64) -> f64;
+    fn hypot(self, other: f64) -> f64;
+    fn ln(self) -> f64;
+    fn exp(self) -> f64;
+    fn cbrt(self) -> f64;
+    fn rem_euclid(self, rhs: f64) -> f64;
+}
+
+impl Float for f64 {
+    fn sqrt(self) -> f64 {
+        libm::sqrt(self)
+    }
+
+    fn powi(self, n: i32) -> f64 {
+        libm::pow(self, f64::from(n))
+    }
+
+    fn sin(self) -> f64 {
+        libm::sin(self)
+    }
+
+    fn cos(self) -> f64 {
+        libm::cos(self)
+    }
+
+    fn tan(self) -> f64 {
+        libm::tan(self)
+    }
+
+    fn acos(self) -> f64 {
+        libm::acos(self)
+    }
+
+    fn atan2(self, other: f64) -> f64 {
+        libm::atan2(self, other)
+    }
+
+    fn hypot(self, other: f64) -> f64 {
+        libm::hypot(self, other)
+    }
+
+    fn ln(self) -> f64 {
+        libm::log(self)
+    }
+
+    fn exp(self) -> f64 {
+        libm::exp(self)
+    }
+
+    fn cbrt(self) -> f64 {
+        libm::cbrt(self)
+    }
+
+    fn rem_euclid(self, rhs: f64) -> f64 {
+        let remainder = libm::fmod(self, rhs);
+        if remainder < 0.0 {
+            remainder + rhs.abs()
+        } else {
+            remainder
+        }
+    }
+}
@@ -1,6 +1,11 @@
+/// Matrices with dimensions only known at runtime
+pub mod dynamic;
+
 /// Functions for working with 2x2 matrices
 pub mod matrix2x2{
     use crate::complex::Complex;
+    #[cfg(not(any(feature = "std", test)))]
+    use crate::math::Float;
 
     /// Calculate the determinant of a 2x2 matrix
     pub fn determinant(matrix: &[[f64; 2]; 2]) -> f64 {
@@ -19,35 +24,87 @@ pub mod matrix2x2{
     }
 
     /// Calculate the eigenvectors of a 2x2 matrix
-    /// returns a tuple of the eigenvectors as 2D arrays
-    pub fn eigenvectors(matrix: &[[f64; 2]; 2]) -> ([f64; 2], [f64; 2]) {
+    /// returns unit eigenvectors in the same order as the eigenvalues from `eigenvalues`
+    /// or None if the eigenvalues are complex
+    /// the sign is chosen so the first non zero component is positive
+    /// a defective matrix such as a shear only has one eigenvector so it is returned twice
+    /// a multiple of the identity has every vector as an eigenvector so the standard basis is returned
+    pub fn eigenvectors(matrix: &[[f64; 2]; 2]) -> Option<([f64; 2], [f64; 2])> {
         let (eigenvalue1, eigenvalue2) = eigenvalues(matrix);
-        let mut eigenvector1 = [0.0; 2];
-        let mut eigenvector2 = [0.0; 2];
-        if eigenvalue1.imaginary == 0.0 {
-            if matrix[0][0] - eigenvalue1.real != 0.0 {
-                eigenvector1[0] = matrix[0][1] / (matrix[0][0] - eigenvalue1.real);
-                eigenvector1[1] = 1.0;
-            } else if matrix[1][0] != 0.0 {
-                eigenvector1[0] = 1.0;
-                eigenvector1[1] = matrix[1][1] / (matrix[1][0] - eigenvalue1.real);
-            }
+        if eigenvalue1.imaginary != 0.0 || eigenvalue2.imaginary != 0.0 {
+            return None;
         }
-        if eigenvalue2.imaginary == 0.0 {
-            if matrix[0][0] - eigenvalue2.real != 0.0 {
-                eigenvector2[0] = matrix[0][1] / (matrix[0][0] - eigenvalue2.real);
-                eigenvector2[1] = 1.0;
-            } else if matrix[1][0] != 0.0 {
-                eigenvector2[0] = 1.0;
-                eigenvector2[1] = matrix[1][1] / (matrix[1][0] - eigenvalue2.real);
-            }
+        let scale = matrix.iter().flatten().fold(0.0_f64, |max, value| max.max(value.abs()));
+        let tolerance = scale * 1e-12;
+        Some((
+            null_vector(matrix, eigenvalue1.real, tolerance).unwrap_or([1.0, 0.0]),
+            null_vector(matrix, eigenvalue2.real, tolerance).unwrap_or([0.0, 1.0])
+        ))
+    }
+
+    /// Get a unit vector in the null space of matrix - eigenvalue * I
+    /// or None if matrix - eigenvalue * I is zero within the tolerance and every vector is in it
+    fn null_vector(matrix: &[[f64; 2]; 2], eigenvalue: f64, tolerance: f64) -> Option<[f64; 2]> {
+        let shifted = [
+            [matrix[0][0] - eigenvalue, matrix[0][1]],
+            [matrix[1][0], matrix[1][1] - eigenvalue]
+        ];
+        let length = |row: &[f64; 2]| (row[0].powi(2) + row[1].powi(2)).sqrt();
+        // The null space is perpendicular to the rows so the longer row gives the most accurate direction
+        let row = if length(&shifted[0]) >= length(&shifted[1]) { shifted[0] } else { shifted[1] };
+        let row_length = length(&row);
+        if row_length <= tolerance {
+            return None;
+        }
+        let vector = [-row[1] / row_length, row[0] / row_length];
+        if vector[0] < 0.0 || (vector[0] == 0.0 && vector[1] < 0.0) {
+            Some([-vector[0], -vector[1]])
+        } else {
+            Some(vector)
         }
-        (eigenvector1, eigenvector2)
+    }
+
+    /// Calculate the singular value decomposition of a 2x2 matrix
+    /// returns (u, singular values, vᵀ) with matrix = u * diag(singular values) * vᵀ
+    /// u and vᵀ are orthogonal and the singular values are non negative and in decreasing order
+    pub fn svd(matrix: &[[f64; 2]; 2]) -> ([[f64; 2]; 2], [f64; 2], [[f64; 2]; 2]) {
+        // Split the matrix into a scaled rotation (e, h) and a scaled reflection (f, g)
+        let e = f64::midpoint(matrix[0][0], matrix[1][1]);
+        let f = (matrix[0][0] - matrix[1][1]) / 2.0;
+        let g = f64::midpoint(matrix[1][0], matrix[0][1]);
+        let h = (matrix[1][0] - matrix[0][1]) / 2.0;
+        let q = (e * e + h * h).sqrt();
+        let r = (f * f + g * g).sqrt();
+        let reflection_angle = g.atan2(f);
+        let rotation_angle = h.atan2(e);
+        let theta = (rotation_angle - reflection_angle) / 2.0;
+        let phi = f64::midpoint(rotation_angle, reflection_angle);
+        let mut u = rotation(phi);
+        let mut second = q - r;
+        if second < 0.0 {
+            second = -second;
+            u[0][1] = -u[0][1];
+            u[1][1] = -u[1][1];
+        }
+        (u, [q + r, second], rotation(theta))
+    }
+
+    /// Get the 2x2 matrix of a counter-clockwise rotation by an angle in radians
+    fn rotation(angle: f64) -> [[f64; 2]; 2] {
+        let (sin, cos) = (angle.sin(), angle.cos());
+        [
+            [cos, -sin],
+            [sin, cos]
+        ]
     }
 }
 
 /// Functions for working with 3x3 matrices
 pub mod matrix3x3 {
+    use crate::angle::AngleRadians;
+    use crate::complex::Complex;
+    #[cfg(not(any(feature = "std", test)))]
+    use crate::math::Float;
     use crate::vec3d::Vec3d;
 
     /// Calculate the determinant of a 3x3 matrix
@@ -119,31 +176,271 @@ pub mod matrix3x3 {
     /// i.e. the transpose of the cofactor matrix
     pub fn adjoint(matrix: &[[f64; 3]; 3]) -> [[f64; 3]; 3] {
         let cofactor_matrix = cofactor_matrix(matrix);
-        let transpose_matrix = transpose(&cofactor_matrix);
-        dbg!(cofactor_matrix);
-        dbg!(transpose_matrix);
-        transpose_matrix
+        transpose(&cofactor_matrix)
+    }
+
+    /// Calculate the inverse of a 3x3 matrix
+    /// i.e. the adjoint divided by the determinant
+    /// returns None if the determinant is zero relative to the size of the elements
+    pub fn inverse(matrix: &[[f64; 3]; 3]) -> Option<[[f64; 3]; 3]> {
+        let determinant = determinant(matrix);
+        let scale = matrix.iter().flatten().fold(0.0_f64, |max, value| max.max(value.abs()));
+        if determinant.abs() <= scale.powi(3) * f64::EPSILON * 3.0 {
+            return None;
+        }
+        Some(adjoint(matrix).map(|row| row.map(|value| value / determinant)))
+    }
+
+    /// Convert a row major 3x3 matrix to a column major glam matrix
+    #[cfg(feature = "glam")]
+    pub fn to_glam(matrix: &[[f64; 3]; 3]) -> glam::DMat3 {
+        glam::DMat3::from_cols_array_2d(&transpose(matrix))
+    }
+
+    /// Convert a column major glam matrix to a row major 3x3 matrix
+    #[cfg(feature = "glam")]
+    pub fn from_glam(matrix: &glam::DMat3) -> [[f64; 3]; 3] {
+        transpose(&matrix.to_cols_array_2d())
+    }
+
+    /// Convert a 3x3 matrix to a row major mint matrix
+    #[cfg(feature = "mint")]
+    pub fn to_row_matrix(matrix: &[[f64; 3]; 3]) -> mint::RowMatrix3<f64> {
+        mint::RowMatrix3::from(*matrix)
+    }
+
+    /// Convert a 3x3 matrix to a column major mint matrix
+    #[cfg(feature = "mint")]
+    pub fn to_column_matrix(matrix: &[[f64; 3]; 3]) -> mint::ColumnMatrix3<f64> {
+        mint::ColumnMatrix3::from(to_row_matrix(matrix))
+    }
+
+    /// Convert a row or column major mint matrix to a 3x3 matrix
+    #[cfg(feature = "mint")]
+    pub fn from_mint(matrix: impl Into<mint::RowMatrix3<f64>>) -> [[f64; 3]; 3] {
+        matrix.into().into()
     }
 
     /// Vector multiplication of a matrix with a Vec3d
     pub fn mul(matrix: &[[f64; 3]; 3], vector: &Vec3d) -> Vec3d {
         let mut result: [f64; 3] = [0.0; 3];
         for (i, j) in matrix.iter().enumerate() {
-            // result[i] = vector.dot(Vec3d::from_slice(j));
-            result[i] = Vec3d::from_slice(j).dot(vector);
+            result[i] = Vec3d::from(*j).dot(vector);
         }
-        Vec3d::from_slice(&result)
+        Vec3d::from(result)
     }
 
-    // Calculate the eigenvalues of a 3x3 matrix
-    // returns a tuple of the eigenvalues as complex numbers
-    // pub fn eigenvalues(matrix: &[[f64; 3]; 3]) -> (Complex, Complex, Complex) {
-    //
-    // }
+    /// Get the matrix of a rotation about the x axis
+    /// the angle is positive for a counter-clockwise rotation
+    pub fn rotation_x(angle: impl Into<AngleRadians>) -> [[f64; 3]; 3] {
+        let angle = angle.into();
+        let (sin, cos) = (angle.sin(), angle.cos());
+        [
+            [1.0, 0.0, 0.0],
+            [0.0, cos, -sin],
+            [0.0, sin, cos]
+        ]
+    }
+
+    /// Get the matrix of a rotation about the y axis
+    /// the angle is positive for a counter-clockwise rotation
+    pub fn rotation_y(angle: impl Into<AngleRadians>) -> [[f64; 3]; 3] {
+        let angle = angle.into();
+        let (sin, cos) = (angle.sin(), angle.cos());
+        [
+            [cos, 0.0, sin],
+            [0.0, 1.0, 0.0],
+            [-sin, 0.0, cos]
+        ]
+    }
+
+    /// Get the matrix of a rotation about the z axis
+    /// the angle is positive for a counter-clockwise rotation
+    pub fn rotation_z(angle: impl Into<AngleRadians>) -> [[f64; 3]; 3] {
+        let angle = angle.into();
+        let (sin, cos) = (angle.sin(), angle.cos());
+        [
+            [cos, -sin, 0.0],
+            [sin, cos, 0.0],
+            [0.0, 0.0, 1.0]
+        ]
+    }
+
+    /// Get the matrix of a rotation about an arbitrary axis
+    /// the axis is normalized and the angle is positive for a counter-clockwise rotation
+    /// multiplying by the matrix rotates a vector the same way as `Quat::from_axis_angle(axis, angle).rotate`
+    pub fn rotation_axis_angle(axis: &Vec3d, angle: impl Into<AngleRadians>) -> [[f64; 3]; 3] {
+        let angle = angle.into();
+        let (sin, cos) = (angle.sin(), angle.cos());
+        let Vec3d { x, y, z } = axis.normalize();
+        let t = 1.0 - cos;
+        [
+            [t * x * x + cos, t * x * y - sin * z, t * x * z + sin * y],
+            [t * x * y + sin * z, t * y * y + cos, t * y * z - sin * x],
+            [t * x * z - sin * y, t * y * z + sin * x, t * z * z + cos]
+        ]
+    }
+
+    /// Get the matrix that scales each axis by a separate factor
+    pub fn scaling(sx: f64, sy: f64, sz: f64) -> [[f64; 3]; 3] {
+        [
+            [sx, 0.0, 0.0],
+            [0.0, sy, 0.0],
+            [0.0, 0.0, sz]
+        ]
+    }
+
+    /// Multiply two 3x3 matrices
+    pub fn mul_matrix(a: &[[f64; 3]; 3], b: &[[f64; 3]; 3]) -> [[f64; 3]; 3] {
+        let mut result = [[0.0; 3]; 3];
+        for (row, a_row) in result.iter_mut().zip(a) {
+            for (col, value) in row.iter_mut().enumerate() {
+                *value = a_row[0] * b[0][col] + a_row[1] * b[1][col] + a_row[2] * b[2][col];
+            }
+        }
+        result
+    }
+
+    /// Check if a 3x3 matrix is orthogonal
+    /// i.e. if its transpose times itself is the identity within a tolerance
+    pub fn is_orthogonal(matrix: &[[f64; 3]; 3], tolerance: f64) -> bool {
+        let product = mul_matrix(&transpose(matrix), matrix);
+        product.iter().enumerate().all(|(i, row)| {
+            row.iter().enumerate().all(|(j, value)| {
+                let expected = if i == j { 1.0 } else { 0.0 };
+                (value - expected).abs() <= tolerance
+            })
+        })
+    }
+
+    /// Calculate the polar decomposition of an invertible 3x3 matrix
+    /// returns (r, s) with matrix = r * s where r is orthogonal and s is symmetric
+    /// r is a rotation when the determinant of the matrix is positive and a reflection when it is negative
+    /// r is found by repeatedly averaging it with its inverse transpose, which converges quadratically
+    /// returns None if the determinant is zero relative to the size of the elements
+    #[allow(clippy::type_complexity)]
+    pub fn polar_decomposition(matrix: &[[f64; 3]; 3]) -> Option<([[f64; 3]; 3], [[f64; 3]; 3])> {
+        let scale = matrix.iter().flatten().fold(0.0_f64, |max, value| max.max(value.abs()));
+        if determinant(matrix).abs() <= scale.powi(3) * f64::EPSILON * 3.0 {
+            return None;
+        }
+        let mut r = *matrix;
+        for _ in 0..100 {
+            // The inverse transpose is the cofactor matrix over the determinant
+            let determinant = determinant(&r);
+            let cofactors = cofactor_matrix(&r);
+            let mut change: f64 = 0.0;
+            for (row, cofactor_row) in r.iter_mut().zip(cofactors) {
+                for (value, cofactor) in row.iter_mut().zip(cofactor_row) {
+                    let next = f64::midpoint(*value, cofactor / determinant);
+                    change = change.max((next - *value).abs());
+                    *value = next;
+                }
+            }
+            if change <= f64::EPSILON {
+                break;
+            }
+        }
+        let s = mul_matrix(&transpose(&r), matrix);
+        Some((r, s))
+    }
+
+    /// Get the coefficients of the characteristic polynomial of a 3x3 matrix
+    /// returns [a, b, c] where the polynomial is det(λI - matrix) = λ³ + aλ² + bλ + c
+    pub fn char_poly_coefficients(matrix: &[[f64; 3]; 3]) -> [f64; 3] {
+        let trace = matrix[0][0] + matrix[1][1] + matrix[2][2];
+        let minors = minor(matrix, 0, 0) + minor(matrix, 1, 1) + minor(matrix, 2, 2);
+        [-trace, minors, -determinant(matrix)]
+    }
+
+    /// Calculate the eigenvalues of a 3x3 matrix
+    /// returns a tuple of the eigenvalues as complex numbers
+    /// real eigenvalues are returned largest first, otherwise the real eigenvalue comes first
+    /// followed by the complex pair with the positive imaginary part first
+    /// the cubic is solved for the matrix shifted by a third of its trace,
+    /// with the trigonometric method when all roots are real and Cardano's method otherwise
+    pub fn eigenvalues(matrix: &[[f64; 3]; 3]) -> (Complex, Complex, Complex) {
+        let shift = (matrix[0][0] + matrix[1][1] + matrix[2][2]) / 3.0;
+        let mut shifted = *matrix;
+        for (i, row) in shifted.iter_mut().enumerate() {
+            row[i] -= shift;
+        }
+        // The shifted matrix has no trace so its characteristic polynomial is the depressed cubic t³ + pt + q
+        let [_, p, q] = char_poly_coefficients(&shifted);
+        let discriminant = (q / 2.0).powi(2) + (p / 3.0).powi(3);
+        if discriminant <= 0.0 {
+            if p == 0.0 {
+                let root = Complex::new(shift, 0.0);
+                return (root, root, root);
+            }
+            let radius = 2.0 * (-p / 3.0).sqrt();
+            // Rounding can push the cosine just outside the range of acos
+            let angle = (3.0 * q / (p * radius)).clamp(-1.0, 1.0).acos() / 3.0;
+            let third = 2.0 * core::f64::consts::PI / 3.0;
+            let root = |k: f64| Complex::new(shift + radius * (angle - third * k).cos(), 0.0);
+            return (root(0.0), root(1.0), root(2.0));
+        }
+        // Taking the root with the same sign as -q avoids cancellation, the other follows from uv = -p/3
+        let u = (-q / 2.0 - (q.signum() * discriminant.sqrt())).cbrt();
+        let v = -p / (3.0 * u);
+        let real = u + v;
+        let imaginary = 3.0_f64.sqrt() / 2.0 * (u - v).abs();
+        (
+            Complex::new(shift + real, 0.0),
+            Complex::new(shift - real / 2.0, imaginary),
+            Complex::new(shift - real / 2.0, -imaginary)
+        )
+    }
 }
 
 /// Functions for working with 4x4 matrices
 pub mod matrix4x4 {
+    #[cfg(not(any(feature = "std", test)))]
+    use crate::math::Float;
+    use crate::quat::Quat;
+    use crate::vec3d::Vec3d;
+
+    /// Get the homogeneous matrix of a translation
+    pub fn translation(offset: &Vec3d) -> [[f64; 4]; 4] {
+        [
+            [1.0, 0.0, 0.0, offset.x],
+            [0.0, 1.0, 0.0, offset.y],
+            [0.0, 0.0, 1.0, offset.z],
+            [0.0, 0.0, 0.0, 1.0]
+        ]
+    }
+
+    /// Get the homogeneous matrix of the rotation applied by `Quat::rotate`
+    pub fn rotation(q: &Quat) -> [[f64; 4]; 4] {
+        // rotate applies q* v q while to_rotation_matrix is the matrix of q v q*
+        let m = q.conjugate().to_rotation_matrix();
+        [
+            [m[0][0], m[0][1], m[0][2], 0.0],
+            [m[1][0], m[1][1], m[1][2], 0.0],
+            [m[2][0], m[2][1], m[2][2], 0.0],
+            [0.0, 0.0, 0.0, 1.0]
+        ]
+    }
+
+    /// Multiply two 4x4 matrices
+    /// a * b applies b first when transforming points
+    pub fn mul_matrix(a: &[[f64; 4]; 4], b: &[[f64; 4]; 4]) -> [[f64; 4]; 4] {
+        let mut result = [[0.0; 4]; 4];
+        for (row, a_row) in result.iter_mut().zip(a) {
+            for (col, value) in row.iter_mut().enumerate() {
+                *value = a_row.iter().zip(b).map(|(a, b_row)| a * b_row[col]).sum();
+            }
+        }
+        result
+    }
+
+    /// Apply a homogeneous matrix to a point with an implicit w of 1
+    /// the result is divided by the resulting w so projective matrices work too
+    pub fn transform_point(matrix: &[[f64; 4]; 4], point: &Vec3d) -> Vec3d {
+        let [x, y, z, w] = matrix.map(|row| row[0] * point.x + row[1] * point.y + row[2] * point.z + row[3]);
+        Vec3d::new(x / w, y / w, z / w)
+    }
+
     /// Calculate the determinant of a 4x4 matrix
     pub fn determinant(matrix: &[[f64; 4]; 4]) -> f64 {
         matrix[0][0] * matrix[1][1] * matrix[2][2] * matrix[3][3] +
@@ -204,6 +501,36 @@ pub mod matrix4x4 {
         ];
         factor[row][col] * minor
     }
+
+    /// Get the cofactor matrix of a 4x4 matrix
+    pub fn cofactor_matrix(matrix: &[[f64; 4]; 4]) -> [[f64; 4]; 4] {
+        core::array::from_fn(|i| core::array::from_fn(|j| cofactor(matrix, i, j)))
+    }
+
+    /// Transpose a 4x4 matrix
+    /// i.e. swap the rows and columns
+    pub fn transpose(matrix: &[[f64; 4]; 4]) -> [[f64; 4]; 4] {
+        core::array::from_fn(|i| core::array::from_fn(|j| matrix[j][i]))
+    }
+
+    /// Calculate the adjoint of a 4x4 matrix
+    /// i.e. the transpose of the cofactor matrix
+    pub fn adjoint(matrix: &[[f64; 4]; 4]) -> [[f64; 4]; 4] {
+        let cofactor_matrix = cofactor_matrix(matrix);
+        transpose(&cofactor_matrix)
+    }
+
+    /// Calculate the inverse of a 4x4 matrix
+    /// i.e. the adjoint divided by the determinant
+    /// returns None if the determinant is zero relative to the size of the elements
+    pub fn inverse(matrix: &[[f64; 4]; 4]) -> Option<[[f64; 4]; 4]> {
+        let determinant = determinant(matrix);
+        let scale = matrix.iter().flatten().fold(0.0_f64, |max, value| max.max(value.abs()));
+        if determinant.abs() <= scale.powi(4) * f64::EPSILON * 4.0 {
+            return None;
+        }
+        Some(adjoint(matrix).map(|row| row.map(|value| value / determinant)))
+    }
 }
 
 #[cfg(test)]
@@ -233,22 +560,284 @@ mod tests {
             assert_eq!(eigenvalue2.imaginary, 0.0);
         }
 
+        #[test]
+        fn test_matrix2x2_svd() {
+            let matrix = [
+                [1.0, 2.0],
+                [3.0, 4.0]
+            ];
+            let (u, sigma, vt) = matrix2x2::svd(&matrix);
+            // The product of the singular values is |det| and the sum of their squares is the squared frobenius norm
+            assert!((sigma[0] * sigma[1] - 2.0).abs() < 1e-12);
+            assert!((sigma[0].powi(2) + sigma[1].powi(2) - 30.0).abs() < 1e-12);
+            for (u_row, row) in u.iter().zip(matrix) {
+                for (j, expected) in row.iter().enumerate() {
+                    let value = u_row[0] * sigma[0] * vt[0][j] + u_row[1] * sigma[1] * vt[1][j];
+                    assert!((value - expected).abs() < 1e-12);
+                }
+            }
+            assert!((matrix2x2::determinant(&u).abs() - 1.0).abs() < 1e-12);
+            assert!((matrix2x2::determinant(&vt).abs() - 1.0).abs() < 1e-12);
+        }
+
+        #[test]
+        fn test_matrix2x2_svd_shear_and_scale() {
+            let golden_ratio = f64::midpoint(1.0, 5.0_f64.sqrt());
+            let (_, sigma, _) = matrix2x2::svd(&[[1.0, 1.0], [0.0, 1.0]]);
+            assert!((sigma[0] - golden_ratio).abs() < 1e-12);
+            assert!((sigma[1] - 1.0 / golden_ratio).abs() < 1e-12);
+            let (_, sigma, _) = matrix2x2::svd(&[[2.0, 0.0], [0.0, -3.0]]);
+            assert!((sigma[0] - 3.0).abs() < 1e-12);
+            assert!((sigma[1] - 2.0).abs() < 1e-12);
+        }
+
+        fn assert_eigenpair(matrix: &[[f64; 2]; 2], eigenvalue: f64, vector: [f64; 2]) {
+            assert!((vector[0].powi(2) + vector[1].powi(2) - 1.0).abs() < 1e-12);
+            for (row, component) in matrix.iter().zip(vector) {
+                let product = row[0] * vector[0] + row[1] * vector[1];
+                assert!((product - eigenvalue * component).abs() < 1e-12, "{product} != {}", eigenvalue * component);
+            }
+        }
+
         #[test]
         fn test_matrix2x2_eigenvectors() {
             let matrix = [
                 [8.0, 4.0],
                 [4.0, 8.0]
             ];
-            let (eigenvector1, eigenvector2) = matrix2x2::eigenvectors(&matrix);
-            assert_eq!(eigenvector1[0], -1.0);
-            assert_eq!(eigenvector1[1], 1.0);
-            assert_eq!(eigenvector2[0], 1.0);
-            assert_eq!(eigenvector2[1], 1.0);
+            let (eigenvector1, eigenvector2) = matrix2x2::eigenvectors(&matrix).unwrap();
+            let half_root_two = 0.5_f64.sqrt();
+            assert!((eigenvector1[0] - half_root_two).abs() < 1e-12);
+            assert!((eigenvector1[1] - half_root_two).abs() < 1e-12);
+            assert!((eigenvector2[0] - half_root_two).abs() < 1e-12);
+            assert!((eigenvector2[1] + half_root_two).abs() < 1e-12);
+            assert_eigenpair(&matrix, 12.0, eigenvector1);
+            assert_eigenpair(&matrix, 4.0, eigenvector2);
+        }
+
+        #[test]
+        fn test_matrix2x2_eigenvectors_non_symmetric() {
+            let matrix = [
+                [2.0, 1.0],
+                [-1.0, 5.0]
+            ];
+            let (eigenvalue1, eigenvalue2) = matrix2x2::eigenvalues(&matrix);
+            let (eigenvector1, eigenvector2) = matrix2x2::eigenvectors(&matrix).unwrap();
+            assert_eigenpair(&matrix, eigenvalue1.real, eigenvector1);
+            assert_eigenpair(&matrix, eigenvalue2.real, eigenvector2);
+        }
+
+        #[test]
+        fn test_matrix2x2_eigenvectors_rotation() {
+            let matrix = [
+                [0.0, -1.0],
+                [1.0, 0.0]
+            ];
+            assert!(matrix2x2::eigenvectors(&matrix).is_none());
+        }
+
+        #[test]
+        fn test_matrix2x2_eigenvectors_repeated() {
+            let shear = [
+                [1.0, 1.0],
+                [0.0, 1.0]
+            ];
+            let (eigenvector1, eigenvector2) = matrix2x2::eigenvectors(&shear).unwrap();
+            assert_eq!((eigenvector1[0], eigenvector1[1]), (1.0, 0.0));
+            assert_eq!((eigenvector2[0], eigenvector2[1]), (1.0, 0.0));
+            let scaled_identity = [
+                [3.0, 0.0],
+                [0.0, 3.0]
+            ];
+            let (eigenvector1, eigenvector2) = matrix2x2::eigenvectors(&scaled_identity).unwrap();
+            assert_eq!((eigenvector1[0], eigenvector1[1]), (1.0, 0.0));
+            assert_eq!((eigenvector2[0], eigenvector2[1]), (0.0, 1.0));
         }
     }
 
     mod tests3x3 {
         use super::super::matrix3x3;
+        use crate::angle::AngleDegrees;
+        use crate::complex::Complex;
+        use crate::quat::Quat;
+        use crate::vec3d::Vec3d;
+
+        fn assert_close(a: &[[f64; 3]; 3], b: &[[f64; 3]; 3]) {
+            for (a_row, b_row) in a.iter().zip(b) {
+                for (a, b) in a_row.iter().zip(b_row) {
+                    assert!((a - b).abs() < 1e-12, "{a} != {b}");
+                }
+            }
+        }
+
+        fn assert_complex_close(actual: Complex, real: f64, imaginary: f64) {
+            assert!((actual.real - real).abs() < 1e-12 && (actual.imaginary - imaginary).abs() < 1e-12, "{actual}");
+        }
+
+        #[test]
+        fn test_matrix3x3_eigenvalues_diagonal() {
+            let (a, b, c) = matrix3x3::eigenvalues(&matrix3x3::scaling(3.0, -1.0, 2.0));
+            assert_complex_close(a, 3.0, 0.0);
+            assert_complex_close(b, 2.0, 0.0);
+            assert_complex_close(c, -1.0, 0.0);
+            let (a, b, c) = matrix3x3::eigenvalues(&matrix3x3::scaling(2.0, 2.0, 2.0));
+            assert_complex_close(a, 2.0, 0.0);
+            assert_complex_close(b, 2.0, 0.0);
+            assert_complex_close(c, 2.0, 0.0);
+            let coefficients = matrix3x3::char_poly_coefficients(&matrix3x3::scaling(1.0, 2.0, 3.0));
+            for (coefficient, expected) in coefficients.iter().zip([-6.0, 11.0, -6.0]) {
+                assert!((coefficient - expected).abs() < 1e-12);
+            }
+        }
+
+        #[test]
+        fn test_matrix3x3_eigenvalues_rotation() {
+            let angle = 0.7_f64;
+            let (a, b, c) = matrix3x3::eigenvalues(&matrix3x3::rotation_z(angle));
+            assert_complex_close(a, 1.0, 0.0);
+            assert_complex_close(b, angle.cos(), angle.sin());
+            assert_complex_close(c, angle.cos(), -angle.sin());
+        }
+
+        #[test]
+        fn test_matrix3x3_eigenvalues_trace_and_determinant() {
+            crate::testing::for_all(200, |(x, y, z): (Vec3d, Vec3d, Vec3d)| {
+                let matrix = [x.to_array(), y.to_array(), z.to_array()];
+                let (a, b, c) = matrix3x3::eigenvalues(&matrix);
+                let scale = x.magnitude().max(y.magnitude()).max(z.magnitude()).max(1.0);
+                let sum = a + b + c;
+                let trace = matrix[0][0] + matrix[1][1] + matrix[2][2];
+                assert!((sum.real - trace).abs() < 1e-9 * scale && sum.imaginary.abs() < 1e-9 * scale);
+                let product = a * b * c;
+                let determinant = matrix3x3::determinant(&matrix);
+                let tolerance = 1e-9 * scale.powi(3);
+                assert!((product.real - determinant).abs() < tolerance && product.imaginary.abs() < tolerance);
+            });
+        }
+
+        #[test]
+        fn test_matrix3x3_inverse() {
+            let a = [
+                [2.0, 0.0, 1.0],
+                [1.0, 3.0, 0.0],
+                [0.0, 1.0, 4.0]
+            ];
+            let identity = [
+                [1.0, 0.0, 0.0],
+                [0.0, 1.0, 0.0],
+                [0.0, 0.0, 1.0]
+            ];
+            assert_close(&matrix3x3::mul_matrix(&a, &matrix3x3::inverse(&a).unwrap()), &identity);
+            let scaled_identity = identity.map(|row| row.map(|value| value * matrix3x3::determinant(&a)));
+            assert_close(&matrix3x3::mul_matrix(&a, &matrix3x3::adjoint(&a)), &scaled_identity);
+            let singular = [
+                [1.0, 2.0, 3.0],
+                [4.0, 5.0, 6.0],
+                [7.0, 8.0, 9.0]
+            ];
+            assert!(matrix3x3::inverse(&singular).is_none());
+        }
+
+        #[test]
+        fn test_matrix3x3_mul_matrix() {
+            let a = [
+                [1.0, 2.0, 3.0],
+                [4.0, 5.0, 6.0],
+                [7.0, 8.0, 9.0]
+            ];
+            let identity = [
+                [1.0, 0.0, 0.0],
+                [0.0, 1.0, 0.0],
+                [0.0, 0.0, 1.0]
+            ];
+            assert_close(&matrix3x3::mul_matrix(&a, &identity), &a);
+            assert_close(&matrix3x3::mul_matrix(&a, &a), &[
+                [30.0, 36.0, 42.0],
+                [66.0, 81.0, 96.0],
+                [102.0, 126.0, 150.0]
+            ]);
+        }
+
+        #[test]
+        fn test_matrix3x3_rotation_z() {
+            let rotated = matrix3x3::mul(&matrix3x3::rotation_z(AngleDegrees::new(90.0)), &Vec3d::i());
+            assert!(rotated.distance_to(&Vec3d::j()) < 1e-12);
+            let rotated = matrix3x3::mul(&matrix3x3::rotation_x(AngleDegrees::new(90.0)), &Vec3d::j());
+            assert!(rotated.distance_to(&Vec3d::k()) < 1e-12);
+            let rotated = matrix3x3::mul(&matrix3x3::rotation_y(AngleDegrees::new(90.0)), &Vec3d::k());
+            assert!(rotated.distance_to(&Vec3d::i()) < 1e-12);
+        }
+
+        #[test]
+        fn test_matrix3x3_rotation_axis_angle() {
+            let axis = Vec3d::new(1.0, 2.0, 3.0).normalize();
+            let angle = AngleDegrees::new(40.0);
+            let matrix = matrix3x3::rotation_axis_angle(&axis, angle);
+            let q = Quat::from_axis_angle(&axis, angle);
+            let v = Vec3d::new(-2.0, 0.5, 1.0);
+            assert!(matrix3x3::mul(&matrix, &v).distance_to(&q.rotate(&v)) < 1e-12);
+            assert_close(&matrix, &q.conjugate().to_rotation_matrix());
+            assert_close(&matrix3x3::rotation_axis_angle(&Vec3d::k(), angle), &matrix3x3::rotation_z(angle));
+            assert!(matrix3x3::is_orthogonal(&matrix, 1e-12));
+        }
+
+        #[test]
+        fn test_matrix3x3_scaling() {
+            let scaled = matrix3x3::mul(&matrix3x3::scaling(2.0, 3.0, 4.0), &Vec3d::new(1.0, 1.0, 1.0));
+            assert_eq!(scaled, Vec3d::new(2.0, 3.0, 4.0));
+        }
+
+        #[test]
+        fn test_matrix3x3_polar_decomposition_shear() {
+            let shear = [
+                [1.0, 1.0, 0.0],
+                [0.0, 1.0, 0.0],
+                [0.0, 0.0, 1.0]
+            ];
+            let (r, s) = matrix3x3::polar_decomposition(&shear).unwrap();
+            let root_five = 5.0_f64.sqrt();
+            assert_close(&r, &[
+                [2.0 / root_five, 1.0 / root_five, 0.0],
+                [-1.0 / root_five, 2.0 / root_five, 0.0],
+                [0.0, 0.0, 1.0]
+            ]);
+            assert_close(&s, &[
+                [2.0 / root_five, 1.0 / root_five, 0.0],
+                [1.0 / root_five, 3.0 / root_five, 0.0],
+                [0.0, 0.0, 1.0]
+            ]);
+            assert!(matrix3x3::is_orthogonal(&r, 1e-12));
+            assert!((matrix3x3::determinant(&r) - 1.0).abs() < 1e-12);
+            assert_close(&matrix3x3::mul_matrix(&r, &s), &shear);
+        }
+
+        #[test]
+        fn test_matrix3x3_polar_decomposition_scale() {
+            let scale = [
+                [2.0, 0.0, 0.0],
+                [0.0, 3.0, 0.0],
+                [0.0, 0.0, 4.0]
+            ];
+            let (r, s) = matrix3x3::polar_decomposition(&scale).unwrap();
+            assert_close(&r, &[
+                [1.0, 0.0, 0.0],
+                [0.0, 1.0, 0.0],
+                [0.0, 0.0, 1.0]
+            ]);
+            assert_close(&s, &scale);
+            assert!(!matrix3x3::is_orthogonal(&scale, 1e-12));
+        }
+
+        #[test]
+        fn test_matrix3x3_polar_decomposition_singular() {
+            let singular = [
+                [1.0, 2.0, 3.0],
+                [4.0, 5.0, 6.0],
+                [7.0, 8.0, 9.0]
+            ];
+            assert!(matrix3x3::polar_decomposition(&singular).is_none());
+            assert!(matrix3x3::polar_decomposition(&[[0.0; 3]; 3]).is_none());
+        }
 
         #[test]
         fn test_matrix3x3_determinant() {
@@ -342,7 +931,6 @@ mod tests {
                 [27.0, -13.0, -12.0]
             ];
             let adjoint_matrix = matrix3x3::adjoint(&matrix);
-            dbg!(adjoint_matrix);
             assert_eq!(adjoint_matrix[0][0], adjoint[0][0]);
             assert_eq!(adjoint_matrix[0][1], adjoint[0][1]);
             assert_eq!(adjoint_matrix[0][2], adjoint[0][2]);
@@ -353,10 +941,56 @@ mod tests {
             assert_eq!(adjoint_matrix[2][1], adjoint[2][1]);
             assert_eq!(adjoint_matrix[2][2], adjoint[2][2]);
         }
+
+        #[cfg(feature = "mint")]
+        #[test]
+        fn test_matrix3x3_mint() {
+            let matrix = [
+                [1.0, 2.0, 3.0],
+                [4.0, 5.0, 6.0],
+                [7.0, 8.0, 9.0]
+            ];
+            let rows = matrix3x3::to_row_matrix(&matrix);
+            let columns = matrix3x3::to_column_matrix(&matrix);
+            assert_eq!((rows.x.x, rows.x.y, rows.y.x), (1.0, 2.0, 4.0));
+            assert_eq!((columns.x.x, columns.x.y, columns.y.x), (1.0, 4.0, 2.0));
+            assert_eq!(matrix3x3::from_mint(rows), matrix);
+            assert_eq!(matrix3x3::from_mint(columns), matrix);
+        }
+
+        #[cfg(feature = "glam")]
+        #[test]
+        fn test_matrix3x3_glam() {
+            let matrix = [
+                [1.0, 2.0, 3.0],
+                [4.0, 5.0, 6.0],
+                [7.0, 8.0, 9.0]
+            ];
+            let converted = matrix3x3::to_glam(&matrix);
+            assert_eq!(converted.row(0), glam::DVec3::new(1.0, 2.0, 3.0));
+            assert_eq!(converted.col(0), glam::DVec3::new(1.0, 4.0, 7.0));
+            assert_eq!(matrix3x3::from_glam(&converted), matrix);
+            let vector = crate::vec3d::Vec3d::new(1.0, -1.0, 2.0);
+            let product = converted * glam::DVec3::from(vector);
+            assert_eq!(crate::vec3d::Vec3d::from(product), matrix3x3::mul(&matrix, &vector));
+        }
     }
 
     mod tests4x4 {
         use super::super::matrix4x4;
+        use crate::angle::AngleDegrees;
+        use crate::quat::Quat;
+        use crate::vec3d::Vec3d;
+
+        #[test]
+        fn test_matrix4x4_translate_then_rotate() {
+            let offset = Vec3d::new(1.0, -2.0, 0.5);
+            let q = Quat::from_axis_angle(&Vec3d::new(1.0, 1.0, 0.0).normalize(), AngleDegrees::new(70.0));
+            let transform = matrix4x4::mul_matrix(&matrix4x4::rotation(&q), &matrix4x4::translation(&offset));
+            let point = Vec3d::new(3.0, 4.0, 5.0);
+            let expected = q.rotate(&(point + offset));
+            assert!(matrix4x4::transform_point(&transform, &point).distance_to(&expected) < 1e-12);
+        }
 
         #[test]
         fn test_matrix4x4_determinant() {
@@ -390,5 +1024,53 @@ mod tests {
             ];
             assert_eq!(matrix4x4::cofactor(&matrix, 0, 0), 0.0);
         }
+
+        fn assert_close(a: &[[f64; 4]; 4], b: &[[f64; 4]; 4]) {
+            for (a_row, b_row) in a.iter().zip(b) {
+                for (a, b) in a_row.iter().zip(b_row) {
+                    assert!((a - b).abs() < 1e-12, "{a} != {b}");
+                }
+            }
+        }
+
+        #[test]
+        fn test_matrix4x4_inverse() {
+            let q = Quat::from_axis_angle(&Vec3d::new(1.0, 2.0, -1.0).normalize(), AngleDegrees::new(35.0));
+            let offset = Vec3d::new(3.0, -1.0, 2.0);
+            let pose = matrix4x4::mul_matrix(&matrix4x4::translation(&offset), &matrix4x4::rotation(&q));
+            let inverse = matrix4x4::inverse(&pose).unwrap();
+            let identity = matrix4x4::translation(&Vec3d::zero());
+            assert_close(&matrix4x4::mul_matrix(&inverse, &pose), &identity);
+            assert_close(&matrix4x4::mul_matrix(&pose, &inverse), &identity);
+            let point = Vec3d::new(1.0, 1.0, 1.0);
+            let transformed = matrix4x4::transform_point(&pose, &point);
+            assert!(matrix4x4::transform_point(&inverse, &transformed).distance_to(&point) < 1e-12);
+        }
+
+        #[test]
+        fn test_matrix4x4_adjoint() {
+            let matrix = [
+                [2.0, 1.0, 0.0, 3.0],
+                [0.0, 1.0, 4.0, 1.0],
+                [1.0, 0.0, 2.0, 0.0],
+                [3.0, 2.0, 1.0, 1.0]
+            ];
+            let determinant = matrix4x4::determinant(&matrix);
+            let scaled_identity = matrix4x4::translation(&Vec3d::zero()).map(|row| row.map(|value| value * determinant));
+            assert_close(&matrix4x4::mul_matrix(&matrix, &matrix4x4::adjoint(&matrix)), &scaled_identity);
+            assert_eq!(matrix4x4::transpose(&matrix4x4::transpose(&matrix)), matrix);
+        }
+
+        #[test]
+        fn test_matrix4x4_singular_inverse() {
+            let matrix = [
+                [1.0, 2.0, 3.0, 4.0],
+                [5.0, 6.0, 7.0, 8.0],
+                [9.0, 10.0, 11.0, 12.0],
+                [13.0, 14.0, 15.0, 16.0]
+            ];
+            assert!(matrix4x4::inverse(&matrix).is_none());
+            assert!(matrix4x4::inverse(&[[0.0; 4]; 4]).is_none());
+        }
     }
 }
@@ -0,0 +1,1637 @@
+use alloc::vec::Vec;
+
+#[cfg(not(any(feature = "std", test)))]
+use crate::math::Float;
+use crate::angle::AngleRadians;
+use crate::complex::Complex;
+use crate::matrix::{matrix2x2, matrix3x3};
+use crate::quat::Quat;
+use crate::vec3d::Vec3d;
+
+/// Errors from dynamically sized matrix operations
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MatrixError {
+    /// The dimensions of a matrix did not match what the operation needed
+    DimensionMismatch {
+        /// The expected rows and columns
+        expected: (usize, usize),
+        /// The rows and columns that were given
+        found: (usize, usize)
+    },
+    /// The data given for a matrix had the wrong number of elements
+    LengthMismatch {
+        /// The number of elements needed, i.e. rows * cols
+        expected: usize,
+        /// The number of elements that were given
+        found: usize
+    },
+    /// The operation needs a square matrix
+    NotSquare,
+    /// The matrix is singular and cannot be inverted or solved
+    Singular
+}
+
+impl core::fmt::Display for MatrixError {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        match self {
+            MatrixError::DimensionMismatch { expected, found } => write!(
+                f,
+                "expected a {}x{} matrix but found {}x{}",
+                expected.0, expected.1, found.0, found.1
+            ),
+            MatrixError::LengthMismatch { expected, found } => {
+                write!(f, "expected {expected} elements for the matrix but found {found}")
+            }
+            MatrixError::NotSquare => write!(f, "matrix is not square"),
+            MatrixError::Singular => write!(f, "matrix is singular")
+        }
+    }
+}
+
+impl core::error::Error for MatrixError {}
+
+/// The pivots encountered while solving a linear system or inverting a matrix
+#[derive(Debug, Clone, PartialEq)]
+pub struct SolveDiagnostics {
+    /// The magnitude of the pivot used for each column in order
+    pub pivots: Vec<f64>
+}
+
+impl SolveDiagnostics {
+    /// Get the smallest pivot magnitude
+    pub fn min_pivot(&self) -> f64 {
+        self.pivots.iter().copied().fold(f64::INFINITY, f64::min)
+    }
+
+    /// Get the largest pivot magnitude
+    pub fn max_pivot(&self) -> f64 {
+        self.pivots.iter().copied().fold(0.0, f64::max)
+    }
+
+    /// Get the ratio of the largest to the smallest pivot magnitude
+    /// a large ratio suggests the system is ill conditioned and it is infinite for a singular matrix
+    pub fn pivot_ratio(&self) -> f64 {
+        self.max_pivot() / self.min_pivot()
+    }
+}
+
+/// A row echelon form along with what happened while reducing to it
+#[derive(Debug, Clone, PartialEq)]
+pub struct RefResult {
+    /// The matrix in row echelon form
+    pub matrix: DMatrix<f64>,
+    /// The column of each pivot in order, one per nonzero row of the reduced matrix
+    pub pivot_columns: Vec<usize>,
+    /// The number of row swaps made while pivoting
+    pub swaps: usize
+}
+
+impl RefResult {
+    /// Get the rank of the original matrix
+    /// i.e. the number of pivots
+    pub fn rank(&self) -> usize {
+        self.pivot_columns.len()
+    }
+
+    /// Get the determinant of the original matrix from the product of the pivots
+    /// None if the matrix is not square
+    pub fn determinant(&self) -> Option<f64> {
+        if !self.matrix.is_square() {
+            return None;
+        }
+        if self.rank() < self.matrix.rows {
+            return Some(0.0);
+        }
+        let product: f64 = (0..self.matrix.rows).map(|i| self.matrix[(i, i)]).product();
+        Some(if self.swaps.is_multiple_of(2) { product } else { -product })
+    }
+}
+
+/// The result of gaussian elimination
+struct Elimination {
+    swaps: usize,
+    pivots: Vec<f64>,
+    pivot_columns: Vec<usize>
+}
+
+/// A matrix with dimensions only known at runtime
+/// the elements are stored row major
+#[derive(Debug, Clone, PartialEq)]
+pub struct DMatrix<T> {
+    rows: usize,
+    cols: usize,
+    data: Vec<T>
+}
+
+impl<T> DMatrix<T> {
+    /// Create a new matrix from row major data
+    /// # Errors
+    /// returns `MatrixError::LengthMismatch` if the data does not have rows * cols elements
+    pub fn new(rows: usize, cols: usize, data: Vec<T>) -> Result<DMatrix<T>, MatrixError> {
+        if data.len() != rows * cols {
+            return Err(MatrixError::LengthMismatch {
+                expected: rows * cols,
+                found: data.len()
+            });
+        }
+        Ok(DMatrix { rows, cols, data })
+    }
+
+    /// Create a new matrix from a Vec of rows
+    /// # Errors
+    /// returns `MatrixError::DimensionMismatch` if the rows are not all the same length
+    pub fn try_from_nested_vec(rows: Vec<Vec<T>>) -> Result<DMatrix<T>, MatrixError> {
+        let cols = rows.first().map_or(0, Vec::len);
+        if let Some(row) = rows.iter().find(|row| row.len() != cols) {
+            return Err(MatrixError::DimensionMismatch {
+                expected: (1, cols),
+                found: (1, row.len())
+            });
+        }
+        let count = rows.len();
+        Ok(DMatrix {
+            rows: count,
+            cols,
+            data: rows.into_iter().flatten().collect()
+        })
+    }
+
+    /// Create a new matrix by calling a function with the row and column of each element
+    pub fn from_fn(rows: usize, cols: usize, mut f: impl FnMut(usize, usize) -> T) -> DMatrix<T> {
+        let data = (0..rows * cols).map(|i| f(i / cols, i % cols)).collect();
+        DMatrix { rows, cols, data }
+    }
+
+    /// Get the number of rows
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    /// Get the number of columns
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    /// Get the rows and columns of the matrix
+    pub fn shape(&self) -> (usize, usize) {
+        (self.rows, self.cols)
+    }
+
+    /// Check if the matrix is square
+    pub fn is_square(&self) -> bool {
+        self.rows == self.cols
+    }
+
+    /// Get an element of the matrix
+    /// None if the row or column is out of bounds
+    pub fn get(&self, row: usize, col: usize) -> Option<&T> {
+        if row < self.rows && col < self.cols {
+            self.data.get(row * self.cols + col)
+        } else {
+            None
+        }
+    }
+
+    /// Get the elements of the matrix in row major order
+    pub fn as_slice(&self) -> &[T] {
+        &self.data
+    }
+
+    /// Iterate over the elements of the matrix in row major order along with their row and column
+    pub fn iter_indexed(&self) -> impl Iterator<Item = ((usize, usize), &T)> {
+        let cols = self.cols;
+        self.data.iter().enumerate().map(move |(i, value)| ((i / cols, i % cols), value))
+    }
+
+    /// Swap two rows
+    /// as an elementary row operation this keeps the solutions of an augmented system and negates the determinant
+    ///
+    /// # Panics
+    /// Panics if either row is out of range
+    pub fn swap_rows(&mut self, a: usize, b: usize) {
+        assert!(a < self.rows && b < self.rows, "row out of range");
+        for col in 0..self.cols {
+            self.data.swap(a * self.cols + col, b * self.cols + col);
+        }
+    }
+}
+
+impl<T: Clone> DMatrix<T> {
+    /// Create a new matrix with every element set to a value
+    pub fn filled(rows: usize, cols: usize, value: T) -> DMatrix<T> {
+        DMatrix {
+            rows,
+            cols,
+            data: alloc::vec![value; rows * cols]
+        }
+    }
+
+    /// Transpose the matrix
+    /// i.e. swap the rows and columns
+    pub fn transpose(&self) -> DMatrix<T> {
+        DMatrix::from_fn(self.cols, self.rows, |row, col| self[(col, row)].clone())
+    }
+
+    /// Join the columns of another matrix on to the right of this one
+    /// e.g. a coefficient matrix and a right hand side into an augmented matrix
+    /// # Errors
+    /// returns `MatrixError::DimensionMismatch` if the matrices do not have the same number of rows
+    pub fn augment(&self, rhs: &DMatrix<T>) -> Result<DMatrix<T>, MatrixError> {
+        if self.rows != rhs.rows {
+            return Err(MatrixError::DimensionMismatch {
+                expected: (self.rows, rhs.cols),
+                found: rhs.shape()
+            });
+        }
+        let cols = self.cols + rhs.cols;
+        Ok(DMatrix::from_fn(self.rows, cols, |row, col| {
+            if col < self.cols { self[(row, col)].clone() } else { rhs[(row, col - self.cols)].clone() }
+        }))
+    }
+
+    /// Split the matrix into the columns before an index and the columns from it on
+    /// this is the inverse of `augment`
+    /// # Errors
+    /// returns `MatrixError::DimensionMismatch` if the index is past the last column
+    pub fn split_columns(&self, at: usize) -> Result<(DMatrix<T>, DMatrix<T>), MatrixError> {
+        if at > self.cols {
+            return Err(MatrixError::DimensionMismatch {
+                expected: (self.rows, at),
+                found: self.shape()
+            });
+        }
+        Ok((
+            DMatrix::from_fn(self.rows, at, |row, col| self[(row, col)].clone()),
+            DMatrix::from_fn(self.rows, self.cols - at, |row, col| self[(row, at + col)].clone())
+        ))
+    }
+}
+
+impl DMatrix<f64> {
+    /// Create a new matrix of zeros
+    pub fn zeros(rows: usize, cols: usize) -> DMatrix<f64> {
+        DMatrix::filled(rows, cols, 0.0)
+    }
+
+    /// Get the only entry of a 1x1 matrix
+    /// e.g. the dot product from multiplying a row vector by a column vector
+    /// or None if the matrix is not 1x1
+    pub fn scalar(&self) -> Option<f64> {
+        (self.shape() == (1, 1)).then(|| self.data[0])
+    }
+
+    /// Convert a 3x1 column vector or a 1x3 row vector to a Vec3d
+    /// # Errors
+    /// returns `MatrixError::DimensionMismatch` if the matrix is any other shape
+    pub fn to_vec3d(&self) -> Result<Vec3d, MatrixError> {
+        if self.shape() != (3, 1) && self.shape() != (1, 3) {
+            return Err(MatrixError::DimensionMismatch {
+                expected: (3, 1),
+                found: self.shape()
+            });
+        }
+        Ok(Vec3d::new(self.data[0], self.data[1], self.data[2]))
+    }
+
+    /// Create a new square identity matrix
+    pub fn identity(size: usize) -> DMatrix<f64> {
+        DMatrix::from_fn(size, size, |row, col| if row == col { 1.0 } else { 0.0 })
+    }
+
+    /// Get the lower and upper bandwidths of the matrix
+    /// i.e. how far the furthest entry larger than the tolerance sits below and above the diagonal
+    /// a tolerance lets row reduced results with tiny residue in their zero entries count as zero
+    pub fn bandwidth(&self, tolerance: f64) -> (usize, usize) {
+        self.iter_indexed()
+            .filter(|(_, value)| value.abs() > tolerance)
+            .fold((0, 0), |(lower, upper), ((row, col), _)| {
+                (lower.max(row.saturating_sub(col)), upper.max(col.saturating_sub(row)))
+            })
+    }
+
+    /// Check if every entry below the diagonal is within the tolerance of zero
+    pub fn is_upper_triangular(&self, tolerance: f64) -> bool {
+        self.bandwidth(tolerance).0 == 0
+    }
+
+    /// Check if every entry above the diagonal is within the tolerance of zero
+    pub fn is_lower_triangular(&self, tolerance: f64) -> bool {
+        self.bandwidth(tolerance).1 == 0
+    }
+
+    /// Check if the matrix is upper triangular with a zero diagonal
+    pub fn is_strictly_upper_triangular(&self, tolerance: f64) -> bool {
+        self.is_upper_triangular(tolerance) && self.diagonal_is_zero(tolerance)
+    }
+
+    /// Check if the matrix is lower triangular with a zero diagonal
+    pub fn is_strictly_lower_triangular(&self, tolerance: f64) -> bool {
+        self.is_lower_triangular(tolerance) && self.diagonal_is_zero(tolerance)
+    }
+
+    /// Check if every entry off the diagonal is within the tolerance of zero
+    pub fn is_diagonal(&self, tolerance: f64) -> bool {
+        self.bandwidth(tolerance) == (0, 0)
+    }
+
+    /// Check if every entry more than one place off the diagonal is within the tolerance of zero
+    pub fn is_tridiagonal(&self, tolerance: f64) -> bool {
+        let (lower, upper) = self.bandwidth(tolerance);
+        lower <= 1 && upper <= 1
+    }
+
+    fn diagonal_is_zero(&self, tolerance: f64) -> bool {
+        (0..self.rows.min(self.cols)).all(|i| self[(i, i)].abs() <= tolerance)
+    }
+
+    /// Multiply two matrices
+    /// # Errors
+    /// returns `MatrixError::DimensionMismatch` if the columns of self do not match the rows of other
+    pub fn multiply(&self, other: &DMatrix<f64>) -> Result<DMatrix<f64>, MatrixError> {
+        if self.cols != other.rows {
+            return Err(MatrixError::DimensionMismatch {
+                expected: (self.cols, other.cols),
+                found: other.shape()
+            });
+        }
+        let mut result = DMatrix::zeros(self.rows, other.cols);
+        // Loop in i k j order so the inner loop walks both matrices along their rows
+        for i in 0..self.rows {
+            for k in 0..self.cols {
+                let a = self[(i, k)];
+                for j in 0..other.cols {
+                    result[(i, j)] += a * other[(k, j)];
+                }
+            }
+        }
+        Ok(result)
+    }
+
+    /// Get the row echelon form of the matrix using gaussian elimination with partial pivoting
+    pub fn to_ref(&self) -> DMatrix<f64> {
+        self.to_ref_full().matrix
+    }
+
+    /// Get the row echelon form of the matrix along with its pivot columns and the number of row swaps
+    /// entries within a small tolerance of zero relative to the largest entry are not used as pivots
+    pub fn to_ref_full(&self) -> RefResult {
+        let mut matrix = self.clone();
+        let elimination = matrix.eliminate(self.cols);
+        RefResult {
+            matrix,
+            pivot_columns: elimination.pivot_columns,
+            swaps: elimination.swaps
+        }
+    }
+
+    /// Get the reduced row echelon form of the matrix
+    /// every pivot is scaled to 1 and cleared from the rows above it
+    /// so for an augmented system with a unique solution the left part becomes the identity and the right part the solution
+    pub fn to_rref(&self) -> DMatrix<f64> {
+        let RefResult { mut matrix, pivot_columns, .. } = self.to_ref_full();
+        for (pivot_row, &col) in pivot_columns.iter().enumerate().rev() {
+            matrix.scale_row(pivot_row, 1.0 / matrix[(pivot_row, col)]);
+            for row in 0..pivot_row {
+                let factor = matrix[(row, col)];
+                matrix.add_scaled_row(pivot_row, row, -factor);
+            }
+        }
+        matrix
+    }
+
+    /// Multiply every element of a row by a factor
+    /// as an elementary row operation the factor should not be zero,
+    /// then it keeps the solutions of an augmented system and scales the determinant by the factor
+    ///
+    /// # Panics
+    /// Panics if the row is out of range
+    pub fn scale_row(&mut self, row: usize, factor: f64) {
+        assert!(row < self.rows, "row out of range");
+        for value in &mut self.data[row * self.cols..(row + 1) * self.cols] {
+            *value *= factor;
+        }
+    }
+
+    /// Add a multiple of the source row to the destination row
+    /// as an elementary row operation the rows should be different,
+    /// then it keeps the solutions of an augmented system and the determinant
+    /// if they are the same row it is scaled by 1 + factor
+    ///
+    /// # Panics
+    /// Panics if either row is out of range
+    pub fn add_scaled_row(&mut self, source: usize, destination: usize, factor: f64) {
+        assert!(source < self.rows && destination < self.rows, "row out of range");
+        for col in 0..self.cols {
+            let value = self.data[source * self.cols + col];
+            self.data[destination * self.cols + col] += factor * value;
+        }
+    }
+
+    /// Get the rank of the matrix
+    /// i.e. the number of linearly independent rows
+    pub fn rank(&self) -> usize {
+        self.to_ref_full().rank()
+    }
+
+    /// Calculate the determinant of the matrix
+    /// # Errors
+    /// returns `MatrixError::NotSquare` if the matrix is not square
+    /// 2x2 and 3x3 matrices use the closed form instead of elimination
+    pub fn determinant(&self) -> Result<f64, MatrixError> {
+        match self.shape() {
+            (2, 2) => Ok(matrix2x2::determinant(&self.to_array())),
+            (3, 3) => Ok(matrix3x3::determinant(&self.to_array())),
+            _ => self.elimination_determinant()
+        }
+    }
+
+    fn elimination_determinant(&self) -> Result<f64, MatrixError> {
+        self.to_ref_full().determinant().ok_or(MatrixError::NotSquare)
+    }
+
+    /// Solve the linear system ax = b for x where self is a
+    /// # Errors
+    /// returns `MatrixError::NotSquare` if the matrix is not square,
+    /// `MatrixError::DimensionMismatch` if b does not have one element per row,
+    /// and `MatrixError::Singular` if the system has no unique solution
+    pub fn solve(&self, b: &[f64]) -> Result<Vec<f64>, MatrixError> {
+        self.solve_with_diagnostics(b).map(|(x, _)| x)
+    }
+
+    /// Solve the linear system ax = b for x and also return the pivots used
+    /// # Errors
+    /// the same as `solve`
+    pub fn solve_with_diagnostics(&self, b: &[f64]) -> Result<(Vec<f64>, SolveDiagnostics), MatrixError> {
+        if b.len() != self.rows {
+            return Err(MatrixError::DimensionMismatch {
+                expected: (self.rows, 1),
+                found: (b.len(), 1)
+            });
+        }
+        let rhs = DMatrix { rows: b.len(), cols: 1, data: b.to_vec() };
+        let (x, diagnostics) = self.solve_columns(&rhs)?;
+        Ok((x.data, diagnostics))
+    }
+
+    /// Calculate the inverse of the matrix
+    /// # Errors
+    /// returns `MatrixError::NotSquare` if the matrix is not square
+    /// and `MatrixError::Singular` if the matrix has no inverse
+    /// 2x2 and 3x3 matrices use the adjugate instead of elimination,
+    /// they are singular when the determinant is within rounding of zero relative to the largest entry
+    pub fn try_inverse(&self) -> Result<DMatrix<f64>, MatrixError> {
+        match self.shape() {
+            (2, 2) => self.closed_form_inverse::<2>(),
+            (3, 3) => self.closed_form_inverse::<3>(),
+            _ => self.try_inverse_with_diagnostics().map(|(inverse, _)| inverse)
+        }
+    }
+
+    /// Calculate the inverse of the matrix and also return the pivots used
+    /// # Errors
+    /// the same as `try_inverse`
+    pub fn try_inverse_with_diagnostics(&self) -> Result<(DMatrix<f64>, SolveDiagnostics), MatrixError> {
+        self.solve_columns(&DMatrix::identity(self.rows))
+    }
+
+    /// Get the infinity norm of the matrix
+    /// i.e. the largest sum of absolute values along a row
+    pub fn norm_inf(&self) -> f64 {
+        (0..self.rows)
+            .map(|row| (0..self.cols).map(|col| self[(row, col)].abs()).sum::<f64>())
+            .fold(0.0, f64::max)
+    }
+
+    /// Get the sum of each row as a column vector
+    pub fn row_sums(&self) -> DMatrix<f64> {
+        DMatrix::from_fn(self.rows, 1, |row, _| self.row(row).iter().sum())
+    }
+
+    /// Get the sum of each column as a row vector
+    pub fn col_sums(&self) -> DMatrix<f64> {
+        DMatrix::from_fn(1, self.cols, |_, col| (0..self.rows).map(|row| self[(row, col)]).sum())
+    }
+
+    /// Get the mean of each row as a column vector
+    /// the means are NaN if there are no columns
+    #[allow(clippy::cast_precision_loss)]
+    pub fn row_means(&self) -> DMatrix<f64> {
+        let count = self.cols as f64;
+        self.row_sums().map(|sum| sum / count)
+    }
+
+    /// Get the mean of each column as a row vector
+    /// e.g. subtracting these from every row centers a data matrix with one sample per row
+    /// the means are NaN if there are no rows
+    #[allow(clippy::cast_precision_loss)]
+    pub fn col_means(&self) -> DMatrix<f64> {
+        let count = self.rows as f64;
+        self.col_sums().map(|sum| sum / count)
+    }
+
+    /// Get the euclidean length of each row as a column vector
+    pub fn row_norms(&self) -> DMatrix<f64> {
+        DMatrix::from_fn(self.rows, 1, |row, _| self.row(row).iter().map(|value| value * value).sum::<f64>().sqrt())
+    }
+
+    /// Get the sum of every element
+    pub fn sum(&self) -> f64 {
+        self.data.iter().sum()
+    }
+
+    /// Get the mean of every element
+    /// the mean is NaN if the matrix is empty
+    #[allow(clippy::cast_precision_loss)]
+    pub fn mean(&self) -> f64 {
+        self.sum() / self.data.len() as f64
+    }
+
+    /// Get the smallest element, or None if the matrix is empty
+    /// NaN elements are ignored unless every element is NaN
+    pub fn min(&self) -> Option<f64> {
+        self.data.iter().copied().reduce(f64::min)
+    }
+
+    /// Get the largest element, or None if the matrix is empty
+    /// NaN elements are ignored unless every element is NaN
+    pub fn max(&self) -> Option<f64> {
+        self.data.iter().copied().reduce(f64::max)
+    }
+
+    fn row(&self, row: usize) -> &[f64] {
+        &self.data[row * self.cols..(row + 1) * self.cols]
+    }
+
+    /// Calculate the condition number of the matrix in the infinity norm
+    /// i.e. the norm of the matrix times the norm of its inverse
+    /// a singular matrix has an infinite condition number
+    /// # Errors
+    /// returns `MatrixError::NotSquare` if the matrix is not square
+    pub fn condition_number_inf(&self) -> Result<f64, MatrixError> {
+        match self.try_inverse() {
+            Ok(inverse) => Ok(self.norm_inf() * inverse.norm_inf()),
+            Err(MatrixError::Singular) => Ok(f64::INFINITY),
+            Err(error) => Err(error)
+        }
+    }
+
+    /// Check if the ratio of the largest to the smallest elimination pivot is below a threshold
+    /// this is cheaper than `condition_number_inf` since no inverse is computed
+    /// but it only estimates the condition number and can miss some ill conditioned matrices
+    /// non square matrices are never well conditioned
+    pub fn is_well_conditioned(&self, threshold: f64) -> bool {
+        if !self.is_square() {
+            return false;
+        }
+        let mut reduced = self.clone();
+        let diagnostics = SolveDiagnostics { pivots: reduced.eliminate(self.cols).pivots };
+        diagnostics.pivot_ratio() < threshold
+    }
+
+    /// Calculate the Moore-Penrose pseudo inverse of the matrix
+    /// uses (aᵀa)⁻¹aᵀ for a matrix with full column rank and aᵀ(aaᵀ)⁻¹ for one with full row rank
+    /// forming aᵀa squares the condition number so this loses accuracy faster than a qr based solve
+    /// # Errors
+    /// returns `MatrixError::Singular` if the matrix has neither full column nor full row rank
+    pub fn pseudo_inverse(&self) -> Result<DMatrix<f64>, MatrixError> {
+        let transpose = self.transpose();
+        if self.rows >= self.cols {
+            transpose.multiply(self)?.try_inverse()?.multiply(&transpose)
+        } else {
+            transpose.multiply(&self.multiply(&transpose)?.try_inverse()?)
+        }
+    }
+
+    /// Find the x that minimizes |ax - b| where self is a using the normal equations
+    /// for an underdetermined system this is the solution with the smallest norm
+    /// the same numerical caveats as `pseudo_inverse` apply
+    /// # Errors
+    /// returns `MatrixError::DimensionMismatch` if b does not have one element per row
+    /// and `MatrixError::Singular` if the matrix has neither full column nor full row rank
+    pub fn solve_least_squares(&self, b: &[f64]) -> Result<Vec<f64>, MatrixError> {
+        if b.len() != self.rows {
+            return Err(MatrixError::DimensionMismatch {
+                expected: (self.rows, 1),
+                found: (b.len(), 1)
+            });
+        }
+        let rhs = DMatrix { rows: b.len(), cols: 1, data: b.to_vec() };
+        Ok(self.pseudo_inverse()?.multiply(&rhs)?.data)
+    }
+
+    /// Calculate the cholesky decomposition of a symmetric positive definite matrix
+    /// returns the lower triangular l with llᵀ = self
+    /// only the lower triangle of the matrix is read, so symmetry is assumed rather than checked
+    /// returns None if the matrix is not square or a pivot is not positive
+    pub fn cholesky(&self) -> Option<DMatrix<f64>> {
+        if !self.is_square() {
+            return None;
+        }
+        let n = self.rows;
+        let mut l = DMatrix::zeros(n, n);
+        for j in 0..n {
+            let pivot = self[(j, j)] - (0..j).map(|k| l[(j, k)] * l[(j, k)]).sum::<f64>();
+            if pivot <= 0.0 || !pivot.is_finite() {
+                return None;
+            }
+            l[(j, j)] = pivot.sqrt();
+            for i in j + 1..n {
+                let sum: f64 = (0..j).map(|k| l[(i, k)] * l[(j, k)]).sum();
+                l[(i, j)] = (self[(i, j)] - sum) / l[(j, j)];
+            }
+        }
+        Some(l)
+    }
+
+    /// Check if a symmetric matrix is positive definite
+    /// i.e. if it has a cholesky decomposition
+    pub fn is_positive_definite(&self) -> bool {
+        self.cholesky().is_some()
+    }
+
+    /// Solve ax = b given the cholesky factor l of a
+    /// by solving ly = b with forward substitution and then lᵀx = y with back substitution
+    /// # Errors
+    /// returns `MatrixError::NotSquare` if l is not square
+    /// and `MatrixError::DimensionMismatch` if b does not have one element per row
+    pub fn cholesky_solve(l: &DMatrix<f64>, b: &[f64]) -> Result<Vec<f64>, MatrixError> {
+        if !l.is_square() {
+            return Err(MatrixError::NotSquare);
+        }
+        if b.len() != l.rows {
+            return Err(MatrixError::DimensionMismatch {
+                expected: (l.rows, 1),
+                found: (b.len(), 1)
+            });
+        }
+        let n = l.rows;
+        let mut y = alloc::vec![0.0; n];
+        for i in 0..n {
+            let sum: f64 = (0..i).map(|k| l[(i, k)] * y[k]).sum();
+            y[i] = (b[i] - sum) / l[(i, i)];
+        }
+        let mut x = alloc::vec![0.0; n];
+        for i in (0..n).rev() {
+            let sum: f64 = (i + 1..n).map(|k| l[(k, i)] * x[k]).sum();
+            x[i] = (y[i] - sum) / l[(i, i)];
+        }
+        Ok(x)
+    }
+
+    /// Estimate the eigenvalue with the largest magnitude and its unit eigenvector using power iteration
+    /// the eigenvalue is the rayleigh quotient of the current vector
+    /// and iteration stops once |av - λv| is below the tolerance
+    /// the iteration starts from the uneven vector (1, 1/2, ..., 1/n) normalized,
+    /// so it does not start on common eigenvectors such as the all ones vector of matrices with constant row sums
+    /// returns None for non square matrices, when av becomes zero, or if it does not converge within `max_iters`
+    #[allow(clippy::cast_precision_loss)]
+    pub fn dominant_eigenpair(&self, max_iters: usize, tolerance: f64) -> Option<(f64, Vec<f64>)> {
+        if !self.is_square() || self.rows == 0 {
+            return None;
+        }
+        let start: Vec<f64> = (1..=self.rows).map(|i| 1.0 / i as f64).collect();
+        let norm = start.iter().map(|v| v * v).sum::<f64>().sqrt();
+        let mut vector = DMatrix { rows: self.rows, cols: 1, data: start.iter().map(|v| v / norm).collect() };
+        for _ in 0..max_iters {
+            let product = self.multiply(&vector).ok()?;
+            let norm = product.data.iter().map(|w| w * w).sum::<f64>().sqrt();
+            if norm == 0.0 {
+                return None;
+            }
+            let eigenvalue: f64 = vector.data.iter().zip(&product.data).map(|(v, w)| v * w).sum();
+            let residual = product.data.iter().zip(&vector.data).map(|(w, v)| (w - eigenvalue * v).powi(2)).sum::<f64>().sqrt();
+            if residual < tolerance {
+                return Some((eigenvalue, vector.data));
+            }
+            vector = DMatrix { rows: self.rows, cols: 1, data: product.data.iter().map(|w| w / norm).collect() };
+        }
+        None
+    }
+
+    /// Solve ax = rhs for every column of rhs at once
+    #[allow(clippy::cast_precision_loss)]
+    fn solve_columns(&self, rhs: &DMatrix<f64>) -> Result<(DMatrix<f64>, SolveDiagnostics), MatrixError> {
+        if !self.is_square() {
+            return Err(MatrixError::NotSquare);
+        }
+        let n = self.rows;
+        let m = rhs.cols;
+        let mut augmented = DMatrix::from_fn(n, n + m, |row, col| if col < n { self[(row, col)] } else { rhs[(row, col - n)] });
+        let elimination = augmented.eliminate(n);
+        let scale = self.data.iter().fold(0.0_f64, |max, value| max.max(value.abs()));
+        let tolerance = scale * f64::EPSILON * n as f64;
+        if (0..n).any(|i| augmented[(i, i)].abs() <= tolerance) {
+            return Err(MatrixError::Singular);
+        }
+        let mut x = DMatrix::zeros(n, m);
+        for col in 0..m {
+            for i in (0..n).rev() {
+                let sum: f64 = (i + 1..n).map(|j| augmented[(i, j)] * x[(j, col)]).sum();
+                x[(i, col)] = (augmented[(i, n + col)] - sum) / augmented[(i, i)];
+            }
+        }
+        Ok((x, SolveDiagnostics { pivots: elimination.pivots }))
+    }
+
+    /// Reduce the matrix to row echelon form in place pivoting on the first `pivot_cols` columns
+    /// the remaining columns are carried along, e.g. the right hand side of an augmented matrix
+    #[allow(clippy::cast_precision_loss)]
+    fn eliminate(&mut self, pivot_cols: usize) -> Elimination {
+        let mut elimination = Elimination { swaps: 0, pivots: Vec::new(), pivot_columns: Vec::new() };
+        let scale = self.iter_indexed()
+            .filter(|((_, col), _)| *col < pivot_cols)
+            .fold(0.0_f64, |max, (_, value)| max.max(value.abs()));
+        let tolerance = scale * f64::EPSILON * self.rows.max(pivot_cols) as f64;
+        let mut pivot_row = 0;
+        for col in 0..pivot_cols {
+            if pivot_row == self.rows {
+                break;
+            }
+            let Some(best) = (pivot_row..self.rows).max_by(|&a, &b| self[(a, col)].abs().total_cmp(&self[(b, col)].abs())) else {
+                break;
+            };
+            elimination.pivots.push(self[(best, col)].abs());
+            if self[(best, col)].abs() <= tolerance {
+                continue;
+            }
+            elimination.pivot_columns.push(col);
+            if best != pivot_row {
+                self.swap_rows(best, pivot_row);
+                elimination.swaps += 1;
+            }
+            for row in pivot_row + 1..self.rows {
+                let factor = self[(row, col)] / self[(pivot_row, col)];
+                self.add_scaled_row(pivot_row, row, -factor);
+            }
+            pivot_row += 1;
+        }
+        elimination
+    }
+
+    /// Copy a square matrix that is known to be N by N into an array
+    fn to_array<const N: usize>(&self) -> [[f64; N]; N] {
+        core::array::from_fn(|row| core::array::from_fn(|col| self.data[row * self.cols + col]))
+    }
+
+    /// Invert a 2x2 or 3x3 matrix from its adjugate
+    #[allow(clippy::cast_precision_loss, clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
+    fn closed_form_inverse<const N: usize>(&self) -> Result<DMatrix<f64>, MatrixError> {
+        let (determinant, adjugate) = if N == 2 {
+            let [[a, b], [c, d]] = self.to_array::<2>();
+            (a * d - b * c, alloc::vec![d, -b, -c, a])
+        } else {
+            let [[a, b, c], [d, e, f], [g, h, i]] = self.to_array::<3>();
+            let cofactors = [e * i - f * h, f * g - d * i, d * h - e * g];
+            let determinant = a * cofactors[0] + b * cofactors[1] + c * cofactors[2];
+            (
+                determinant,
+                alloc::vec![
+                    cofactors[0], c * h - b * i, b * f - c * e,
+                    cofactors[1], a * i - c * g, c * d - a * f,
+                    cofactors[2], b * g - a * h, a * e - b * d
+                ]
+            )
+        };
+        let scale = self.data.iter().fold(0.0_f64, |max, value| max.max(value.abs()));
+        if determinant.abs() <= scale.powi(N as i32) * f64::EPSILON * N as f64 {
+            return Err(MatrixError::Singular);
+        }
+        Ok(DMatrix { rows: N, cols: N, data: adjugate.into_iter().map(|value| value / determinant).collect() })
+    }
+
+}
+
+/// Mixed real and complex products
+/// a real matrix is promoted to a complex one so every product goes through `DMatrix<Complex>::multiply`
+impl DMatrix<f64> {
+    /// Get the matrix as a complex matrix with no imaginary part
+    pub fn to_complex(&self) -> DMatrix<Complex> {
+        self.map(|value| Complex::new(value, 0.0))
+    }
+
+    /// Multiply every element by a complex scalar
+    pub fn scale_complex(&self, scalar: Complex) -> DMatrix<Complex> {
+        self.map(|value| Complex::new(value * scalar.real, value * scalar.imaginary))
+    }
+
+    /// Multiply by a complex matrix
+    /// # Errors
+    /// returns `MatrixError::DimensionMismatch` if the columns of self do not match the rows of other
+    pub fn multiply_complex(&self, other: &DMatrix<Complex>) -> Result<DMatrix<Complex>, MatrixError> {
+        self.to_complex().multiply(other)
+    }
+
+    fn map<T>(&self, f: impl Fn(f64) -> T) -> DMatrix<T> {
+        DMatrix { rows: self.rows, cols: self.cols, data: self.data.iter().map(|&value| f(value)).collect() }
+    }
+}
+
+impl DMatrix<Complex> {
+    /// Create a complex matrix from its real and imaginary parts
+    /// # Errors
+    /// returns `MatrixError::DimensionMismatch` if the parts are not the same shape
+    pub fn from_parts(real: &DMatrix<f64>, imaginary: &DMatrix<f64>) -> Result<DMatrix<Complex>, MatrixError> {
+        if real.shape() != imaginary.shape() {
+            return Err(MatrixError::DimensionMismatch { expected: real.shape(), found: imaginary.shape() });
+        }
+        let data = real.data.iter().zip(&imaginary.data).map(|(&re, &im)| Complex::new(re, im)).collect();
+        Ok(DMatrix { rows: real.rows, cols: real.cols, data })
+    }
+
+    /// Create a complex matrix from the magnitude and phase of every element
+    /// the phases can be any angle type, plain f64 phases are in radians
+    /// # Errors
+    /// returns `MatrixError::DimensionMismatch` if the magnitudes and phases are not the same shape
+    pub fn from_polar<A: Copy + Into<AngleRadians>>(
+        magnitudes: &DMatrix<f64>,
+        phases: &DMatrix<A>
+    ) -> Result<DMatrix<Complex>, MatrixError> {
+        if magnitudes.shape() != phases.shape() {
+            return Err(MatrixError::DimensionMismatch { expected: magnitudes.shape(), found: phases.shape() });
+        }
+        let data = magnitudes
+            .data
+            .iter()
+            .zip(&phases.data)
+            .map(|(&magnitude, &phase)| {
+                let phase: AngleRadians = phase.into();
+                Complex::new(magnitude * phase.cos(), magnitude * phase.sin())
+            })
+            .collect();
+        Ok(DMatrix { rows: magnitudes.rows, cols: magnitudes.cols, data })
+    }
+
+    /// Split the matrix into its real and imaginary parts, the inverse of `from_parts`
+    pub fn into_parts(self) -> (DMatrix<f64>, DMatrix<f64>) {
+        (self.real_part(), self.imag_part())
+    }
+
+    /// Get the real part of every element
+    pub fn real_part(&self) -> DMatrix<f64> {
+        DMatrix { rows: self.rows, cols: self.cols, data: self.data.iter().map(|value| value.real).collect() }
+    }
+
+    /// Get the imaginary part of every element
+    pub fn imag_part(&self) -> DMatrix<f64> {
+        DMatrix { rows: self.rows, cols: self.cols, data: self.data.iter().map(|value| value.imaginary).collect() }
+    }
+
+    /// Multiply every element by a complex scalar
+    pub fn scale(&self, scalar: Complex) -> DMatrix<Complex> {
+        DMatrix { rows: self.rows, cols: self.cols, data: self.data.iter().map(|value| value * scalar).collect() }
+    }
+
+    /// Multiply two complex matrices
+    /// # Errors
+    /// returns `MatrixError::DimensionMismatch` if the columns of self do not match the rows of other
+    pub fn multiply(&self, other: &DMatrix<Complex>) -> Result<DMatrix<Complex>, MatrixError> {
+        if self.cols != other.rows {
+            return Err(MatrixError::DimensionMismatch {
+                expected: (self.cols, other.cols),
+                found: other.shape()
+            });
+        }
+        let mut result = DMatrix::filled(self.rows, other.cols, Complex::default());
+        for i in 0..self.rows {
+            for k in 0..self.cols {
+                let a = self[(i, k)];
+                for j in 0..other.cols {
+                    result[(i, j)] = result[(i, j)] + a * other[(k, j)];
+                }
+            }
+        }
+        Ok(result)
+    }
+
+    /// Multiply by a real matrix
+    /// # Errors
+    /// returns `MatrixError::DimensionMismatch` if the columns of self do not match the rows of other
+    pub fn multiply_real(&self, other: &DMatrix<f64>) -> Result<DMatrix<Complex>, MatrixError> {
+        self.multiply(&other.to_complex())
+    }
+}
+
+impl<T> core::ops::Index<(usize, usize)> for DMatrix<T> {
+    type Output = T;
+
+    fn index(&self, (row, col): (usize, usize)) -> &T {
+        assert!(row < self.rows && col < self.cols, "index ({row}, {col}) out of bounds for a {}x{} matrix", self.rows, self.cols);
+        &self.data[row * self.cols + col]
+    }
+}
+
+impl<T> core::ops::IndexMut<(usize, usize)> for DMatrix<T> {
+    fn index_mut(&mut self, (row, col): (usize, usize)) -> &mut T {
+        assert!(row < self.rows && col < self.cols, "index ({row}, {col}) out of bounds for a {}x{} matrix", self.rows, self.cols);
+        &mut self.data[row * self.cols + col]
+    }
+}
+
+impl<T: Clone, const R: usize, const C: usize> From<&[[T; C]; R]> for DMatrix<T> {
+    fn from(matrix: &[[T; C]; R]) -> DMatrix<T> {
+        DMatrix::from_fn(R, C, |row, col| matrix[row][col].clone())
+    }
+}
+
+impl<T: Clone, const R: usize, const C: usize> From<[[T; C]; R]> for DMatrix<T> {
+    fn from(matrix: [[T; C]; R]) -> DMatrix<T> {
+        DMatrix::from(&matrix)
+    }
+}
+
+impl<T: Copy + Default, const R: usize, const C: usize> TryFrom<&DMatrix<T>> for [[T; C]; R] {
+    type Error = MatrixError;
+
+    /// Convert to a fixed size matrix such as the ones used by `matrix3x3`
+    fn try_from(matrix: &DMatrix<T>) -> Result<[[T; C]; R], MatrixError> {
+        if matrix.shape() != (R, C) {
+            return Err(MatrixError::DimensionMismatch {
+                expected: (R, C),
+                found: matrix.shape()
+            });
+        }
+        let mut result = [[T::default(); C]; R];
+        for ((row, col), value) in matrix.iter_indexed() {
+            result[row][col] = *value;
+        }
+        Ok(result)
+    }
+}
+
+impl From<Vec3d> for DMatrix<f64> {
+    /// Convert to a 3x1 column vector
+    fn from(v: Vec3d) -> DMatrix<f64> {
+        DMatrix { rows: 3, cols: 1, data: alloc::vec![v.x, v.y, v.z] }
+    }
+}
+
+impl From<Quat> for DMatrix<f64> {
+    /// Convert to a 4x1 column vector in the order w, i, j, k
+    fn from(q: Quat) -> DMatrix<f64> {
+        DMatrix { rows: 4, cols: 1, data: alloc::vec![q.w, q.i, q.j, q.k] }
+    }
+}
+
+impl TryFrom<&DMatrix<f64>> for Vec3d {
+    type Error = MatrixError;
+
+    /// Convert a 3x1 column vector or a 1x3 row vector, see `DMatrix::to_vec3d`
+    fn try_from(matrix: &DMatrix<f64>) -> Result<Vec3d, MatrixError> {
+        matrix.to_vec3d()
+    }
+}
+
+impl TryFrom<&DMatrix<f64>> for Quat {
+    type Error = MatrixError;
+
+    /// Convert a 4x1 column vector in the order w, i, j, k
+    fn try_from(matrix: &DMatrix<f64>) -> Result<Quat, MatrixError> {
+        if matrix.shape() != (4, 1) {
+            return Err(MatrixError::DimensionMismatch {
+                expected: (4, 1),
+                found: matrix.shape()
+            });
+        }
+        Ok(Quat::new(matrix.data[0], matrix.data[1], matrix.data[2], matrix.data[3]))
+    }
+}
+
+/// Serialize the matrix as a nested array of rows
+#[cfg(feature = "serde")]
+impl<T: serde::Serialize> serde::Serialize for DMatrix<T> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq((0..self.rows).map(|row| &self.data[row * self.cols..(row + 1) * self.cols]))
+    }
+}
+
+/// Deserialize the matrix from a nested array of rows
+/// rows of different lengths are an error
+#[cfg(feature = "serde")]
+impl<'de, T: serde::Deserialize<'de>> serde::Deserialize<'de> for DMatrix<T> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let rows = Vec::<Vec<T>>::deserialize(deserializer)?;
+        DMatrix::try_from_nested_vec(rows).map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::angle::AngleDegrees;
+
+    #[test]
+    fn test_vec3d_round_trip() {
+        let v = Vec3d::new(1.0, -2.0, 3.0);
+        let column = DMatrix::from(v);
+        assert_eq!(column.shape(), (3, 1));
+        assert_eq!(column.to_vec3d(), Ok(v));
+        assert_eq!(Vec3d::try_from(&column.transpose()), Ok(v));
+        assert_eq!(
+            DMatrix::zeros(2, 1).to_vec3d(),
+            Err(MatrixError::DimensionMismatch { expected: (3, 1), found: (2, 1) })
+        );
+    }
+
+    #[test]
+    fn test_quat_round_trip() {
+        let q = Quat::new(0.5, -0.5, 0.5, 0.5);
+        let column = DMatrix::from(q);
+        assert_eq!(column.as_slice(), &[0.5, -0.5, 0.5, 0.5]);
+        let back = Quat::try_from(&column).unwrap();
+        assert_eq!((back.w, back.i, back.j, back.k), (q.w, q.i, q.j, q.k));
+        assert!(Quat::try_from(&column.transpose()).is_err());
+    }
+
+    #[test]
+    fn test_solve_into_vec3d() {
+        // Three planes x + y + z = 6, 2y + 5z = -4, and 2x + 5y - z = 27 meet at (5, 3, -2)
+        let a = DMatrix::from([
+            [1.0, 1.0, 1.0],
+            [0.0, 2.0, 5.0],
+            [2.0, 5.0, -1.0]
+        ]);
+        let x = a.solve(&[6.0, -4.0, 27.0]).unwrap();
+        let point = DMatrix::new(3, 1, x).unwrap().to_vec3d().unwrap();
+        assert!(point.distance_to(&Vec3d::new(5.0, 3.0, -2.0)) < 1e-12);
+    }
+
+    #[test]
+    fn test_scalar() {
+        let row = DMatrix::from([[1.0, 2.0, 3.0]]);
+        let column = DMatrix::from(Vec3d::new(4.0, 5.0, 6.0));
+        let product = row.multiply(&column).unwrap();
+        assert_eq!(product.scalar(), Some(32.0));
+        assert_eq!(column.scalar(), None);
+    }
+
+    fn sample() -> [[f64; 3]; 3] {
+        [
+            [1.0, 2.0, 3.0],
+            [4.0, 5.0, 6.0],
+            [7.0, 8.0, 10.0]
+        ]
+    }
+
+    #[test]
+    fn test_index_and_iter_indexed() {
+        let matrix = DMatrix::new(2, 3, alloc::vec![1, 2, 3, 4, 5, 6]).unwrap();
+        assert_eq!(matrix[(1, 0)], 4);
+        assert_eq!(matrix.get(2, 0), None);
+        let collected: Vec<((usize, usize), i32)> = matrix.iter_indexed().map(|(index, value)| (index, *value)).collect();
+        assert_eq!(collected[5], ((1, 2), 6));
+        assert_eq!(matrix.transpose().as_slice(), &[1, 4, 2, 5, 3, 6]);
+        assert_eq!(
+            DMatrix::new(2, 2, alloc::vec![1, 2, 3]),
+            Err(MatrixError::LengthMismatch { expected: 4, found: 3 })
+        );
+        assert_eq!(
+            alloc::format!("{}", MatrixError::LengthMismatch { expected: 4, found: 3 }),
+            "expected 4 elements for the matrix but found 3"
+        );
+    }
+
+    #[test]
+    fn test_try_from_nested_vec() {
+        let matrix = DMatrix::try_from_nested_vec(alloc::vec![alloc::vec![1, 2], alloc::vec![3, 4], alloc::vec![5, 6]]).unwrap();
+        assert_eq!(matrix.shape(), (3, 2));
+        assert_eq!(matrix[(2, 1)], 6);
+        assert_eq!(
+            DMatrix::try_from_nested_vec(alloc::vec![alloc::vec![1, 2], alloc::vec![3]]),
+            Err(MatrixError::DimensionMismatch {
+                expected: (1, 2),
+                found: (1, 1)
+            })
+        );
+    }
+
+    #[test]
+    fn test_multiply() {
+        let a = DMatrix::from(sample());
+        let b = DMatrix::from(matrix3x3::transpose(&sample()));
+        let product = a.multiply(&b).unwrap();
+        let expected = [
+            [14.0, 32.0, 53.0],
+            [32.0, 77.0, 128.0],
+            [53.0, 128.0, 213.0]
+        ];
+        for ((row, col), value) in product.iter_indexed() {
+            assert!((value - expected[row][col]).abs() < f64::EPSILON);
+        }
+        let identity = a.multiply(&DMatrix::identity(3)).unwrap();
+        assert_eq!(identity, a);
+    }
+
+    #[test]
+    fn test_multiply_dimension_mismatch() {
+        let a = DMatrix::from(sample());
+        let b = DMatrix::zeros(2, 3);
+        assert_eq!(
+            a.multiply(&b),
+            Err(MatrixError::DimensionMismatch {
+                expected: (3, 3),
+                found: (2, 3)
+            })
+        );
+    }
+
+    #[test]
+    fn test_determinant() {
+        let matrix = DMatrix::from(sample());
+        assert!((matrix.determinant().unwrap() - matrix3x3::determinant(&sample())).abs() < 1e-12);
+        let singular = DMatrix::from([[1.0, 2.0], [2.0, 4.0]]);
+        assert!(singular.determinant().unwrap().abs() < f64::EPSILON);
+        assert_eq!(DMatrix::zeros(2, 3).determinant(), Err(MatrixError::NotSquare));
+    }
+
+    #[test]
+    fn test_to_ref() {
+        let reduced = DMatrix::from(sample()).to_ref();
+        for ((row, col), value) in reduced.iter_indexed() {
+            if row > col {
+                assert!(value.abs() < 1e-12);
+            }
+        }
+    }
+
+    #[test]
+    fn test_solve() {
+        let matrix = DMatrix::from(sample());
+        let x = matrix.solve(&[14.0, 32.0, 53.0]).unwrap();
+        for (value, expected) in x.iter().zip([1.0, 2.0, 3.0]) {
+            assert!((value - expected).abs() < 1e-12);
+        }
+        let singular = DMatrix::from([[1.0, 2.0], [2.0, 4.0]]);
+        assert_eq!(singular.solve(&[1.0, 2.0]), Err(MatrixError::Singular));
+        assert!(matches!(matrix.solve(&[1.0]), Err(MatrixError::DimensionMismatch { .. })));
+    }
+
+    fn hilbert(size: usize) -> DMatrix<f64> {
+        DMatrix::from_fn(size, size, |row, col| 1.0 / f64::from(u32::try_from(row + col + 1).unwrap()))
+    }
+
+    #[test]
+    fn test_try_inverse() {
+        let matrix = DMatrix::from(sample());
+        let product = matrix.multiply(&matrix.try_inverse().unwrap()).unwrap();
+        for ((row, col), value) in product.iter_indexed() {
+            let expected = if row == col { 1.0 } else { 0.0 };
+            assert!((value - expected).abs() < 1e-12);
+        }
+        let singular = DMatrix::from([[1.0, 2.0], [2.0, 4.0]]);
+        assert_eq!(singular.try_inverse(), Err(MatrixError::Singular));
+    }
+
+    #[test]
+    fn test_condition_number_inf() {
+        assert!((DMatrix::identity(4).condition_number_inf().unwrap() - 1.0).abs() < f64::EPSILON);
+        let condition = hilbert(4).condition_number_inf().unwrap();
+        assert!((condition - 28375.0).abs() < 1e-6);
+        let singular = DMatrix::from([[1.0, 2.0], [2.0, 4.0]]);
+        assert_eq!(singular.condition_number_inf(), Ok(f64::INFINITY));
+        assert_eq!(DMatrix::zeros(2, 3).condition_number_inf(), Err(MatrixError::NotSquare));
+    }
+
+    #[test]
+    fn test_is_well_conditioned() {
+        assert!(DMatrix::identity(3).is_well_conditioned(10.0));
+        assert!(!hilbert(4).is_well_conditioned(100.0));
+        assert!(!DMatrix::from([[1.0, 2.0], [2.0, 4.0]]).is_well_conditioned(1e12));
+    }
+
+    #[test]
+    fn test_solve_diagnostics() {
+        let (x, diagnostics) = DMatrix::from([[2.0, 0.0], [0.0, 0.5]]).solve_with_diagnostics(&[4.0, 1.0]).unwrap();
+        assert_eq!(x, alloc::vec![2.0, 2.0]);
+        assert_eq!(diagnostics.pivots, alloc::vec![2.0, 0.5]);
+        assert!((diagnostics.pivot_ratio() - 4.0).abs() < f64::EPSILON);
+    }
+
+    fn assert_matrix_close(a: &DMatrix<f64>, b: &DMatrix<f64>) {
+        assert_eq!(a.shape(), b.shape());
+        for ((row, col), value) in a.iter_indexed() {
+            assert!((value - b[(row, col)]).abs() < 1e-12, "{a:?} != {b:?}");
+        }
+    }
+
+    #[test]
+    fn test_pseudo_inverse_orthonormal_columns() {
+        let root_half = core::f64::consts::FRAC_1_SQRT_2;
+        let matrix = DMatrix::from([[root_half, 0.0], [root_half, 0.0], [0.0, 1.0]]);
+        assert_matrix_close(&matrix.pseudo_inverse().unwrap(), &matrix.transpose());
+    }
+
+    #[test]
+    fn test_pseudo_inverse_reproduces_matrix() {
+        let tall = DMatrix::from([[1.0, 2.0], [3.0, 4.0], [5.0, 7.0]]);
+        let wide = tall.transpose();
+        for matrix in [tall, wide] {
+            let pseudo_inverse = matrix.pseudo_inverse().unwrap();
+            assert_eq!(pseudo_inverse.shape(), matrix.transpose().shape());
+            let product = matrix.multiply(&pseudo_inverse).unwrap().multiply(&matrix).unwrap();
+            assert_matrix_close(&product, &matrix);
+        }
+        let rank_deficient = DMatrix::from([[1.0, 2.0], [2.0, 4.0], [3.0, 6.0]]);
+        assert_eq!(rank_deficient.pseudo_inverse(), Err(MatrixError::Singular));
+    }
+
+    #[test]
+    fn test_solve_least_squares_line_fit() {
+        // Fit y = slope * x + intercept through noisy samples of y = 2x + 1
+        let xs = [0.0, 1.0, 2.0, 3.0];
+        let ys = [1.1, 2.9, 5.1, 6.9];
+        let design = DMatrix::from_fn(4, 2, |row, col| if col == 0 { xs[row] } else { 1.0 });
+        let fit = design.solve_least_squares(&ys).unwrap();
+        assert!((fit[0] - 1.96).abs() < 1e-12);
+        assert!((fit[1] - 1.06).abs() < 1e-12);
+        assert!(matches!(design.solve_least_squares(&[1.0]), Err(MatrixError::DimensionMismatch { .. })));
+    }
+
+    #[test]
+    fn test_dominant_eigenpair_diagonal() {
+        let matrix = DMatrix::from([[1.0, 0.0, 0.0], [0.0, 5.0, 0.0], [0.0, 0.0, 3.0]]);
+        let (eigenvalue, eigenvector) = matrix.dominant_eigenpair(100, 1e-10).unwrap();
+        assert!((eigenvalue - 5.0).abs() < 1e-9);
+        assert!((eigenvector[1].abs() - 1.0).abs() < 1e-9);
+        assert!(eigenvector[0].abs() < 1e-9 && eigenvector[2].abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_dominant_eigenpair_symmetric() {
+        // The eigenvalues are 3 + √3, 3, and 3 - √3
+        let matrix = DMatrix::from([[4.0, 1.0, 0.0], [1.0, 3.0, 1.0], [0.0, 1.0, 2.0]]);
+        let (eigenvalue, eigenvector) = matrix.dominant_eigenpair(200, 1e-10).unwrap();
+        assert!((eigenvalue - (3.0 + 3.0_f64.sqrt())).abs() < 1e-9);
+        let product = matrix.multiply(&DMatrix::new(3, 1, eigenvector.clone()).unwrap()).unwrap();
+        for (w, v) in product.as_slice().iter().zip(&eigenvector) {
+            assert!((w - eigenvalue * v).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_dominant_eigenpair_constant_row_sums() {
+        // The all ones vector is an eigenvector of both but not the dominant one
+        let matrix = DMatrix::from([[1.0, -2.0], [-2.0, 1.0]]);
+        let (eigenvalue, eigenvector) = matrix.dominant_eigenpair(200, 1e-10).unwrap();
+        assert!((eigenvalue - 3.0).abs() < 1e-9);
+        assert!((eigenvector[0] + eigenvector[1]).abs() < 1e-9);
+        let laplacian = DMatrix::from([[1.0, -1.0, 0.0], [-1.0, 2.0, -1.0], [0.0, -1.0, 1.0]]);
+        let (eigenvalue, eigenvector) = laplacian.dominant_eigenpair(500, 1e-10).unwrap();
+        assert!((eigenvalue - 3.0).abs() < 1e-9);
+        let expected = [1.0, -2.0, 1.0].map(|v| v / 6.0_f64.sqrt() * eigenvector[0].signum());
+        for (v, e) in eigenvector.iter().zip(expected) {
+            assert!((v - e).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_dominant_eigenpair_failure() {
+        assert_eq!(DMatrix::zeros(3, 3).dominant_eigenpair(100, 1e-10), None);
+        // Eigenvalues of equal magnitude and opposite sign never settle
+        let oscillating = DMatrix::from([[1.0, 0.0], [0.0, -1.0]]);
+        assert_eq!(oscillating.dominant_eigenpair(100, 1e-10), None);
+        assert_eq!(DMatrix::zeros(2, 3).dominant_eigenpair(100, 1e-10), None);
+    }
+
+    fn spd() -> DMatrix<f64> {
+        DMatrix::from([[4.0, 12.0, -16.0], [12.0, 37.0, -43.0], [-16.0, -43.0, 98.0]])
+    }
+
+    #[test]
+    fn test_cholesky() {
+        let matrix = spd();
+        let l = matrix.cholesky().unwrap();
+        assert_matrix_close(&l, &DMatrix::from([[2.0, 0.0, 0.0], [6.0, 1.0, 0.0], [-8.0, 5.0, 3.0]]));
+        assert_matrix_close(&l.multiply(&l.transpose()).unwrap(), &matrix);
+        assert!(matrix.is_positive_definite());
+    }
+
+    #[test]
+    fn test_cholesky_indefinite() {
+        let indefinite = DMatrix::from([[1.0, 2.0], [2.0, 1.0]]);
+        assert_eq!(indefinite.cholesky(), None);
+        assert!(!indefinite.is_positive_definite());
+        assert!(!DMatrix::zeros(2, 3).is_positive_definite());
+    }
+
+    #[test]
+    fn test_cholesky_solve() {
+        let matrix = spd();
+        let b = [1.0, 2.0, 3.0];
+        let x = DMatrix::cholesky_solve(&matrix.cholesky().unwrap(), &b).unwrap();
+        for (value, expected) in x.iter().zip(matrix.solve(&b).unwrap()) {
+            assert!((value - expected).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_bandwidth() {
+        let banded = DMatrix::from([
+            [1.0, 2.0, 0.0, 0.0],
+            [3.0, 4.0, 5.0, 0.0],
+            [6.0, 7.0, 8.0, 9.0],
+            [0.0, 0.0, 1.0, 2.0]
+        ]);
+        assert_eq!(banded.bandwidth(0.0), (2, 1));
+        assert!(!banded.is_tridiagonal(0.0));
+        assert_eq!(DMatrix::identity(3).bandwidth(0.0), (0, 0));
+        assert_eq!(DMatrix::zeros(2, 4).bandwidth(0.0), (0, 0));
+    }
+
+    #[test]
+    fn test_triangular_predicates() {
+        let upper = DMatrix::from([[1.0, 2.0, 3.0], [0.0, 4.0, 5.0], [0.0, 0.0, 6.0]]);
+        assert!(upper.is_upper_triangular(0.0));
+        assert!(!upper.is_lower_triangular(0.0));
+        assert!(!upper.is_strictly_upper_triangular(0.0));
+        assert!(upper.transpose().is_lower_triangular(0.0));
+        let strict = DMatrix::from([[0.0, 2.0, 3.0], [0.0, 0.0, 5.0], [0.0, 0.0, 0.0]]);
+        assert!(strict.is_strictly_upper_triangular(0.0));
+        assert!(strict.transpose().is_strictly_lower_triangular(0.0));
+        assert!(!strict.is_strictly_lower_triangular(0.0));
+    }
+
+    #[test]
+    fn test_diagonal_predicates() {
+        let diagonal = DMatrix::from([[1.0, 0.0], [0.0, 2.0]]);
+        assert!(diagonal.is_diagonal(0.0));
+        assert!(diagonal.is_tridiagonal(0.0));
+        assert!(diagonal.is_upper_triangular(0.0) && diagonal.is_lower_triangular(0.0));
+        let tridiagonal = DMatrix::from([[2.0, -1.0, 0.0], [-1.0, 2.0, -1.0], [0.0, -1.0, 2.0]]);
+        assert!(tridiagonal.is_tridiagonal(0.0));
+        assert!(!tridiagonal.is_diagonal(0.0));
+    }
+
+    #[test]
+    fn test_predicates_with_noise() {
+        let noisy = DMatrix::from([[1.0, 2.0, 3.0], [1e-17, 4.0, 5.0], [-2e-17, 1e-17, 6.0]]);
+        assert!(!noisy.is_upper_triangular(0.0));
+        assert!(noisy.is_upper_triangular(1e-12));
+        assert_eq!(noisy.bandwidth(1e-12), (0, 2));
+        // Row reducing leaves residue in entries that should be zero
+        let reduced = DMatrix::from(sample()).to_ref();
+        assert!(reduced.is_upper_triangular(1e-12));
+    }
+
+    #[test]
+    fn test_to_ref_full() {
+        // The first column needs a swap to put the largest entry on top
+        let matrix = DMatrix::from([[1.0, 2.0, 3.0], [4.0, 5.0, 6.0], [7.0, 8.0, 9.0]]);
+        let reduced = matrix.to_ref_full();
+        assert_eq!(reduced.pivot_columns, alloc::vec![0, 1]);
+        assert_eq!(reduced.rank(), 2);
+        assert_eq!(reduced.swaps, 2);
+        assert_eq!(reduced.matrix, matrix.to_ref());
+        assert_eq!(reduced.determinant(), Some(0.0));
+        assert_eq!(matrix.rank(), 2);
+        // A zero column is skipped so the pivots land in later columns
+        let skipped = DMatrix::from([[0.0, 1.0, 2.0], [0.0, 3.0, 4.0]]);
+        let reduced = skipped.to_ref_full();
+        assert_eq!(reduced.pivot_columns, alloc::vec![1, 2]);
+        assert_eq!(reduced.swaps, 1);
+        assert_eq!(reduced.determinant(), None);
+    }
+
+    #[test]
+    fn test_determinant_via_ref_matches_closed_form() {
+        let matrices = [
+            sample(),
+            [[0.0, 2.0, 1.0], [1.0, 0.0, 3.0], [4.0, 5.0, 0.0]],
+            [[2.0, -1.0, 0.0], [-1.0, 2.0, -1.0], [0.0, -1.0, 2.0]]
+        ];
+        for matrix in matrices {
+            let expected = matrix3x3::determinant(&matrix);
+            let determinant = DMatrix::from(matrix).to_ref_full().determinant().unwrap();
+            assert!((determinant - expected).abs() < 1e-12, "{determinant} != {expected}");
+        }
+    }
+
+    #[test]
+    fn test_fixed_size_conversion() {
+        let matrix = DMatrix::from(sample());
+        let back: [[f64; 3]; 3] = (&matrix).try_into().unwrap();
+        assert_eq!(DMatrix::from(back), matrix);
+        let wrong: Result<[[f64; 2]; 2], MatrixError> = (&matrix).try_into();
+        assert_eq!(
+            wrong,
+            Err(MatrixError::DimensionMismatch {
+                expected: (2, 2),
+                found: (3, 3)
+            })
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_round_trip() {
+        let matrix = DMatrix::from(sample());
+        let json = serde_json::to_string(&matrix).unwrap();
+        assert_eq!(json, "[[1.0,2.0,3.0],[4.0,5.0,6.0],[7.0,8.0,10.0]]");
+        assert_eq!(serde_json::from_str::<DMatrix<f64>>(&json).unwrap(), matrix);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_complex_round_trip() {
+        use crate::complex::Complex;
+        let matrix = DMatrix::from([
+            [Complex::new(1.0, 2.0), Complex::new(0.0, -1.0)],
+            [Complex::new(3.0, 0.0), Complex::new(-4.0, 0.5)]
+        ]);
+        let json = serde_json::to_string(&matrix).unwrap();
+        let parsed: DMatrix<Complex> = serde_json::from_str(&json).unwrap();
+        let components = |matrix: &DMatrix<Complex>| -> Vec<(f64, f64)> {
+            matrix.as_slice().iter().map(|c| (c.real, c.imaginary)).collect()
+        };
+        assert_eq!(parsed.shape(), (2, 2));
+        assert_eq!(components(&parsed), components(&matrix));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_wrong_shape() {
+        let error = serde_json::from_str::<DMatrix<f64>>("[[1.0, 2.0], [3.0]]").unwrap_err();
+        assert!(error.to_string().contains("expected a 1x2 matrix but found 1x1"));
+    }
+
+    fn complex_close(matrix: &DMatrix<Complex>, expected: &[(f64, f64)]) -> bool {
+        matrix.as_slice().len() == expected.len()
+            && matrix.as_slice().iter().zip(expected).all(|(value, &(re, im))| {
+                (value.real - re).abs() < 1e-12 && (value.imaginary - im).abs() < 1e-12
+            })
+    }
+
+    #[test]
+    fn test_complex_parts_round_trip() {
+        let a = DMatrix::from([[1.0, 2.0], [3.0, 4.0]]);
+        let b = DMatrix::from([[-1.0, 0.5], [0.0, 2.0]]);
+        let c = DMatrix::from_parts(&a, &b).unwrap();
+        assert_eq!(c.real_part(), a);
+        assert_eq!(c.imag_part(), b);
+        assert!(DMatrix::from_parts(&a, &DMatrix::zeros(2, 3)).is_err());
+
+        assert_eq!(a.to_complex().imag_part(), DMatrix::zeros(2, 2));
+        let scaled = a.scale_complex(Complex::new(0.0, 2.0));
+        assert_eq!(scaled.real_part(), DMatrix::zeros(2, 2));
+        assert!(complex_close(&c.scale(Complex::new(0.0, 1.0)), &[(1.0, 1.0), (-0.5, 2.0), (0.0, 3.0), (-2.0, 4.0)]));
+    }
+
+    #[test]
+    fn test_complex_from_polar() {
+        let ones = DMatrix::filled(2, 2, 1.0);
+        let polar = DMatrix::from_polar(&ones, &DMatrix::zeros(2, 2)).unwrap();
+        assert!(complex_close(&polar, &[(1.0, 0.0); 4]));
+        let phases = DMatrix::from([[AngleDegrees::new(90.0), AngleDegrees::new(180.0)]]);
+        let polar = DMatrix::from_polar(&DMatrix::from([[2.0, 0.5]]), &phases).unwrap();
+        assert!(complex_close(&polar, &[(0.0, 2.0), (-0.5, 0.0)]));
+        assert!(DMatrix::from_polar(&ones, &DMatrix::zeros(1, 2)).is_err());
+    }
+
+    #[test]
+    fn test_complex_into_parts_and_multiply() {
+        let a = DMatrix::from([[1.0, 2.0], [3.0, 4.0]]);
+        let b = DMatrix::from([[-1.0, 0.5], [0.0, 2.0]]);
+        let c = DMatrix::from_parts(&a, &b).unwrap();
+        assert_eq!(c.clone().into_parts(), (a, b));
+        let by_hand = DMatrix::from([
+            [Complex::new(1.0, -1.0), Complex::new(2.0, 0.5)],
+            [Complex::new(3.0, 0.0), Complex::new(4.0, 2.0)]
+        ]);
+        let vector = DMatrix::new(2, 1, alloc::vec![Complex::new(0.5, 1.0), Complex::new(-1.0, 2.0)]).unwrap();
+        let expected = by_hand.multiply(&vector).unwrap();
+        let expected: Vec<(f64, f64)> = expected.as_slice().iter().map(|value| (value.real, value.imaginary)).collect();
+        assert!(complex_close(&c.multiply(&vector).unwrap(), &expected));
+    }
+
+    #[test]
+    fn test_real_times_complex() {
+        // A quarter turn about z applied to a complex vector
+        let rotation = DMatrix::from([[0.0, -1.0, 0.0], [1.0, 0.0, 0.0], [0.0, 0.0, 1.0]]);
+        let vector = DMatrix::new(
+            3,
+            1,
+            alloc::vec![Complex::new(1.0, 2.0), Complex::new(3.0, -1.0), Complex::new(0.5, 0.5)]
+        )
+        .unwrap();
+        let product = rotation.multiply_complex(&vector).unwrap();
+        assert!(complex_close(&product, &[(-3.0, 1.0), (1.0, 2.0), (0.5, 0.5)]));
+        // Multiplying the parts separately gives the same result
+        assert_eq!(product.real_part(), rotation.multiply(&vector.real_part()).unwrap());
+        assert_eq!(product.imag_part(), rotation.multiply(&vector.imag_part()).unwrap());
+
+        let row = vector.transpose().multiply_real(&rotation).unwrap();
+        assert!(complex_close(&row, &[(3.0, -1.0), (-1.0, -2.0), (0.5, 0.5)]));
+        assert!(rotation.multiply_complex(&row).is_err());
+    }
+
+    fn random_matrix(rng: &mut crate::testing::Rng, size: usize) -> DMatrix<f64> {
+        DMatrix::from_fn(size, size, |_, _| rng.range(-5.0, 5.0))
+    }
+
+    fn general_inverse(matrix: &DMatrix<f64>) -> DMatrix<f64> {
+        matrix.try_inverse_with_diagnostics().unwrap().0
+    }
+
+    #[test]
+    fn test_small_fast_paths_agree_with_elimination() {
+        let mut rng = crate::testing::Rng::new(3);
+        for size in [2, 3] {
+            for _ in 0..500 {
+                let matrix = random_matrix(&mut rng, size);
+                let fast = matrix.determinant().unwrap();
+                let general = matrix.elimination_determinant().unwrap();
+                assert!((fast - general).abs() <= 1e-12 * general.abs().max(1.0));
+                let Ok(inverse) = matrix.try_inverse() else {
+                    continue;
+                };
+                let expected = general_inverse(&matrix);
+                // Compare relative to the size of the inverse so nearly singular matrices are not too strict
+                let scale = expected.norm_inf().max(1.0);
+                for (a, b) in inverse.as_slice().iter().zip(expected.as_slice()) {
+                    assert!((a - b).abs() <= 1e-12 * scale * scale, "{a} != {b}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_small_fast_paths_singular() {
+        assert_eq!(DMatrix::from([[1.0, 2.0], [2.0, 4.0]]).try_inverse(), Err(MatrixError::Singular));
+        assert_eq!(
+            DMatrix::from([[1.0, 2.0, 3.0], [4.0, 5.0, 6.0], [7.0, 8.0, 9.0]]).try_inverse(),
+            Err(MatrixError::Singular)
+        );
+        assert_eq!(DMatrix::from([[2.0, 0.0], [0.0, 4.0]]).try_inverse(), Ok(DMatrix::from([[0.5, 0.0], [0.0, 0.25]])));
+    }
+
+    #[test]
+    fn test_small_inverse_uses_closed_form() {
+        // The determinant is 1 so the adjugate gives the inverse exactly but elimination rounds
+        let matrix = [[1.0, 2.0, 3.0], [0.0, 1.0, 4.0], [5.0, 6.0, 0.0]];
+        let inverse = DMatrix::from(matrix).try_inverse().unwrap();
+        assert_eq!(inverse, DMatrix::from(crate::matrix::matrix3x3::inverse(&matrix).unwrap()));
+        assert_eq!(inverse, DMatrix::from([[-24.0, 18.0, 5.0], [20.0, -15.0, -4.0], [-5.0, 4.0, 1.0]]));
+        assert_ne!(inverse, general_inverse(&DMatrix::from(matrix)));
+    }
+
+    #[test]
+    fn test_augment_and_split() {
+        let a = DMatrix::from([[1.0, 2.0], [3.0, 4.0]]);
+        let b = DMatrix::from([[5.0], [6.0]]);
+        let augmented = a.augment(&b).unwrap();
+        assert_eq!(augmented, DMatrix::from([[1.0, 2.0, 5.0], [3.0, 4.0, 6.0]]));
+        assert_eq!(augmented.split_columns(2), Ok((a.clone(), b)));
+        assert!(a.augment(&DMatrix::zeros(3, 1)).is_err());
+        assert!(a.split_columns(3).is_err());
+        let (left, right) = a.split_columns(0).unwrap();
+        assert_eq!(left.shape(), (2, 0));
+        assert_eq!(right, a);
+    }
+
+    #[test]
+    fn test_row_operations() {
+        let mut m = DMatrix::from([[1.0, 2.0], [3.0, 4.0]]);
+        m.swap_rows(0, 1);
+        assert_eq!(m, DMatrix::from([[3.0, 4.0], [1.0, 2.0]]));
+        m.scale_row(1, 2.0);
+        assert_eq!(m, DMatrix::from([[3.0, 4.0], [2.0, 4.0]]));
+        m.add_scaled_row(1, 0, -1.0);
+        assert_eq!(m, DMatrix::from([[1.0, 0.0], [2.0, 4.0]]));
+    }
+
+    #[test]
+    fn test_rref_solves_augmented_system() {
+        let a = DMatrix::from([[2.0, 1.0, -1.0], [-3.0, -1.0, 2.0], [-2.0, 1.0, 2.0]]);
+        let b = [8.0, -11.0, -3.0];
+        let augmented = a.augment(&DMatrix::new(3, 1, b.to_vec()).unwrap()).unwrap();
+        let (identity, x) = augmented.to_rref().split_columns(3).unwrap();
+        for (value, expected) in identity.as_slice().iter().zip(DMatrix::identity(3).as_slice()) {
+            assert!((value - expected).abs() < 1e-12);
+        }
+        for (value, expected) in x.as_slice().iter().zip(a.solve(&b).unwrap()) {
+            assert!((value - expected).abs() < 1e-12);
+        }
+        assert!((x[(0, 0)] - 2.0).abs() < 1e-12 && (x[(1, 0)] - 3.0).abs() < 1e-12 && (x[(2, 0)] + 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_reductions() {
+        let m = DMatrix::from([[1.0, 2.0, 3.0], [4.0, -6.0, 8.0]]);
+        assert_eq!(m.row_sums(), DMatrix::from([[6.0], [6.0]]));
+        assert_eq!(m.col_sums(), DMatrix::from([[5.0, -4.0, 11.0]]));
+        assert_eq!(m.row_means(), DMatrix::from([[2.0], [2.0]]));
+        assert_eq!(m.col_means(), DMatrix::from([[2.5, -2.0, 5.5]]));
+        assert_eq!(m.row_norms(), DMatrix::from([[14.0_f64.sqrt()], [116.0_f64.sqrt()]]));
+        assert_eq!((m.sum(), m.mean(), m.min(), m.max()), (12.0, 2.0, Some(-6.0), Some(8.0)));
+        assert_eq!(DMatrix::zeros(0, 3).max(), None);
+    }
+
+    #[test]
+    fn test_reductions_single_row() {
+        let m = DMatrix::from([[3.0, 4.0]]);
+        assert_eq!(m.row_sums(), DMatrix::from([[m.sum()]]));
+        assert_eq!(m.row_means(), DMatrix::from([[m.mean()]]));
+        assert_eq!(m.row_norms(), DMatrix::from([[5.0]]));
+        assert_eq!(m.col_sums(), m);
+        assert_eq!(m.col_means(), m);
+    }
+}
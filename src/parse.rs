@@ -0,0 +1,154 @@
+//! Parsing vectors and shapes back from their `Display` output
+//! vectors are written as "(x, y, z)" and shapes as a name followed by key=value fields, e.g. "sphere c=(0, 0, 1) r=2"
+//! floats are written with the shortest representation that parses back to the same value so the text round-trips exactly
+
+use alloc::string::{String, ToString};
+
+use crate::vec3d::Vec3d;
+
+/// Errors from parsing a vector or shape from text
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    /// The text did not start with the expected name or bracket
+    Expected {
+        /// What was expected, e.g. "sphere" or "("
+        expected: &'static str,
+        /// The text that was found instead
+        found: String
+    },
+    /// A field was missing or out of order
+    MissingField {
+        /// The name of the missing field
+        field: &'static str,
+        /// The text that was found in its place
+        found: String
+    },
+    /// A field was present but its value could not be parsed
+    InvalidValue {
+        /// The name of the field
+        field: &'static str,
+        /// The value that could not be parsed
+        found: String
+    },
+    /// There was more text after the last field
+    TrailingText(String)
+}
+
+impl core::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        match self {
+            ParseError::Expected { expected, found } => write!(f, "expected \"{expected}\" but found \"{found}\""),
+            ParseError::MissingField { field, found } => write!(f, "missing field {field}, found \"{found}\""),
+            ParseError::InvalidValue { field, found } => write!(f, "invalid value \"{found}\" for field {field}"),
+            ParseError::TrailingText(text) => write!(f, "unexpected text \"{text}\" after the last field")
+        }
+    }
+}
+
+impl core::error::Error for ParseError {}
+
+/// Parse a vector written as "(x, y, z)" with any whitespace around the components
+pub(crate) fn parse_vector(s: &str) -> Result<Vec3d, ParseError> {
+    let s = s.trim();
+    let inner = s.strip_prefix('(').ok_or_else(|| ParseError::Expected { expected: "(", found: first_word(s) })?;
+    let inner = inner.strip_suffix(')').ok_or_else(|| ParseError::Expected { expected: ")", found: s.to_string() })?;
+    let mut components = inner.split(',');
+    let mut values = [0.0; 3];
+    for (value, field) in values.iter_mut().zip(["x", "y", "z"]) {
+        let text = components.next().ok_or(ParseError::MissingField { field, found: String::new() })?.trim();
+        *value = text.parse().map_err(|_| ParseError::InvalidValue { field, found: text.to_string() })?;
+    }
+    if let Some(extra) = components.next() {
+        return Err(ParseError::TrailingText(extra.trim().to_string()));
+    }
+    Ok(Vec3d::from(values))
+}
+
+/// Reads the key=value fields of a shape in order
+pub(crate) struct Fields<'a> {
+    rest: &'a str
+}
+
+impl<'a> Fields<'a> {
+    /// Start reading the fields after the name of the shape
+    pub(crate) fn new(s: &'a str, name: &'static str) -> Result<Fields<'a>, ParseError> {
+        let s = s.trim();
+        match s.strip_prefix(name) {
+            Some(rest) if rest.is_empty() || rest.starts_with(char::is_whitespace) => Ok(Fields { rest }),
+            _ => Err(ParseError::Expected { expected: name, found: first_word(s) })
+        }
+    }
+
+    /// Read a field holding a vector
+    pub(crate) fn vector(&mut self, field: &'static str) -> Result<Vec3d, ParseError> {
+        let value = self.value(field)?;
+        // The vector may contain whitespace so it runs to the closing bracket instead of the next space
+        let end = value.find(')').map_or(value.len(), |index| index + 1);
+        let (text, rest) = value.split_at(end);
+        self.rest = rest;
+        parse_vector(text).map_err(|_| ParseError::InvalidValue { field, found: text.to_string() })
+    }
+
+    /// Read a field holding a number
+    pub(crate) fn number(&mut self, field: &'static str) -> Result<f64, ParseError> {
+        let value = self.value(field)?;
+        let end = value.find(char::is_whitespace).unwrap_or(value.len());
+        let (text, rest) = value.split_at(end);
+        self.rest = rest;
+        text.parse().map_err(|_| ParseError::InvalidValue { field, found: text.to_string() })
+    }
+
+    /// Check that there is nothing after the last field
+    pub(crate) fn finish(self) -> Result<(), ParseError> {
+        let rest = self.rest.trim();
+        if rest.is_empty() {
+            Ok(())
+        } else {
+            Err(ParseError::TrailingText(rest.to_string()))
+        }
+    }
+
+    /// Skip past "field =" and return the text after it
+    fn value(&mut self, field: &'static str) -> Result<&'a str, ParseError> {
+        let rest = self.rest.trim_start();
+        let missing = || ParseError::MissingField { field, found: first_word(rest) };
+        let after_key = rest.strip_prefix(field).ok_or_else(missing)?;
+        let value = after_key.trim_start().strip_prefix('=').ok_or_else(missing)?;
+        Ok(value.trim_start())
+    }
+}
+
+fn first_word(s: &str) -> String {
+    s.split_whitespace().next().unwrap_or_default().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_vector() {
+        assert_eq!(parse_vector("(1, -2.5, 3e2)"), Ok(Vec3d::new(1.0, -2.5, 300.0)));
+        assert_eq!(parse_vector("  (1,2 ,  3)\n"), Ok(Vec3d::new(1.0, 2.0, 3.0)));
+        assert_eq!(parse_vector("1, 2, 3"), Err(ParseError::Expected { expected: "(", found: "1,".to_string() }));
+        assert_eq!(parse_vector("(1, 2"), Err(ParseError::Expected { expected: ")", found: "(1, 2".to_string() }));
+        assert_eq!(parse_vector("(1, 2)"), Err(ParseError::MissingField { field: "z", found: String::new() }));
+        assert_eq!(parse_vector("(1, a, 3)"), Err(ParseError::InvalidValue { field: "y", found: "a".to_string() }));
+        assert_eq!(parse_vector("(1, 2, 3, 4)"), Err(ParseError::TrailingText("4".to_string())));
+    }
+
+    #[test]
+    fn test_fields() {
+        let mut fields = Fields::new("shape a = (1, 2, 3)  b=4", "shape").unwrap();
+        assert_eq!(fields.vector("a"), Ok(Vec3d::new(1.0, 2.0, 3.0)));
+        assert_eq!(fields.number("b"), Ok(4.0));
+        assert_eq!(fields.finish(), Ok(()));
+        assert!(matches!(Fields::new("shapes a=1", "shape"), Err(ParseError::Expected { .. })));
+        let mut fields = Fields::new("shape b=4", "shape").unwrap();
+        assert_eq!(fields.number("a"), Err(ParseError::MissingField { field: "a", found: "b=4".to_string() }));
+        assert_eq!(
+            ParseError::InvalidValue { field: "r", found: "x".to_string() }.to_string(),
+            "invalid value \"x\" for field r"
+        );
+    }
+}
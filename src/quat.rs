@@ -1,8 +1,13 @@
-use crate::angle::AngleRadians;
+use alloc::vec::Vec;
+
+use crate::angle::{AngleDegrees, AngleRadians};
+#[cfg(not(any(feature = "std", test)))]
+use crate::math::Float;
 use crate::vec3d::Vec3d;
 
 /// A quaternion
 #[derive(Copy, Clone)]
+#[repr(C)]
 pub struct Quat {
     /// The real component of the quaternion
     pub w: f64,
@@ -16,13 +21,13 @@ pub struct Quat {
 
 impl Quat {
     /// Create a new quaternion
-    pub fn new(w: f64, i: f64, j: f64, k: f64) -> Quat {
+    pub const fn new(w: f64, i: f64, j: f64, k: f64) -> Quat {
         Quat { w, i, j, k }
     }
 
     /// Create a new identity quaternion
     /// i.e. a quaternion with a real component of 1 and imaginary components of 0
-    pub fn identity() -> Quat {
+    pub const fn identity() -> Quat {
         Quat { w: 1.0, i: 0.0, j: 0.0, k: 0.0 }
     }
 
@@ -42,6 +47,22 @@ impl Quat {
         }
     }
 
+    /// Create a new quaternion from a rotation vector
+    /// i.e. the axis of rotation scaled by the angle, so this matches `from_axis_angle` with the normalized vector and its magnitude
+    /// small rotations use a series expansion so tiny steps keep their precision and a zero vector gives the identity
+    pub fn from_scaled_axis(v: &Vec3d) -> Quat {
+        let angle = v.magnitude();
+        // (w, sin(angle / 2) / angle)
+        let (w, s) = if angle < 1e-4 {
+            let angle2 = angle * angle;
+            (1.0 - angle2 / 8.0 + angle2 * angle2 / 384.0, 0.5 - angle2 / 48.0 + angle2 * angle2 / 3840.0)
+        } else {
+            ((angle / 2.0).cos(), (angle / 2.0).sin() / angle)
+        };
+        // The angle is negated the same way as from_axis_angle
+        Quat { w, i: -v.x * s, j: -v.y * s, k: -v.z * s }
+    }
+
     /// Create a new quaternion from a rotation matrix
     pub fn from_rotation_matrix(m: &[[f64; 3]; 3]) -> Quat {
         let w = (1.0 + m[0][0] + m[1][1] + m[2][2]).sqrt() / 2.0;
@@ -96,22 +117,85 @@ impl Quat {
     }
 
     /// Check if the quaternion is a unit quaternion
+    /// the magnitude only has to be within 1e-12 of 1 to allow for rounding
     pub fn is_unit(&self) -> bool {
-        (self.magnitude() - 1.0).abs() < f64::EPSILON
+        self.is_unit_with_tolerance(1e-12)
+    }
+
+    /// Check if the magnitude of the quaternion is within a tolerance of 1
+    pub fn is_unit_with_tolerance(&self, tolerance: f64) -> bool {
+        (self.magnitude() - 1.0).abs() <= tolerance
     }
 
-    /// Convert the quaternion to an axis and an angle
+    /// Get how far the quaternion is from the unit sphere as |1 - magnitude²|
+    /// this avoids a square root so it is cheap enough to check often
+    pub fn unit_error(&self) -> f64 {
+        (1.0 - self.dot(self)).abs()
+    }
+
+    /// Pull a nearly unit quaternion back onto the unit sphere with a single Newton step for 1 / magnitude
+    /// this is cheaper than dividing by the magnitude since it takes no square root or division,
+    /// and squares the unit error each time, so calling it every few hundred multiplications stops drift
+    /// quaternions far from unit length are only moved towards it, not normalized
+    pub fn renormalized(&self) -> Quat {
+        self.scale((3.0 - self.dot(self)) / 2.0)
+    }
+
+    /// Convert the quaternion to an axis and an angle, the inverse of `from_axis_angle`
+    /// the quaternion is normalized first so rounding in its magnitude does not produce NaN
+    /// the angle is in the range 0 to 2π and comes from atan2 so small angles keep their precision
+    /// a quaternion with no rotation, or a zero quaternion, gives the i axis and an angle of 0
     pub fn to_axis_angle(&self) -> (Vec3d, AngleRadians) {
-        if (self.w - 1.0).abs() < f64::EPSILON {
-            (Vec3d::i(), 0.0.into())
-        } else {
-            let angle = 2.0 * self.w.acos();
-            let s = (angle / 2.0).sin();
-            let x = self.i / s;
-            let y = self.j / s;
-            let z = self.k / s;
-            (Vec3d::new(x, y, z), angle.into())
+        let magnitude = self.magnitude();
+        if magnitude == 0.0 {
+            return (Vec3d::i(), 0.0.into());
+        }
+        let imaginary = self.to_vec() / magnitude;
+        let s = imaginary.magnitude();
+        if s == 0.0 {
+            return (Vec3d::i(), 0.0.into());
         }
+        // s is sin(angle / 2), taking it from the imaginary part keeps the axis unit length near the identity
+        let angle = 2.0 * s.atan2(self.w / magnitude);
+        // from_axis_angle negates the angle, so the imaginary part points away from the axis
+        (-imaginary / s, angle.into())
+    }
+
+    /// Convert the quaternion to a rotation vector, the inverse of `from_scaled_axis`
+    /// the rotation is measured the short way round so the magnitude is at most π
+    /// the quaternion is normalized first and a quaternion with no rotation gives a zero vector
+    pub fn to_scaled_axis(&self) -> Vec3d {
+        let magnitude = if self.w < 0.0 { -self.magnitude() } else { self.magnitude() };
+        if magnitude == 0.0 {
+            return Vec3d::zero();
+        }
+        let imaginary = self.to_vec() / magnitude;
+        let s = imaginary.magnitude();
+        if s == 0.0 {
+            return Vec3d::zero();
+        }
+        // atan2 is accurate for tiny s so the ratio keeps its precision without a series
+        imaginary * (-2.0 * s.atan2(self.w / magnitude) / s)
+    }
+
+    /// Advance an orientation by an angular velocity over a time step
+    /// the angular velocity is a rotation vector per unit time about fixed axes, so the step is applied after self
+    /// the step goes through `from_scaled_axis` so many small steps do not drift
+    pub fn integrated(&self, angular_velocity: &Vec3d, dt: f64) -> Quat {
+        *self * Quat::from_scaled_axis(&(angular_velocity * dt))
+    }
+
+    /// Get the rotation limited to at most an angle about the same axis
+    /// the rotation is measured the short way round, so a quaternion and its negation are clamped the same
+    /// rotations already within the limit are returned unchanged, otherwise the result is a unit quaternion
+    pub fn clamped_angle(&self, max: impl Into<AngleRadians>) -> Quat {
+        let max = f64::from(max.into()).abs();
+        let shortest = if self.w < 0.0 { self.scale(-1.0) } else { *self };
+        let (axis, angle) = shortest.to_axis_angle();
+        if f64::from(angle) <= max {
+            return *self;
+        }
+        Quat::from_axis_angle(&axis, max)
     }
 
     /// Convert the quaternion to a vector
@@ -123,6 +207,8 @@ impl Quat {
 
     /// Convert the quaternion to a rotation matrix
     pub fn to_rotation_matrix(&self) -> [[f64; 3]; 3] {
+        #[cfg(feature = "debug-unit-checks")]
+        self.debug_assert_unit();
         [
             [
                 1.0 - 2.0 * (self.j * self.j + self.k * self.k),
@@ -145,12 +231,164 @@ impl Quat {
     /// Rotate a vector by the quaternion
     /// this is an active rotation
     pub fn rotate(&self, v: &Vec3d) -> Vec3d {
+        #[cfg(feature = "debug-unit-checks")]
+        self.debug_assert_unit();
         let qv = Quat { w: 0.0, i: v.x, j: v.y, k: v.z };
         (self.conjugate() * qv * self).to_vec()
     }
+
+    /// Rotate every vector in a slice by the quaternion in place
+    /// for a unit quaternion this gives the same result as calling `rotate` on each vector
+    /// with the `simd` feature the rotation is applied as a matrix to fixed width lanes of vectors
+    pub fn rotate_slice(&self, points: &mut [Vec3d]) {
+        #[cfg(feature = "simd")]
+        {
+            use crate::vec3d::batch::{Lanes, LANES};
+            let m = self.conjugate().to_rotation_matrix();
+            let mut chunks = points.chunks_exact_mut(LANES);
+            for chunk in &mut chunks {
+                let v = Lanes::load(chunk);
+                let rotated = Lanes {
+                    x: core::array::from_fn(|lane| m[0][0] * v.x[lane] + m[0][1] * v.y[lane] + m[0][2] * v.z[lane]),
+                    y: core::array::from_fn(|lane| m[1][0] * v.x[lane] + m[1][1] * v.y[lane] + m[1][2] * v.z[lane]),
+                    z: core::array::from_fn(|lane| m[2][0] * v.x[lane] + m[2][1] * v.y[lane] + m[2][2] * v.z[lane])
+                };
+                rotated.store(chunk);
+            }
+            for point in chunks.into_remainder() {
+                *point = crate::matrix::matrix3x3::mul(&m, point);
+            }
+        }
+        #[cfg(not(feature = "simd"))]
+        for point in points {
+            *point = self.rotate(point);
+        }
+    }
+
+    /// Calculate the dot product of two quaternions as 4d vectors
+    /// for unit quaternions this is the cosine of half the angle between the rotations
+    pub fn dot(&self, other: &Quat) -> f64 {
+        self.w * other.w + self.i * other.i + self.j * other.j + self.k * other.k
+    }
+
+    /// Calculate the natural logarithm of the quaternion
+    /// for a unit quaternion this is the pure quaternion of the axis scaled by half the angle
+    pub fn ln(&self) -> Quat {
+        let magnitude = self.magnitude();
+        let imaginary = self.to_vec();
+        let s = imaginary.magnitude();
+        let axis = if s == 0.0 { Vec3d::zero() } else { imaginary * (s.atan2(self.w) / s) };
+        Quat { w: magnitude.ln(), i: axis.x, j: axis.y, k: axis.z }
+    }
+
+    /// Calculate the exponential of the quaternion
+    /// the inverse of `ln`
+    pub fn exp(&self) -> Quat {
+        let imaginary = self.to_vec();
+        let s = imaginary.magnitude();
+        let scale = self.w.exp();
+        let axis = if s == 0.0 { Vec3d::zero() } else { imaginary * (scale * s.sin() / s) };
+        Quat { w: scale * s.cos(), i: axis.x, j: axis.y, k: axis.z }
+    }
+
+    /// Spherically interpolate between two unit quaternions
+    /// t = 0 gives self and t = 1 gives other
+    /// the shorter of the two arcs between the rotations is used by negating other if needed
+    pub fn slerp(&self, other: &Quat, t: f64) -> Quat {
+        #[cfg(feature = "debug-unit-checks")]
+        self.debug_assert_unit();
+        #[cfg(feature = "debug-unit-checks")]
+        other.debug_assert_unit();
+        if self.dot(other) < 0.0 {
+            self.slerp_along(&other.scale(-1.0), t)
+        } else {
+            self.slerp_along(other, t)
+        }
+    }
+
+    /// Interpolate between q0 and q1 with the spherical quadrangle curve through the inner control points a and b
+    /// t = 0 gives q0 and t = 1 gives q1
+    /// the quaternions should be unit and already have matching signs, see `squad_spline`
+    pub fn squad(q0: &Quat, q1: &Quat, a: &Quat, b: &Quat, t: f64) -> Quat {
+        let outer = q0.slerp_along(q1, t);
+        let inner = a.slerp_along(b, t);
+        outer.slerp_along(&inner, 2.0 * t * (1.0 - t))
+    }
+
+    /// Evaluate a smooth path through a sequence of unit quaternion keys
+    /// t is measured in keys, so t = 1.5 is halfway between the second and third key, and is clamped to the keys
+    /// the keys are first negated as needed so each has a positive dot product with the one before,
+    /// then each segment is a `squad` with inner controls from the neighbouring keys
+    /// so the path and its rate of rotation are continuous at the interior keys
+    /// with two keys this is the same as `slerp`
+    ///
+    /// # Panics
+    /// Panics if there are no keys
+    pub fn squad_spline(keys: &[Quat], t: f64) -> Quat {
+        assert!(!keys.is_empty(), "squad_spline needs at least one key");
+        let mut aligned: Vec<Quat> = Vec::with_capacity(keys.len());
+        for key in keys {
+            let flip = aligned.last().is_some_and(|previous| previous.dot(key) < 0.0);
+            aligned.push(if flip { key.scale(-1.0) } else { *key });
+        }
+        let last = aligned.len() - 1;
+        if last == 0 {
+            return aligned[0];
+        }
+        #[allow(clippy::cast_precision_loss)]
+        let t = t.clamp(0.0, last as f64);
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let segment = (t as usize).min(last - 1);
+        #[allow(clippy::cast_precision_loss)]
+        let local = t - segment as f64;
+        let control = |index: usize| {
+            if index == 0 || index == last {
+                return aligned[index];
+            }
+            let q = aligned[index];
+            let inverse = q.conjugate();
+            let next = (inverse * aligned[index + 1]).ln();
+            let previous = (inverse * aligned[index - 1]).ln();
+            let tangent = Quat::new(next.w + previous.w, next.i + previous.i, next.j + previous.j, next.k + previous.k);
+            q * tangent.scale(-0.25).exp()
+        };
+        Quat::squad(&aligned[segment], &aligned[segment + 1], &control(segment), &control(segment + 1), local)
+    }
+
+    /// Spherically interpolate without choosing the shorter arc
+    fn slerp_along(&self, other: &Quat, t: f64) -> Quat {
+        let cos = self.dot(other).clamp(-1.0, 1.0);
+        let (a, b) = if cos > 1.0 - 1e-10 {
+            // Nearly identical so interpolate linearly to avoid dividing by a tiny sine
+            (1.0 - t, t)
+        } else {
+            let angle = cos.acos();
+            let sin = angle.sin();
+            (((1.0 - t) * angle).sin() / sin, (t * angle).sin() / sin)
+        };
+        let q = Quat::new(
+            a * self.w + b * other.w,
+            a * self.i + b * other.i,
+            a * self.j + b * other.j,
+            a * self.k + b * other.k
+        );
+        q.scale(1.0 / q.magnitude())
+    }
+
+    /// Panic in debug builds with the `debug-unit-checks` feature if the quaternion has drifted off the unit sphere
+    /// the tolerance is loose so only real drift is caught, not rounding
+    #[cfg(feature = "debug-unit-checks")]
+    #[inline]
+    fn debug_assert_unit(&self) {
+        debug_assert!(self.unit_error() <= 1e-6, "{self} is not a unit quaternion, the unit error is {}", self.unit_error());
+    }
+
+    fn scale(&self, factor: f64) -> Quat {
+        Quat::new(self.w * factor, self.i * factor, self.j * factor, self.k * factor)
+    }
 }
 
-impl std::ops::Mul for Quat {
+impl core::ops::Mul for Quat {
     type Output = Quat;
 
     /// Multiply two quaternions
@@ -159,7 +397,7 @@ impl std::ops::Mul for Quat {
     }
 }
 
-impl std::ops::Mul<&Quat> for Quat {
+impl core::ops::Mul<&Quat> for Quat {
     type Output = Quat;
 
     /// Multiply two quaternions
@@ -174,7 +412,25 @@ impl std::ops::Mul<&Quat> for Quat {
     }
 }
 
-impl std::ops::Index<usize> for Quat {
+impl core::iter::Product for Quat {
+    /// Multiply quaternions from left to right starting at the identity
+    /// so the product of [a, b, c] is a * b * c
+    /// as rotations this applies a first and c last
+    fn product<I: Iterator<Item = Quat>>(iter: I) -> Quat {
+        iter.fold(Quat::identity(), |product, q| product * q)
+    }
+}
+
+impl<'a> core::iter::Product<&'a Quat> for Quat {
+    /// Multiply quaternions from left to right starting at the identity
+    /// so the product of [a, b, c] is a * b * c
+    /// as rotations this applies a first and c last
+    fn product<I: Iterator<Item = &'a Quat>>(iter: I) -> Quat {
+        iter.fold(Quat::identity(), |product, q| product * q)
+    }
+}
+
+impl core::ops::Index<usize> for Quat {
     type Output = f64;
 
     /// Index into a quaternion
@@ -191,15 +447,101 @@ impl std::ops::Index<usize> for Quat {
     }
 }
 
-impl std::fmt::Display for Quat {
+impl core::fmt::Display for Quat {
     /// Format the quaternion as a string
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "({}, {}, {}, {})", self.w, self.i, self.j, self.k)
+    /// the precision is applied to each component
+    /// the alternate form `{:#}` describes the rotation applied by `rotate` instead,
+    /// as the angle in degrees and the unit axis that `from_axis_angle` would take to build it,
+    /// with 1 decimal place for the angle and 3 for the axis unless a precision is given
+    /// a quaternion that is not unit length is normalized and its magnitude is added on the end
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        if !f.alternate() {
+            return if let Some(precision) = f.precision() {
+                write!(f, "({:.4$}, {:.4$}, {:.4$}, {:.4$})", self.w, self.i, self.j, self.k, precision)
+            } else {
+                write!(f, "({}, {}, {}, {})", self.w, self.i, self.j, self.k)
+            };
+        }
+        let (axis, angle) = self.to_axis_angle();
+        let degrees = AngleDegrees::from(angle);
+        if f64::from(angle) == 0.0 {
+            write!(f, "identity")?;
+        } else {
+            let angle_precision = f.precision().unwrap_or(1);
+            let axis_precision = f.precision().unwrap_or(3);
+            write!(f, "rot {degrees:.angle_precision$} about {axis:.axis_precision$}")?;
+        }
+        if !self.is_unit() {
+            write!(f, ", magnitude {:.1$}", self.magnitude(), f.precision().unwrap_or(3))?;
+        }
+        Ok(())
+    }
+}
+
+impl Default for Quat {
+    /// The identity quaternion
+    fn default() -> Quat {
+        Quat::identity()
+    }
+}
+
+#[cfg(feature = "glam")]
+impl From<glam::DQuat> for Quat {
+    /// Convert from a glam quaternion representing the same rotation
+    /// glam rotates with q v q* while `Quat::rotate` uses q* v q, so the quaternion is conjugated
+    fn from(q: glam::DQuat) -> Quat {
+        Quat::new(q.w, q.x, q.y, q.z).conjugate()
     }
 }
 
+#[cfg(feature = "glam")]
+impl From<Quat> for glam::DQuat {
+    /// Convert to a glam quaternion representing the same rotation
+    /// glam rotates with q v q* while `Quat::rotate` uses q* v q, so the quaternion is conjugated
+    fn from(q: Quat) -> glam::DQuat {
+        let q = q.conjugate();
+        glam::DQuat::from_xyzw(q.i, q.j, q.k, q.w)
+    }
+}
+
+#[cfg(feature = "mint")]
+impl From<mint::Quaternion<f64>> for Quat {
+    /// Convert from a mint quaternion representing the same rotation
+    /// other crates rotate with q v q* while `Quat::rotate` uses q* v q, so the quaternion is conjugated
+    fn from(q: mint::Quaternion<f64>) -> Quat {
+        Quat::new(q.s, q.v.x, q.v.y, q.v.z).conjugate()
+    }
+}
+
+#[cfg(feature = "mint")]
+impl From<Quat> for mint::Quaternion<f64> {
+    /// Convert to a mint quaternion representing the same rotation
+    /// other crates rotate with q v q* while `Quat::rotate` uses q* v q, so the quaternion is conjugated
+    fn from(q: Quat) -> mint::Quaternion<f64> {
+        let q = q.conjugate();
+        mint::Quaternion {
+            v: mint::Vector3 { x: q.i, y: q.j, z: q.k },
+            s: q.w
+        }
+    }
+}
+
+// SAFETY: Quat is repr(C) with only f64 fields, so it has no padding and every bit pattern is valid
+#[cfg(feature = "bytemuck")]
+unsafe impl bytemuck::Zeroable for Quat {}
+
+#[cfg(feature = "bytemuck")]
+unsafe impl bytemuck::Pod for Quat {}
+
+#[cfg(feature = "bytemuck")]
+const _: () = assert!(core::mem::size_of::<Quat>() == 4 * core::mem::size_of::<f64>());
+#[cfg(feature = "bytemuck")]
+const _: () = assert!(core::mem::align_of::<Quat>() == core::mem::align_of::<f64>());
+
 #[cfg(test)]
 mod tests {
+    use core::f64::consts::PI;
+
     use super::*;
 
     #[test]
@@ -230,6 +572,19 @@ mod tests {
         assert_eq!(q.k, 0.0);
     }
 
+    #[test]
+    fn test_from_axis_angle_accepts_any_angle() {
+        let axis = Vec3d::new(0.0, 1.0, 1.0).normalize();
+        let v = Vec3d::new(1.0, 2.0, 3.0);
+        let from_f64 = Quat::from_axis_angle(&axis, core::f64::consts::FRAC_PI_3).rotate(&v);
+        let from_radians = Quat::from_axis_angle(&axis, AngleRadians::pi() / 3.0).rotate(&v);
+        let from_degrees = Quat::from_axis_angle(&axis, crate::angle::AngleDegrees::new(60.0)).rotate(&v);
+        assert!(from_f64.distance_to(&from_radians) < 1e-12);
+        assert!(from_f64.distance_to(&from_degrees) < 1e-12);
+        let (_, angle) = Quat::from_axis_angle(&axis, crate::angle::AngleDegrees::new(60.0)).to_axis_angle();
+        assert!((f64::from(angle) - core::f64::consts::FRAC_PI_3).abs() < 1e-12);
+    }
+
     #[test]
     fn test_from_rotation_matrix() {
         let m = [
@@ -264,6 +619,15 @@ mod tests {
     fn test_is_unit() {
         let q = Quat::new(1.0, 2.0, 3.0, 4.0);
         assert_eq!(q.is_unit(), false);
+        for i in 1..200 {
+            let t = f64::from(i);
+            let axis = Vec3d::new(t.sin(), t.cos(), t.sqrt()).normalize();
+            assert!(Quat::from_axis_angle(&axis, t).is_unit());
+        }
+        let near = Quat::new(1.0 + 1e-6, 0.0, 0.0, 0.0);
+        assert!(!near.is_unit());
+        assert!(near.is_unit_with_tolerance(2e-6));
+        assert!(!near.is_unit_with_tolerance(5e-7));
     }
 
     #[test]
@@ -276,6 +640,67 @@ mod tests {
         assert_eq!(angle, 0.0.into());
     }
 
+    #[test]
+    fn test_axis_angle_round_trip() {
+        let axis = Vec3d::new(-1.0, 0.5, 2.0).normalize();
+        for angle in [0.1, 1.3, 3.0] {
+            let (found_axis, found_angle) = Quat::from_axis_angle(&axis, angle).to_axis_angle();
+            assert!(found_axis.distance_to(&axis) < 1e-12);
+            assert!((f64::from(found_angle) - angle).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_to_axis_angle_half_turn() {
+        let (axis, angle) = Quat::new(0.0, 0.0, 0.0, 1.0).to_axis_angle();
+        assert!(axis.distance_to(&-Vec3d::k()) < 1e-12);
+        assert!((f64::from(angle) - core::f64::consts::PI).abs() < 1e-12);
+        let (axis, angle) = Quat::new(-1.0, 0.0, 0.0, 0.0).to_axis_angle();
+        assert_eq!(axis, Vec3d::i());
+        assert_eq!(angle, 0.0.into());
+    }
+
+    #[test]
+    fn test_to_axis_angle_near_identity() {
+        let axis = Vec3d::new(1.0, 2.0, 2.0).normalize();
+        let q = Quat::from_axis_angle(&axis, 1e-10);
+        let (found_axis, found_angle) = q.to_axis_angle();
+        assert!(found_axis.is_unit());
+        assert!(found_axis.distance_to(&axis) < 1e-9);
+        assert!((f64::from(found_angle) - 1e-10).abs() < 1e-20);
+    }
+
+    #[test]
+    fn test_to_axis_angle_denormalized() {
+        let (axis, angle) = Quat::new(1.000_000_1, 0.0, 0.0, 0.0).to_axis_angle();
+        assert_eq!(axis, Vec3d::i());
+        assert_eq!(angle, 0.0.into());
+        let q = Quat::from_axis_angle(&Vec3d::j(), core::f64::consts::FRAC_PI_2);
+        let scaled = Quat::new(q.w * 1.000_000_1, q.i * 1.000_000_1, q.j * 1.000_000_1, q.k * 1.000_000_1);
+        let (axis, angle) = scaled.to_axis_angle();
+        assert!(axis.distance_to(&Vec3d::j()) < 1e-12);
+        assert!((f64::from(angle) - core::f64::consts::FRAC_PI_2).abs() < 1e-12);
+        assert_eq!(Quat::new(0.0, 0.0, 0.0, 0.0).to_axis_angle().1, 0.0.into());
+    }
+
+    #[test]
+    fn test_display() {
+        let q = Quat::new(1.0, 0.5, 0.25, 0.0);
+        assert_eq!(alloc::format!("{q}"), "(1, 0.5, 0.25, 0)");
+        assert_eq!(alloc::format!("{q:.2}"), "(1.00, 0.50, 0.25, 0.00)");
+    }
+
+    #[test]
+    fn test_display_rotation() {
+        assert_eq!(alloc::format!("{:#}", Quat::identity()), "identity");
+        let q = Quat::from_axis_angle(&Vec3d::k(), core::f64::consts::FRAC_PI_2);
+        assert_eq!(alloc::format!("{q:#}"), "rot 90.0° about (0.000, 0.000, 1.000)");
+        assert_eq!(alloc::format!("{q:#.2}"), "rot 90.00° about (0.00, 0.00, 1.00)");
+        let scaled = Quat::new(q.w * 2.0, q.i * 2.0, q.j * 2.0, q.k * 2.0);
+        assert_eq!(alloc::format!("{scaled:#}"), "rot 90.0° about (0.000, 0.000, 1.000), magnitude 2.000");
+        assert_eq!(alloc::format!("{:#}", Quat::new(2.0, 0.0, 0.0, 0.0)), "identity, magnitude 2.000");
+    }
+
     #[test]
     fn test_to_vec() {
         let q = Quat::new(1.0, 2.0, 3.0, 4.0);
@@ -321,6 +746,30 @@ mod tests {
         assert_eq!(q.k, 24.0);
     }
 
+    #[test]
+    fn test_product() {
+        let rotations = [
+            Quat::from_axis_angle(&Vec3d::k(), AngleRadians::half_pi()),
+            Quat::from_axis_angle(&Vec3d::i(), AngleRadians::half_pi()),
+            Quat::new(1.0, 2.0, 3.0, 4.0)
+        ];
+        let components = |q: Quat| (q.w, q.i, q.j, q.k);
+        let fold = rotations[0] * rotations[1] * rotations[2];
+        assert_eq!(components(rotations.iter().product()), components(fold));
+        assert_eq!(components(rotations.into_iter().product()), components(fold));
+        assert_eq!(components(core::iter::empty::<Quat>().product()), (1.0, 0.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn test_product_applies_rotations_in_order() {
+        let first = Quat::from_axis_angle(&Vec3d::k(), AngleRadians::half_pi());
+        let second = Quat::from_axis_angle(&Vec3d::i(), AngleRadians::half_pi());
+        let combined: Quat = [first, second].iter().product();
+        let v = Vec3d::new(1.0, 2.0, 3.0);
+        let expected = second.rotate(&first.rotate(&v));
+        assert!(combined.rotate(&v).distance_to(&expected) < 1e-12);
+    }
+
     #[test]
     fn test_index() {
         let q = Quat::new(1.0, 2.0, 3.0, 4.0);
@@ -329,7 +778,212 @@ mod tests {
         assert_eq!(q[2], 3.0);
         assert_eq!(q[3], 4.0);
     }
-}
 
+    #[cfg(feature = "glam")]
+    #[test]
+    fn test_glam_round_trip() {
+        let q = Quat::new(0.5, -0.5, 0.5, 0.5);
+        let round_trip = Quat::from(glam::DQuat::from(q));
+        assert_eq!((round_trip.w, round_trip.i, round_trip.j, round_trip.k), (q.w, q.i, q.j, q.k));
+    }
+
+    #[cfg(feature = "glam")]
+    #[test]
+    fn test_glam_rotation_matches() {
+        let q = Quat::from_axis_angle(&Vec3d::new(1.0, 2.0, -1.0).normalize(), 0.7);
+        let v = Vec3d::new(0.3, -4.0, 2.0);
+        let rotated = Vec3d::from(glam::DQuat::from(q) * glam::DVec3::from(v));
+        assert!(rotated.distance_to(&q.rotate(&v)) < 1e-12);
+    }
+
+    #[cfg(feature = "mint")]
+    #[test]
+    fn test_mint_round_trip() {
+        let q = Quat::new(0.5, -0.5, 0.5, 0.5);
+        let converted = mint::Quaternion::from(q);
+        assert_eq!((converted.s, converted.v.x, converted.v.y, converted.v.z), (0.5, 0.5, -0.5, -0.5));
+        let round_trip = Quat::from(converted);
+        assert_eq!((round_trip.w, round_trip.i, round_trip.j, round_trip.k), (q.w, q.i, q.j, q.k));
+    }
+
+    #[cfg(feature = "bytemuck")]
+    #[test]
+    fn test_bytemuck_cast() {
+        let q = Quat::new(1.0, 2.0, 3.0, 4.0);
+        let [w, i, j, k]: [f64; 4] = bytemuck::cast(q);
+        assert_eq!((w, i, j, k), (1.0, 2.0, 3.0, 4.0));
+        let zero: Quat = bytemuck::Zeroable::zeroed();
+        assert_eq!((zero.w, zero.i, zero.j, zero.k), (0.0, 0.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn test_const_constructors() {
+        const IDENTITY: Quat = Quat::identity();
+        const Q: Quat = Quat::new(1.0, 2.0, 3.0, 4.0);
+        assert_eq!((IDENTITY.w, IDENTITY.i, IDENTITY.j, IDENTITY.k), (1.0, 0.0, 0.0, 0.0));
+        assert!((Q[3] - 4.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_default() {
+        let q = Quat::default();
+        assert_eq!((q.w, q.i, q.j, q.k), (1.0, 0.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn test_rotate_slice() {
+        let q = Quat::from_axis_angle(&Vec3d::new(-1.0, 0.5, 2.0).normalize(), 1.3);
+        for count in [0, 1, 4, 6, 11] {
+            let mut points: Vec<Vec3d> = (0..count)
+                .map(|n| Vec3d::new(f64::from(n), 1.0 - f64::from(n) * 0.5, 2.0))
+                .collect();
+            let expected: Vec<Vec3d> = points.iter().map(|point| q.rotate(point)).collect();
+            q.rotate_slice(&mut points);
+            for (point, expected) in points.iter().zip(&expected) {
+                assert!(point.distance_to(expected) < 1e-12);
+            }
+        }
+    }
+
+    fn assert_quat_close(actual: &Quat, expected: &Quat, tolerance: f64) {
+        let difference = Quat::new(actual.w - expected.w, actual.i - expected.i, actual.j - expected.j, actual.k - expected.k);
+        assert!(difference.magnitude() <= tolerance, "{actual} != {expected}");
+    }
+
+    #[test]
+    fn test_ln_exp_round_trip() {
+        let q = Quat::from_axis_angle(&Vec3d::new(1.0, 2.0, 2.0).normalize(), 1.2);
+        let ln = q.ln();
+        assert!(ln.w.abs() < 1e-12);
+        assert!((ln.to_vec().magnitude() - 0.6).abs() < 1e-12);
+        assert_quat_close(&ln.exp(), &q, 1e-12);
+        assert_quat_close(&Quat::identity().ln().exp(), &Quat::identity(), 1e-15);
+    }
+
+    #[test]
+    fn test_slerp() {
+        let a = Quat::identity();
+        let b = Quat::from_axis_angle(&Vec3d::k(), PI / 2.0);
+        assert_quat_close(&a.slerp(&b, 0.0), &a, 1e-12);
+        assert_quat_close(&a.slerp(&b, 1.0), &b, 1e-12);
+        assert_quat_close(&a.slerp(&b, 0.5), &Quat::from_axis_angle(&Vec3d::k(), PI / 4.0), 1e-12);
+        // The negated quaternion is the same rotation so the short way round is still taken
+        let negated = Quat::new(-b.w, -b.i, -b.j, -b.k);
+        let v = a.slerp(&negated, 0.5).rotate(&Vec3d::i());
+        assert!((v - Quat::from_axis_angle(&Vec3d::k(), PI / 4.0).rotate(&Vec3d::i())).magnitude() < 1e-12);
+    }
+
+    #[test]
+    fn test_squad_two_keys_is_slerp() {
+        let a = Quat::from_axis_angle(&Vec3d::i(), 0.3);
+        let b = Quat::from_axis_angle(&Vec3d::new(0.0, 0.6, 0.8), 2.0);
+        for step in 0..=10 {
+            let t = f64::from(step) / 10.0;
+            assert_quat_close(&Quat::squad_spline(&[a, b], t), &a.slerp(&b, t), 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_squad_spline_is_smooth() {
+        let keys = [
+            Quat::identity(),
+            Quat::from_axis_angle(&Vec3d::k(), 1.0),
+            // Negated so the sign alignment is exercised
+            Quat::from_axis_angle(&Vec3d::new(0.0, 0.6, 0.8), 2.0).scale(-1.0),
+            Quat::from_axis_angle(&Vec3d::i(), 0.5)
+        ];
+        assert_quat_close(&Quat::squad_spline(&keys, 0.0), &keys[0], 1e-12);
+        assert_quat_close(&Quat::squad_spline(&keys, 1.0), &keys[1], 1e-12);
+        let h = 1e-6;
+        for key in [1.0, 2.0] {
+            let before = Quat::squad_spline(&keys, key - h);
+            let at = Quat::squad_spline(&keys, key);
+            let after = Quat::squad_spline(&keys, key + h);
+            assert_quat_close(&before, &at, 1e-5);
+            assert_quat_close(&after, &at, 1e-5);
+            // The rate of change is the same on each side of the key
+            let left = Quat::new(at.w - before.w, at.i - before.i, at.j - before.j, at.k - before.k).scale(1.0 / h);
+            let right = Quat::new(after.w - at.w, after.i - at.i, after.j - at.j, after.k - at.k).scale(1.0 / h);
+            assert_quat_close(&left, &right, 1e-4);
+        }
+        for step in 0..=30 {
+            assert!(Quat::squad_spline(&keys, f64::from(step) / 10.0).is_unit());
+        }
+    }
 
+    #[test]
+    fn test_clamped_angle() {
+        let identity = Quat::identity().clamped_angle(0.5);
+        assert_quat_close(&identity, &Quat::identity(), 0.0);
+        let axis = Vec3d::new(1.0, -2.0, 2.0).normalize();
+        let q = Quat::from_axis_angle(&axis, 2.0 * PI / 3.0);
+        let (expected_axis, _) = q.to_axis_angle();
+        let (clamped_axis, clamped_angle) = q.clamped_angle(PI / 2.0).to_axis_angle();
+        assert!((clamped_axis - expected_axis).magnitude() < 1e-12);
+        assert!((f64::from(clamped_angle) - PI / 2.0).abs() < 1e-12);
+        assert_quat_close(&q.clamped_angle(PI), &q, 0.0);
+        // The negated quaternion is the same rotation so it is clamped to the same rotation
+        let negated = q.scale(-1.0).clamped_angle(PI / 2.0);
+        let v = Vec3d::new(0.3, 0.1, -0.7);
+        assert!((negated.rotate(&v) - q.clamped_angle(PI / 2.0).rotate(&v)).magnitude() < 1e-12);
+    }
+
+    #[test]
+    fn test_scaled_axis_matches_axis_angle() {
+        let axis = Vec3d::new(1.0, -2.0, 0.5).normalize();
+        for angle in [1e-6, 1e-4, 0.3, 2.0] {
+            assert_quat_close(&Quat::from_scaled_axis(&(axis * angle)), &Quat::from_axis_angle(&axis, angle), 1e-15);
+        }
+        assert_quat_close(&Quat::from_scaled_axis(&Vec3d::zero()), &Quat::identity(), 0.0);
+        assert_eq!(Quat::identity().to_scaled_axis(), Vec3d::zero());
+    }
+
+    #[test]
+    fn test_scaled_axis_round_trip() {
+        let axis = Vec3d::new(0.3, 0.4, -0.5).normalize();
+        for exponent in -15..=0 {
+            let angle = 10.0_f64.powi(exponent) * core::f64::consts::PI;
+            let v = axis * angle;
+            let round_trip = Quat::from_scaled_axis(&v).to_scaled_axis();
+            assert!((round_trip - v).magnitude() <= 1e-14 * angle.max(1e-14), "{round_trip} != {v}");
+        }
+        // The negated quaternion is the same rotation
+        let q = Quat::from_scaled_axis(&(axis * 0.5));
+        assert!((q.scale(-1.0).to_scaled_axis() - axis * 0.5).magnitude() < 1e-15);
+    }
+
+    #[test]
+    fn test_integrate_tiny_steps() {
+        let axis = Vec3d::new(2.0, 1.0, -1.0).normalize();
+        let mut q = Quat::identity();
+        for _ in 0..1_000_000 {
+            q = q.integrated(&axis, 1e-12);
+        }
+        assert_quat_close(&q, &Quat::from_axis_angle(&axis, 1e-6), 1e-12);
+        assert!((q.to_scaled_axis() - axis * 1e-6).magnitude() < 1e-12);
+    }
 
+    #[test]
+    fn test_renormalized_stops_drift() {
+        let step = Quat::from_axis_angle(&Vec3d::new(1.0, -1.0, 2.0).normalize(), 0.01);
+        let mut q = Quat::identity();
+        for n in 1..=100_000 {
+            q = q * step;
+            if n % 100 == 0 {
+                q = q.renormalized();
+            }
+            assert!(q.unit_error() < 1e-12);
+        }
+        let drifted = Quat::new(1.0 + 1e-4, 0.0, 0.0, 0.0);
+        assert!(drifted.renormalized().unit_error() < 1e-7);
+        assert!(Quat::identity().unit_error() < f64::EPSILON);
+        assert!((Quat::new(2.0, 0.0, 0.0, 0.0).unit_error() - 3.0).abs() < f64::EPSILON);
+    }
+
+    #[cfg(all(feature = "debug-unit-checks", debug_assertions))]
+    #[test]
+    #[should_panic(expected = "is not a unit quaternion")]
+    fn test_debug_unit_checks() {
+        let _ = Quat::new(1.0, 1.0, 0.0, 0.0).rotate(&Vec3d::i());
+    }
+}
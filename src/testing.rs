@@ -0,0 +1,264 @@
+//! Random input generators and invariant checks for property tests
+//! the generators are deterministic for a seed so a failing case can be reproduced by rerunning with the same seed
+//! there are no dependencies, the random numbers come from a small xorshift generator
+
+use alloc::vec::Vec;
+use core::f64::consts::PI;
+
+use crate::angle::AngleRadians;
+use crate::geometry::circle::Circle;
+use crate::geometry::plane::Plane;
+use crate::geometry::sphere::Sphere;
+#[cfg(not(any(feature = "std", test)))]
+use crate::math::Float;
+use crate::quat::Quat;
+use crate::vec3d::Vec3d;
+
+/// The seed used by `for_all`
+pub const DEFAULT_SEED: u64 = 0x5eed_1e55_c0ff_ee00;
+
+/// A deterministic pseudo random number generator
+/// this is xorshift64*, it is fast and small but not suitable for anything other than generating test inputs
+#[derive(Debug, Clone)]
+pub struct Rng {
+    state: u64
+}
+
+impl Rng {
+    /// Create a new generator from a seed
+    /// a seed of 0 is replaced since xorshift would only ever produce 0 from it
+    pub fn new(seed: u64) -> Rng {
+        Rng { state: if seed == 0 { DEFAULT_SEED } else { seed } }
+    }
+
+    /// Get the next random u64
+    pub fn next_u64(&mut self) -> u64 {
+        self.state ^= self.state >> 12;
+        self.state ^= self.state << 25;
+        self.state ^= self.state >> 27;
+        self.state.wrapping_mul(0x2545_f491_4f6c_dd1d)
+    }
+
+    /// Get a random f64 in the range 0 to 1, excluding 1
+    #[allow(clippy::cast_precision_loss)]
+    pub fn next_f64(&mut self) -> f64 {
+        // The top 53 bits fill the mantissa exactly
+        (self.next_u64() >> 11) as f64 / (1_u64 << 53) as f64
+    }
+
+    /// Get a random f64 in the range min to max, excluding max
+    pub fn range(&mut self, min: f64, max: f64) -> f64 {
+        min + (max - min) * self.next_f64()
+    }
+
+    /// Get a random unit vector, uniformly distributed over the sphere
+    pub fn unit_vector(&mut self) -> Vec3d {
+        let z = self.range(-1.0, 1.0);
+        let theta = self.range(0.0, 2.0 * PI);
+        let r = (1.0 - z * z).sqrt();
+        Vec3d::new(r * theta.cos(), r * theta.sin(), z)
+    }
+}
+
+/// Types that can be generated at random for property tests
+/// the values are kept to a moderate size so rounding errors stay comparable to the tolerances in the helpers below
+pub trait Arbitrary: Sized {
+    /// Generate a random value
+    fn arbitrary(rng: &mut Rng) -> Self;
+}
+
+impl Arbitrary for f64 {
+    /// A value in the range -10 to 10
+    fn arbitrary(rng: &mut Rng) -> f64 {
+        rng.range(-10.0, 10.0)
+    }
+}
+
+impl Arbitrary for Vec3d {
+    /// A vector with each component in the range -10 to 10
+    fn arbitrary(rng: &mut Rng) -> Vec3d {
+        Vec3d::new(f64::arbitrary(rng), f64::arbitrary(rng), f64::arbitrary(rng))
+    }
+}
+
+impl Arbitrary for AngleRadians {
+    /// An angle in the range -2pi to 2pi
+    fn arbitrary(rng: &mut Rng) -> AngleRadians {
+        AngleRadians::new(rng.range(-2.0 * PI, 2.0 * PI))
+    }
+}
+
+impl Arbitrary for Quat {
+    /// A unit quaternion for a rotation about a random axis
+    fn arbitrary(rng: &mut Rng) -> Quat {
+        let axis = rng.unit_vector();
+        Quat::from_axis_angle(&axis, AngleRadians::arbitrary(rng))
+    }
+}
+
+impl Arbitrary for Sphere {
+    /// A sphere with a radius in the range 0.1 to 5
+    fn arbitrary(rng: &mut Rng) -> Sphere {
+        Sphere::new(Vec3d::arbitrary(rng), rng.range(0.1, 5.0))
+    }
+}
+
+impl Arbitrary for Circle {
+    /// A circle with a radius in the range 0.1 to 5 and a random orientation
+    fn arbitrary(rng: &mut Rng) -> Circle {
+        let center = Vec3d::arbitrary(rng);
+        let radius = rng.range(0.1, 5.0);
+        Circle::new(center, radius, rng.unit_vector())
+    }
+}
+
+impl Arbitrary for Plane {
+    /// A plane through a random point with a random orientation
+    fn arbitrary(rng: &mut Rng) -> Plane {
+        let point = Vec3d::arbitrary(rng);
+        Plane::from_point(rng.unit_vector(), point)
+    }
+}
+
+impl<A: Arbitrary, B: Arbitrary> Arbitrary for (A, B) {
+    fn arbitrary(rng: &mut Rng) -> (A, B) {
+        (A::arbitrary(rng), B::arbitrary(rng))
+    }
+}
+
+impl<A: Arbitrary, B: Arbitrary, C: Arbitrary> Arbitrary for (A, B, C) {
+    fn arbitrary(rng: &mut Rng) -> (A, B, C) {
+        (A::arbitrary(rng), B::arbitrary(rng), C::arbitrary(rng))
+    }
+}
+
+/// Check a property against a number of random inputs generated from `DEFAULT_SEED`
+/// the property should panic, e.g. with one of the assert helpers, when it does not hold
+pub fn for_all<T: Arbitrary>(cases: usize, property: impl FnMut(T)) {
+    for_all_seeded(cases, DEFAULT_SEED, property);
+}
+
+/// Check a property against a number of random inputs generated from a seed
+pub fn for_all_seeded<T: Arbitrary>(cases: usize, seed: u64, mut property: impl FnMut(T)) {
+    let mut rng = Rng::new(seed);
+    for _ in 0..cases {
+        property(T::arbitrary(&mut rng));
+    }
+}
+
+/// Get evenly spaced points around a circle
+pub fn sample_circle(circle: &Circle, count: usize) -> Vec<Vec3d> {
+    circle.sample(count)
+}
+
+/// Assert that a point is within a tolerance of a plane
+///
+/// # Panics
+/// Panics if the point is further than the tolerance from the plane
+pub fn assert_on_plane(point: &Vec3d, plane: &Plane, tolerance: f64) {
+    let distance = plane.distance_to_point(point);
+    assert!(distance.abs() <= tolerance, "{point} is {distance} from the plane");
+}
+
+/// Assert that a point is within a tolerance of the surface of a sphere
+///
+/// # Panics
+/// Panics if the point is further than the tolerance from the surface
+pub fn assert_on_sphere(point: &Vec3d, sphere: &Sphere, tolerance: f64) {
+    let distance = point.distance_to(&sphere.center) - sphere.radius;
+    assert!(distance.abs() <= tolerance, "{point} is {distance} from the surface of the sphere");
+}
+
+/// Assert that a vector is a unit vector, within the tolerance of `Vec3d::is_unit`
+///
+/// # Panics
+/// Panics if the vector is not a unit vector
+pub fn assert_unit(vector: &Vec3d) {
+    assert!(vector.is_unit(), "{vector} has a magnitude of {}", vector.magnitude());
+}
+
+/// Assert that two vectors are within a tolerance of each other
+///
+/// # Panics
+/// Panics if the vectors are further apart than the tolerance
+pub fn assert_close(actual: &Vec3d, expected: &Vec3d, tolerance: f64) {
+    assert!(actual.distance_to(expected) <= tolerance, "{actual} != {expected}");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geometry::intersection::{sphere_plane, sphere_sphere};
+
+    #[test]
+    fn test_rng_is_deterministic() {
+        let mut rng1 = Rng::new(7);
+        let mut rng2 = Rng::new(7);
+        for _ in 0..10 {
+            assert_eq!(rng1.next_u64(), rng2.next_u64());
+        }
+        for _ in 0..1000 {
+            let value = rng1.range(-2.0, 3.0);
+            assert!((-2.0..3.0).contains(&value));
+        }
+    }
+
+    #[test]
+    fn test_arbitrary_invariants() {
+        for_all(200, |(q, circle, plane): (Quat, Circle, Plane)| {
+            assert!(q.is_unit());
+            assert_unit(&circle.normal);
+            assert_unit(&plane.normal);
+        });
+    }
+
+    #[test]
+    fn property_sphere_sphere_points_lie_on_both_spheres() {
+        for_all(500, |(sphere1, sphere2): (Sphere, Sphere)| {
+            let Some(circle) = sphere_sphere(&sphere1, &sphere2) else {
+                return;
+            };
+            assert_unit(&circle.normal);
+            for point in sample_circle(&circle, 16) {
+                assert_on_sphere(&point, &sphere1, 1e-9);
+                assert_on_sphere(&point, &sphere2, 1e-9);
+            }
+        });
+    }
+
+    #[test]
+    fn property_sphere_plane_circle_lies_on_plane_and_sphere() {
+        // Spheres are placed near the plane so most cases intersect
+        for_all(500, |(plane, offset, radius): (Plane, Vec3d, f64)| {
+            let sphere = Sphere::new(offset - plane.normal * plane.distance, radius.abs() + 0.1);
+            let Some(circle) = sphere_plane(&sphere, &plane) else {
+                assert!(plane.distance_to_point(&sphere.center).abs() > sphere.radius);
+                return;
+            };
+            assert_on_plane(&circle.center, &plane, 1e-9);
+            for point in sample_circle(&circle, 16) {
+                assert_on_plane(&point, &plane, 1e-9);
+                assert_on_sphere(&point, &sphere, 1e-9);
+            }
+        });
+    }
+
+    #[test]
+    fn property_rotation_preserves_length_and_inverts() {
+        for_all(500, |(q, v): (Quat, Vec3d)| {
+            let rotated = q.rotate(&v);
+            assert!((rotated.magnitude() - v.magnitude()).abs() < 1e-9);
+            assert_close(&q.conjugate().rotate(&rotated), &v, 1e-9);
+        });
+    }
+
+    #[test]
+    fn property_wrap_is_in_range_and_equivalent() {
+        for_all(500, |angle: AngleRadians| {
+            let wrapped = f64::from(angle.wrap());
+            assert!((0.0..2.0 * PI).contains(&wrapped));
+            assert!((wrapped.sin() - angle.sin()).abs() < 1e-12);
+            assert!((wrapped.cos() - angle.cos()).abs() < 1e-12);
+        });
+    }
+}
@@ -0,0 +1,857 @@
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::f64::consts::{PI, TAU};
+
+use crate::angle::AngleRadians;
+use crate::geometry::ScaleGeometry;
+
+/// Meters in an inch, by definition
+const METERS_PER_INCH: f64 = 0.0254;
+/// Meters in a foot, by definition
+const METERS_PER_FOOT: f64 = 0.3048;
+/// Meters in a mile, by definition
+const METERS_PER_MILE: f64 = 1609.344;
+/// Seconds in a minute
+const SECONDS_PER_MINUTE: f64 = 60.0;
+/// Seconds in an hour
+const SECONDS_PER_HOUR: f64 = 3600.0;
+/// Meters per second squared in one standard gravity, by definition
+const STANDARD_GRAVITY: f64 = 9.806_65;
+/// Newtons in a pound force, by definition
+const NEWTONS_PER_POUND_FORCE: f64 = 4.448_221_615_260_5;
+/// Pascals in a bar, by definition
+const PASCALS_PER_BAR: f64 = 100_000.0;
+/// Pascals in a standard atmosphere, by definition
+const PASCALS_PER_ATMOSPHERE: f64 = 101_325.0;
+/// Pascals in a pound force per square inch
+const PASCALS_PER_PSI: f64 = NEWTONS_PER_POUND_FORCE / (METERS_PER_INCH * METERS_PER_INCH);
+/// Kelvin at zero degrees celsius, by definition
+const KELVIN_AT_ZERO_CELSIUS: f64 = 273.15;
+/// Kelvin in a degree fahrenheit, by definition
+const KELVIN_PER_FAHRENHEIT: f64 = 5.0 / 9.0;
+/// Fahrenheit at zero degrees celsius, by definition
+const FAHRENHEIT_AT_ZERO_CELSIUS: f64 = 32.0;
+
+/// A physical quantity stored as a single value in its base unit
+pub trait Quantity: Copy {
+    /// The units the quantity can be expressed in
+    type Unit: Copy;
+
+    /// Create the quantity from a value in its base unit
+    fn from_base(value: f64) -> Self;
+
+    /// Get the value of the quantity in its base unit
+    fn base_value(&self) -> f64;
+
+    /// Create the quantity from a value in the given unit
+    fn new(value: f64, unit: Self::Unit) -> Self;
+
+    /// Get the value of the quantity in the given unit
+    fn as_unit(&self, unit: Self::Unit) -> f64;
+}
+
+/// Convert a value of a quantity from one unit to another
+/// e.g. `convert::<Length>(1.0, LengthUnit::Feet, LengthUnit::Inches)` is 12 inches
+pub fn convert<Q: Quantity>(value: f64, from: Q::Unit, to: Q::Unit) -> f64 {
+    Q::new(value, from).as_unit(to)
+}
+
+define_unit! {
+    /// A length, stored internally in meters
+    linear Length("length", meters, "m");
+    /// Units a length can be expressed in
+    enum LengthUnit {
+        Meters => meters, as_meters, "meters", ["m"], 1.0;
+        Millimeters => millimeters, as_millimeters, "millimeters", ["mm"], 0.001;
+        Kilometers => kilometers, as_kilometers, "kilometers", ["km"], 1000.0;
+        Inches => inches, as_inches, "inches", ["in"], METERS_PER_INCH;
+        Feet => feet, as_feet, "feet", ["ft"], METERS_PER_FOOT;
+        Miles => miles, as_miles, "miles", ["mi"], METERS_PER_MILE;
+    }
+}
+
+define_unit! {
+    /// An absolute temperature, stored internally in kelvin
+    /// absolute temperatures cannot be added together, use a `TemperatureDelta` to offset them
+    affine Temperature("temperature", kelvin, "K");
+    /// Units a temperature can be expressed in
+    enum TemperatureUnit {
+        Kelvin => kelvin, as_kelvin, "kelvin", ["K"], 1.0;
+        Celsius => celsius, as_celsius, "degrees celsius", ["°C", "degC"], 1.0, KELVIN_AT_ZERO_CELSIUS;
+        Fahrenheit => fahrenheit, as_fahrenheit, "degrees fahrenheit", ["°F", "degF"], KELVIN_PER_FAHRENHEIT,
+            KELVIN_AT_ZERO_CELSIUS - FAHRENHEIT_AT_ZERO_CELSIUS * KELVIN_PER_FAHRENHEIT;
+    }
+}
+
+define_unit! {
+    /// A difference between two temperatures, stored internally in kelvin
+    /// a degree celsius is the same size as a kelvin
+    linear TemperatureDelta("temperature difference", kelvin, "K");
+    use TemperatureUnit {
+        Kelvin => kelvin, as_kelvin, "kelvin", 1.0;
+        Celsius => celsius, as_celsius, "degrees celsius", 1.0;
+        Fahrenheit => fahrenheit, as_fahrenheit, "degrees fahrenheit", KELVIN_PER_FAHRENHEIT;
+    }
+}
+
+impl core::ops::Add<TemperatureDelta> for Temperature {
+    type Output = Temperature;
+
+    fn add(self, rhs: TemperatureDelta) -> Temperature {
+        Temperature::kelvin(self.kelvin + rhs.kelvin)
+    }
+}
+
+impl core::ops::Sub<TemperatureDelta> for Temperature {
+    type Output = Temperature;
+
+    fn sub(self, rhs: TemperatureDelta) -> Temperature {
+        Temperature::kelvin(self.kelvin - rhs.kelvin)
+    }
+}
+
+impl core::ops::Sub for Temperature {
+    type Output = TemperatureDelta;
+
+    fn sub(self, rhs: Temperature) -> TemperatureDelta {
+        TemperatureDelta::kelvin(self.kelvin - rhs.kelvin)
+    }
+}
+
+define_unit! {
+    /// A time, stored internally in seconds
+    linear Time("time", seconds, "s");
+    /// Units a time can be expressed in
+    enum TimeUnit {
+        Seconds => seconds, as_seconds, "seconds", ["s"], 1.0;
+        Milliseconds => milliseconds, as_milliseconds, "milliseconds", ["ms"], 0.001;
+        Minutes => minutes, as_minutes, "minutes", ["min"], SECONDS_PER_MINUTE;
+        Hours => hours, as_hours, "hours", ["h"], SECONDS_PER_HOUR;
+    }
+}
+
+define_unit! {
+    /// A velocity, stored internally in meters per second
+    linear Velocity("velocity", meters_per_second, "m/s");
+    /// Units a velocity can be expressed in
+    enum VelocityUnit {
+        MetersPerSecond => meters_per_second, as_meters_per_second, "meters per second", ["m/s"], 1.0;
+        KilometersPerHour => kilometers_per_hour, as_kilometers_per_hour, "kilometers per hour", ["km/h"],
+            1000.0 / SECONDS_PER_HOUR;
+        MilesPerHour => miles_per_hour, as_miles_per_hour, "miles per hour", ["mph"], METERS_PER_MILE / SECONDS_PER_HOUR;
+        FeetPerSecond => feet_per_second, as_feet_per_second, "feet per second", ["ft/s"], METERS_PER_FOOT;
+    }
+}
+
+define_unit! {
+    /// An acceleration, stored internally in meters per second squared
+    linear Acceleration("acceleration", meters_per_second_squared, "m/s²");
+    /// Units an acceleration can be expressed in
+    enum AccelerationUnit {
+        MetersPerSecondSquared => meters_per_second_squared, as_meters_per_second_squared,
+            "meters per second squared", ["m/s²", "m/s^2"], 1.0;
+        StandardGravities => standard_gravities, as_standard_gravities, "standard gravities", ["g"], STANDARD_GRAVITY;
+    }
+}
+
+impl core::ops::Div<Time> for Length {
+    type Output = Velocity;
+
+    fn div(self, rhs: Time) -> Velocity {
+        Velocity::meters_per_second(self.meters / rhs.seconds)
+    }
+}
+
+impl core::ops::Div<Time> for Velocity {
+    type Output = Acceleration;
+
+    fn div(self, rhs: Time) -> Acceleration {
+        Acceleration::meters_per_second_squared(self.meters_per_second / rhs.seconds)
+    }
+}
+
+impl core::ops::Mul<Time> for Velocity {
+    type Output = Length;
+
+    fn mul(self, rhs: Time) -> Length {
+        Length::meters(self.meters_per_second * rhs.seconds)
+    }
+}
+
+impl core::ops::Mul<Velocity> for Time {
+    type Output = Length;
+
+    fn mul(self, rhs: Velocity) -> Length {
+        rhs * self
+    }
+}
+
+impl core::ops::Mul<Time> for Acceleration {
+    type Output = Velocity;
+
+    fn mul(self, rhs: Time) -> Velocity {
+        Velocity::meters_per_second(self.meters_per_second_squared * rhs.seconds)
+    }
+}
+
+impl core::ops::Mul<Acceleration> for Time {
+    type Output = Velocity;
+
+    fn mul(self, rhs: Acceleration) -> Velocity {
+        rhs * self
+    }
+}
+
+define_unit! {
+    /// A force, stored internally in newtons
+    linear Force("force", newtons, "N");
+    /// Units a force can be expressed in
+    enum ForceUnit {
+        Newtons => newtons, as_newtons, "newtons", ["N"], 1.0;
+        Kilonewtons => kilonewtons, as_kilonewtons, "kilonewtons", ["kN"], 1000.0;
+        PoundsForce => pounds_force, as_pounds_force, "pounds force", ["lbf"], NEWTONS_PER_POUND_FORCE;
+    }
+}
+
+define_unit! {
+    /// A pressure, stored internally in pascals
+    linear Pressure("pressure", pascals, "Pa");
+    /// Units a pressure can be expressed in
+    enum PressureUnit {
+        Pascals => pascals, as_pascals, "pascals", ["Pa"], 1.0;
+        Kilopascals => kilopascals, as_kilopascals, "kilopascals", ["kPa"], 1000.0;
+        Megapascals => megapascals, as_megapascals, "megapascals", ["MPa"], 1_000_000.0;
+        Bars => bars, as_bars, "bars", ["bar"], PASCALS_PER_BAR;
+        Psi => psi, as_psi, "pounds force per square inch", ["psi"], PASCALS_PER_PSI;
+        Atmospheres => atmospheres, as_atmospheres, "standard atmospheres", ["atm"], PASCALS_PER_ATMOSPHERE;
+    }
+}
+
+define_unit! {
+    /// A torque, stored internally in newton meters
+    linear Torque("torque", newton_meters, "N·m");
+    /// Units a torque can be expressed in
+    enum TorqueUnit {
+        NewtonMeters => newton_meters, as_newton_meters, "newton meters", ["N·m", "Nm"], 1.0;
+        PoundForceFeet => pound_force_feet, as_pound_force_feet, "pound force feet", ["lbf·ft", "lbf-ft"],
+            NEWTONS_PER_POUND_FORCE * METERS_PER_FOOT;
+        PoundForceInches => pound_force_inches, as_pound_force_inches, "pound force inches", ["lbf·in", "lbf-in"],
+            NEWTONS_PER_POUND_FORCE * METERS_PER_INCH;
+    }
+}
+
+impl core::ops::Mul<Length> for Force {
+    type Output = Torque;
+
+    /// Multiply a force by a lever arm length
+    /// this only gives the magnitude of the torque for a force perpendicular to the lever arm
+    /// use the cross product of Vec3d's for the full torque vector
+    fn mul(self, rhs: Length) -> Torque {
+        Torque::newton_meters(self.newtons * rhs.meters)
+    }
+}
+
+impl core::ops::Mul<Force> for Length {
+    type Output = Torque;
+
+    /// Multiply a lever arm length by a force
+    /// this only gives the magnitude of the torque for a force perpendicular to the lever arm
+    /// use the cross product of Vec3d's for the full torque vector
+    fn mul(self, rhs: Force) -> Torque {
+        rhs * self
+    }
+}
+
+define_unit! {
+    /// An angular velocity, stored internally in radians per second
+    linear AngularVelocity("angular velocity", radians_per_second, "rad/s");
+    /// Units an angular velocity can be expressed in
+    enum AngularVelocityUnit {
+        RadiansPerSecond => radians_per_second, as_radians_per_second, "radians per second", ["rad/s"], 1.0;
+        DegreesPerSecond => degrees_per_second, as_degrees_per_second, "degrees per second", ["deg/s", "°/s"], PI / 180.0;
+        RevolutionsPerMinute => revolutions_per_minute, as_revolutions_per_minute, "revolutions per minute", ["rpm"],
+            TAU / SECONDS_PER_MINUTE;
+    }
+}
+
+define_unit! {
+    /// A frequency, stored internally in hertz
+    linear Frequency("frequency", hertz, "Hz");
+    /// Units a frequency can be expressed in
+    enum FrequencyUnit {
+        Hertz => hertz, as_hertz, "hertz", ["Hz"], 1.0;
+        Kilohertz => kilohertz, as_kilohertz, "kilohertz", ["kHz"], 1000.0;
+    }
+}
+
+impl From<Frequency> for AngularVelocity {
+    /// Convert a frequency to the angular velocity of one full turn per cycle
+    fn from(value: Frequency) -> Self {
+        AngularVelocity::radians_per_second(value.hertz * TAU)
+    }
+}
+
+impl core::ops::Mul<Time> for AngularVelocity {
+    type Output = AngleRadians;
+
+    fn mul(self, rhs: Time) -> AngleRadians {
+        AngleRadians::new(self.radians_per_second * rhs.seconds)
+    }
+}
+
+impl core::ops::Mul<AngularVelocity> for Time {
+    type Output = AngleRadians;
+
+    fn mul(self, rhs: AngularVelocity) -> AngleRadians {
+        rhs * self
+    }
+}
+
+impl core::ops::Div<Time> for AngleRadians {
+    type Output = AngularVelocity;
+
+    fn div(self, rhs: Time) -> AngularVelocity {
+        AngularVelocity::radians_per_second(f64::from(self) / rhs.seconds)
+    }
+}
+
+/// A scale factor converting lengths from one unit to another
+/// applies to plain values as well as points and geometry
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct LengthScale {
+    factor: f64
+}
+
+impl LengthScale {
+    /// Create the scale converting lengths in one unit to another
+    /// e.g. `LengthScale::from_to(LengthUnit::Millimeters, LengthUnit::Meters)` has a factor of 0.001
+    pub fn from_to(from: LengthUnit, to: LengthUnit) -> Self {
+        Self { factor: convert::<Length>(1.0, from, to) }
+    }
+
+    /// Get the scale factor
+    pub fn factor(&self) -> f64 {
+        self.factor
+    }
+
+    /// Get the scale converting back to the original unit
+    pub fn inverse(&self) -> Self {
+        Self { factor: 1.0 / self.factor }
+    }
+
+    /// Apply the scale to a value
+    pub fn apply_value(&self, value: f64) -> f64 {
+        value * self.factor
+    }
+
+    /// Apply the scale to a point, a point cloud, or a piece of geometry
+    pub fn apply<T: ScaleGeometry>(&self, geometry: &T) -> T {
+        geometry.scaled(self.factor)
+    }
+}
+
+/// Errors from parsing a quantity with a unit
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum UnitParseError {
+    /// The number before the unit could not be parsed
+    InvalidNumber(String),
+    /// No unit followed the number
+    MissingUnit {
+        /// The unit abbreviations recognized for the quantity
+        recognized: Vec<&'static str>
+    },
+    /// The unit was not recognized for the quantity
+    UnknownUnit {
+        /// The unit that was found
+        found: String,
+        /// The unit abbreviations recognized for the quantity
+        recognized: Vec<&'static str>
+    }
+}
+
+impl core::fmt::Display for UnitParseError {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        match self {
+            UnitParseError::InvalidNumber(number) => write!(f, "invalid number \"{number}\""),
+            UnitParseError::MissingUnit { recognized } => {
+                write!(f, "missing unit, expected one of {}", recognized.join(", "))
+            }
+            UnitParseError::UnknownUnit { found, recognized } => {
+                write!(f, "unknown unit \"{found}\", expected one of {}", recognized.join(", "))
+            }
+        }
+    }
+}
+
+impl core::error::Error for UnitParseError {}
+
+/// Split a string into a number and a recognized unit suffix
+/// whitespace around the number and between the number and the unit is ignored
+fn parse_with_unit<U: Copy>(s: &str, suffixes: &[(&'static str, U)]) -> Result<(f64, U), UnitParseError> {
+    let s = s.trim();
+    let mut candidates: Vec<&(&'static str, U)> = suffixes.iter().collect();
+    candidates.sort_by_key(|(suffix, _)| core::cmp::Reverse(suffix.len()));
+    let mut invalid_number = None;
+    for (suffix, unit) in candidates {
+        if let Some(number) = s.strip_suffix(suffix) {
+            let trimmed = number.trim_end();
+            if let Ok(value) = trimmed.parse::<f64>() {
+                return Ok((value, *unit));
+            }
+            // A known unit set apart by a space means the text before it was meant as the number
+            if invalid_number.is_none() && !trimmed.is_empty() && trimmed.len() < number.len() {
+                invalid_number = Some(trimmed);
+            }
+        }
+    }
+    if let Some(number) = invalid_number {
+        return Err(UnitParseError::InvalidNumber(number.to_string()));
+    }
+    let recognized = suffixes.iter().map(|(suffix, _)| *suffix).collect();
+    let split = s
+        .find(|c: char| !(c.is_ascii_digit() || "+-.eE".contains(c)))
+        .unwrap_or(s.len());
+    let (number, unit) = s.split_at(split);
+    if number.parse::<f64>().is_err() {
+        // Text that does not start like a number is reported up to the first space instead of as empty
+        let number = if number.is_empty() { s.split_whitespace().next().unwrap_or_default() } else { number };
+        return Err(UnitParseError::InvalidNumber(number.to_string()));
+    }
+    let unit = unit.trim();
+    if unit.is_empty() {
+        Err(UnitParseError::MissingUnit { recognized })
+    } else {
+        Err(UnitParseError::UnknownUnit { found: unit.to_string(), recognized })
+    }
+}
+
+/// Serialize a unit type as a string with its unit suffix
+/// use with `#[serde(with = "vec_utils::units::serde_with_suffix")]`
+/// unit types serialize as a bare number in the SI base unit by default,
+/// both forms are accepted when deserializing
+#[cfg(feature = "serde")]
+pub mod serde_with_suffix {
+    /// Serialize a unit type as a string such as "0.3048 m"
+    ///
+    /// # Errors
+    /// Returns an error if the serializer fails
+    pub fn serialize<T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: core::fmt::Display,
+        S: serde::Serializer
+    {
+        serializer.collect_str(value)
+    }
+
+    /// Deserialize a unit type from either a bare number in the SI base unit or a string with a unit suffix
+    ///
+    /// # Errors
+    /// Returns an error if the input is not a number or a string with a recognized unit
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+    where
+        T: serde::Deserialize<'de>,
+        D: serde::Deserializer<'de>
+    {
+        T::deserialize(deserializer)
+    }
+}
+
+/// Deserialize a unit type from a bare number in the SI base unit or a string with a unit suffix
+#[cfg(feature = "serde")]
+struct QuantityVisitor<T>(core::marker::PhantomData<T>);
+
+#[cfg(feature = "serde")]
+impl<T: Quantity + core::str::FromStr<Err = UnitParseError>> serde::de::Visitor<'_> for QuantityVisitor<T> {
+    type Value = T;
+
+    fn expecting(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(f, "a number or a string with a unit suffix")
+    }
+
+    fn visit_f64<E: serde::de::Error>(self, value: f64) -> Result<T, E> {
+        Ok(T::from_base(value))
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    fn visit_i64<E: serde::de::Error>(self, value: i64) -> Result<T, E> {
+        Ok(T::from_base(value as f64))
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    fn visit_u64<E: serde::de::Error>(self, value: u64) -> Result<T, E> {
+        Ok(T::from_base(value as f64))
+    }
+
+    fn visit_str<E: serde::de::Error>(self, value: &str) -> Result<T, E> {
+        value.parse().map_err(E::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{format, vec};
+
+    use super::*;
+    use crate::vec3d::Vec3d;
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!((actual - expected).abs() <= 1e-12 * expected.abs().max(1.0), "{actual} != {expected}");
+    }
+
+    #[test]
+    fn test_length_definitions() {
+        assert_close(Length::inches(1.0).as_millimeters(), 25.4);
+        assert_close(Length::feet(1.0).as_inches(), 12.0);
+        assert_close(Length::miles(1.0).as_feet(), 5280.0);
+        assert_close(Length::kilometers(1.0).as_meters(), 1000.0);
+    }
+
+    #[test]
+    fn test_length_round_trip() {
+        let value = 123.456;
+        assert_close(Length::meters(value).as_meters(), value);
+        assert_close(Length::millimeters(value).as_millimeters(), value);
+        assert_close(Length::kilometers(value).as_kilometers(), value);
+        assert_close(Length::inches(value).as_inches(), value);
+        assert_close(Length::feet(value).as_feet(), value);
+        assert_close(Length::miles(value).as_miles(), value);
+    }
+
+    #[test]
+    fn test_length_arithmetic() {
+        assert_close((Length::feet(1.0) + Length::inches(6.0)).as_inches(), 18.0);
+        assert_close((Length::meters(1.0) - Length::millimeters(1.0)).as_millimeters(), 999.0);
+        assert_close((Length::inches(2.0) * 3.0).as_inches(), 6.0);
+        assert_close((3.0 * Length::inches(2.0)).as_inches(), 6.0);
+        assert_close((Length::feet(3.0) / 3.0).as_feet(), 1.0);
+        assert_close((-Length::meters(2.0)).as_meters(), -2.0);
+    }
+
+    #[test]
+    fn test_length_ordering() {
+        assert!(Length::feet(1.0) > Length::inches(11.0));
+        assert!(Length::millimeters(999.0) < Length::meters(1.0));
+        assert_eq!(Length::millimeters(1000.0).partial_cmp(&Length::meters(1.0)), Some(core::cmp::Ordering::Equal));
+    }
+
+    #[test]
+    fn test_length_display() {
+        assert_eq!(Length::meters(1.5).to_string(), "1.5 m");
+        assert_eq!(format!("{:.2}", Length::millimeters(1.0)), "0.00 m");
+    }
+
+    #[test]
+    fn test_temperature_fixed_points() {
+        let freezing = Temperature::celsius(0.0);
+        assert_close(freezing.as_fahrenheit(), 32.0);
+        assert_close(freezing.as_kelvin(), 273.15);
+        assert_close(Temperature::fahrenheit(32.0).as_celsius(), 0.0);
+        assert_close(Temperature::kelvin(273.15).as_celsius(), 0.0);
+        assert_close(Temperature::fahrenheit(212.0).as_celsius(), 100.0);
+        assert_close(Temperature::kelvin(0.0).as_celsius(), -273.15);
+    }
+
+    #[test]
+    fn test_temperature_crossover() {
+        assert_close(Temperature::celsius(-40.0).as_fahrenheit(), -40.0);
+        assert_close(Temperature::fahrenheit(-40.0).as_celsius(), -40.0);
+    }
+
+    #[test]
+    fn test_temperature_delta_arithmetic() {
+        let warmed = Temperature::celsius(20.0) + TemperatureDelta::fahrenheit(9.0);
+        assert_close(warmed.as_celsius(), 25.0);
+        let cooled = Temperature::fahrenheit(50.0) - TemperatureDelta::kelvin(5.0);
+        assert_close(cooled.as_fahrenheit(), 41.0);
+        let difference = Temperature::celsius(30.0) - Temperature::fahrenheit(32.0);
+        assert_close(difference.as_celsius(), 30.0);
+        assert_close(difference.as_fahrenheit(), 54.0);
+        assert_close((TemperatureDelta::celsius(2.0) + TemperatureDelta::kelvin(3.0)).as_kelvin(), 5.0);
+        assert_close((TemperatureDelta::celsius(2.0) * 3.0).as_celsius(), 6.0);
+        assert_close((-TemperatureDelta::celsius(2.0) / 2.0).as_celsius(), -1.0);
+    }
+
+    #[test]
+    fn test_time_conversions() {
+        assert_close(Time::hours(1.0).as_minutes(), 60.0);
+        assert_close(Time::minutes(1.5).as_seconds(), 90.0);
+        assert_close(Time::milliseconds(250.0).as_seconds(), 0.25);
+        assert_close(Time::seconds(7200.0).as_hours(), 2.0);
+        assert_close((Time::minutes(1.0) + Time::seconds(30.0)).as_seconds(), 90.0);
+    }
+
+    #[test]
+    fn test_velocity_conversions() {
+        assert_close(Velocity::miles_per_hour(60.0).as_meters_per_second(), 26.8224);
+        assert_close(Velocity::kilometers_per_hour(36.0).as_meters_per_second(), 10.0);
+        assert_close(Velocity::feet_per_second(1.0).as_meters_per_second(), 0.3048);
+        assert_close(Velocity::meters_per_second(26.8224).as_miles_per_hour(), 60.0);
+        assert_close(Velocity::miles_per_hour(15.0).as_feet_per_second(), 22.0);
+    }
+
+    #[test]
+    fn test_acceleration_conversions() {
+        assert_close(Acceleration::standard_gravities(1.0).as_meters_per_second_squared(), 9.80665);
+        assert_close(Acceleration::meters_per_second_squared(19.6133).as_standard_gravities(), 2.0);
+    }
+
+    #[test]
+    fn test_dimensional_operators() {
+        let velocity = Length::kilometers(1.0) / Time::seconds(100.0);
+        assert_close(velocity.as_meters_per_second(), 10.0);
+        let acceleration = Velocity::meters_per_second(20.0) / Time::seconds(4.0);
+        assert_close(acceleration.as_meters_per_second_squared(), 5.0);
+        let distance = Velocity::miles_per_hour(60.0) * Time::hours(2.0);
+        assert_close(distance.as_miles(), 120.0);
+        assert_close((Time::hours(2.0) * Velocity::miles_per_hour(60.0)).as_miles(), 120.0);
+        let speed = Acceleration::standard_gravities(1.0) * Time::seconds(2.0);
+        assert_close(speed.as_meters_per_second(), 19.6133);
+        assert_close((Time::seconds(2.0) * Acceleration::meters_per_second_squared(3.0)).as_meters_per_second(), 6.0);
+    }
+
+    #[test]
+    fn test_force_conversions() {
+        assert_close(Force::pounds_force(1.0).as_newtons(), 4.448_221_615_260_5);
+        assert_close(Force::kilonewtons(1.0).as_newtons(), 1000.0);
+        assert_close(Force::newtons(4.448_221_615_260_5).as_pounds_force(), 1.0);
+    }
+
+    #[test]
+    fn test_pressure_conversions() {
+        assert_close(Pressure::psi(1.0).as_pascals(), 6_894.757_293_168_361);
+        assert_close(Pressure::atmospheres(1.0).as_pascals(), 101_325.0);
+        assert_close(Pressure::atmospheres(1.0).as_psi(), 14.695_948_775_513_45);
+        assert_close(Pressure::bars(1.0).as_kilopascals(), 100.0);
+        assert_close(Pressure::megapascals(1.0).as_bars(), 10.0);
+        assert_close(Pressure::kilopascals(101.325).as_atmospheres(), 1.0);
+        assert_close((Pressure::bars(1.0) - Pressure::kilopascals(50.0)).as_kilopascals(), 50.0);
+    }
+
+    #[test]
+    fn test_torque_conversions() {
+        assert_close(Torque::pound_force_feet(1.0).as_newton_meters(), 1.355_817_948_331_400_4);
+        assert_close(Torque::pound_force_inches(1.0).as_newton_meters(), 0.112_984_829_027_616_7);
+        assert_close(Torque::pound_force_feet(1.0).as_pound_force_inches(), 12.0);
+    }
+
+    #[test]
+    fn test_force_times_length() {
+        let torque = Force::pounds_force(10.0) * Length::feet(2.0);
+        assert_close(torque.as_pound_force_feet(), 20.0);
+        assert_close((Length::meters(0.5) * Force::newtons(8.0)).as_newton_meters(), 4.0);
+    }
+
+    #[test]
+    fn test_parse_every_suffix() {
+        for unit in LengthUnit::ALL {
+            let parsed: Length = format!("2.5 {}", unit.abbreviation()).parse().unwrap();
+            assert_close(parsed.as_unit(unit), 2.5);
+        }
+        for unit in TemperatureUnit::ALL {
+            let parsed: Temperature = format!("2.5 {}", unit.abbreviation()).parse().unwrap();
+            assert_close(parsed.as_unit(unit), 2.5);
+            let parsed: TemperatureDelta = format!("2.5 {}", unit.abbreviation()).parse().unwrap();
+            assert_close(parsed.as_unit(unit), 2.5);
+        }
+        for unit in TimeUnit::ALL {
+            let parsed: Time = format!("2.5 {}", unit.abbreviation()).parse().unwrap();
+            assert_close(parsed.as_unit(unit), 2.5);
+        }
+        for unit in VelocityUnit::ALL {
+            let parsed: Velocity = format!("2.5 {}", unit.abbreviation()).parse().unwrap();
+            assert_close(parsed.as_unit(unit), 2.5);
+        }
+        for unit in AccelerationUnit::ALL {
+            let parsed: Acceleration = format!("2.5 {}", unit.abbreviation()).parse().unwrap();
+            assert_close(parsed.as_unit(unit), 2.5);
+        }
+        for unit in ForceUnit::ALL {
+            let parsed: Force = format!("2.5 {}", unit.abbreviation()).parse().unwrap();
+            assert_close(parsed.as_unit(unit), 2.5);
+        }
+        for unit in PressureUnit::ALL {
+            let parsed: Pressure = format!("2.5 {}", unit.abbreviation()).parse().unwrap();
+            assert_close(parsed.as_unit(unit), 2.5);
+        }
+        for unit in TorqueUnit::ALL {
+            let parsed: Torque = format!("2.5 {}", unit.abbreviation()).parse().unwrap();
+            assert_close(parsed.as_unit(unit), 2.5);
+        }
+    }
+
+    #[test]
+    fn test_parse_aliases() {
+        assert_close("20 degC".parse::<Temperature>().unwrap().as_celsius(), 20.0);
+        assert_close("68 degF".parse::<Temperature>().unwrap().as_celsius(), 20.0);
+        assert_close("9.8 m/s^2".parse::<Acceleration>().unwrap().as_meters_per_second_squared(), 9.8);
+        assert_close("3 Nm".parse::<Torque>().unwrap().as_newton_meters(), 3.0);
+        assert_close("3 lbf-ft".parse::<Torque>().unwrap().as_pound_force_feet(), 3.0);
+    }
+
+    #[test]
+    fn test_parse_whitespace() {
+        assert_close("12.7 mm".parse::<Length>().unwrap().as_millimeters(), 12.7);
+        assert_close("12.7mm".parse::<Length>().unwrap().as_millimeters(), 12.7);
+        assert_close("  3   ft \t".parse::<Length>().unwrap().as_feet(), 3.0);
+        assert_close("-1e3 m".parse::<Length>().unwrap().as_meters(), -1000.0);
+        assert_close("5 ms".parse::<Time>().unwrap().as_milliseconds(), 5.0);
+        assert_close("5 min".parse::<Time>().unwrap().as_minutes(), 5.0);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            "3 furlongs".parse::<Length>(),
+            Err(UnitParseError::UnknownUnit {
+                found: "furlongs".to_string(),
+                recognized: vec!["m", "mm", "km", "in", "ft", "mi"]
+            })
+        );
+        assert_eq!(
+            "3".parse::<Length>(),
+            Err(UnitParseError::MissingUnit { recognized: vec!["m", "mm", "km", "in", "ft", "mi"] })
+        );
+        assert_eq!("three m".parse::<Length>(), Err(UnitParseError::InvalidNumber("three".to_string())));
+        assert_eq!("  one point five  km ".parse::<Length>(), Err(UnitParseError::InvalidNumber("one point five".to_string())));
+        assert_eq!("threem".parse::<Length>(), Err(UnitParseError::InvalidNumber("threem".to_string())));
+        assert_eq!(
+            "5 spam".parse::<Length>(),
+            Err(UnitParseError::UnknownUnit {
+                found: "spam".to_string(),
+                recognized: vec!["m", "mm", "km", "in", "ft", "mi"]
+            })
+        );
+        assert_eq!("three m".parse::<Length>().unwrap_err().to_string(), "invalid number \"three\"");
+        assert_eq!("1.2.3 m".parse::<Length>(), Err(UnitParseError::InvalidNumber("1.2.3".to_string())));
+        assert_eq!(
+            "3 m".parse::<Pressure>().unwrap_err().to_string(),
+            "unknown unit \"m\", expected one of Pa, kPa, MPa, bar, psi, atm"
+        );
+    }
+
+    #[test]
+    fn test_format_in_round_trip() {
+        let length = Length::inches(0.5);
+        let formatted = length.format_in(LengthUnit::Millimeters, 3);
+        assert_eq!(formatted, "12.700 mm");
+        assert_close(formatted.parse::<Length>().unwrap().as_inches(), 0.5);
+        let temperature = Temperature::celsius(100.0);
+        let formatted = temperature.format_in(TemperatureUnit::Fahrenheit, 1);
+        assert_eq!(formatted, "212.0 °F");
+        assert_close(formatted.parse::<Temperature>().unwrap().as_celsius(), 100.0);
+        let torque = Torque::newton_meters(1.0);
+        let formatted = torque.format_in(TorqueUnit::PoundForceInches, 15);
+        assert_close(formatted.parse::<Torque>().unwrap().as_newton_meters(), 1.0);
+    }
+
+    #[test]
+    fn test_display_round_trip() {
+        let velocity = Velocity::miles_per_hour(60.0);
+        assert_eq!(velocity.to_string().parse::<Velocity>(), Ok(velocity));
+        let pressure = Pressure::psi(30.0);
+        assert_eq!(pressure.to_string().parse::<Pressure>(), Ok(pressure));
+        let acceleration = Acceleration::standard_gravities(1.0);
+        assert_eq!(acceleration.to_string().parse::<Acceleration>(), Ok(acceleration));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_bare_number() {
+        let length = Length::millimeters(250.0);
+        assert_eq!(serde_json::to_string(&length).unwrap(), "0.25");
+        assert_eq!(serde_json::from_str::<Length>("0.25").unwrap(), length);
+        assert_eq!(serde_json::from_str::<Length>("2").unwrap(), Length::meters(2.0));
+        let temperature = Temperature::kelvin(300.0);
+        assert_eq!(serde_json::from_str::<Temperature>(&serde_json::to_string(&temperature).unwrap()).unwrap(), temperature);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_with_suffix() {
+        let length = Length::feet(2.0);
+        let value = serde_with_suffix::serialize(&length, serde_json::value::Serializer).unwrap();
+        assert_eq!(value, serde_json::Value::String(length.to_string()));
+        let parsed: Length = serde_with_suffix::deserialize(value).unwrap();
+        assert_eq!(parsed, length);
+        let parsed: Length = serde_json::from_str("\"12.7 mm\"").unwrap();
+        assert_close(parsed.as_millimeters(), 12.7);
+        let pressure: Pressure = serde_with_suffix::deserialize(serde_json::json!("30 psi")).unwrap();
+        assert_close(pressure.as_psi(), 30.0);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_unknown_unit() {
+        let error = serde_json::from_str::<Length>("\"3 cubits\"").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "unknown unit \"cubits\", expected one of m, mm, km, in, ft, mi at line 1 column 10"
+        );
+        assert!(serde_json::from_str::<Length>("true").is_err());
+    }
+
+    #[test]
+    fn test_angular_velocity_conversions() {
+        let rpm = AngularVelocity::revolutions_per_minute(60.0);
+        assert_close(rpm.as_radians_per_second(), TAU);
+        assert_close(rpm.as_degrees_per_second(), 360.0);
+        assert_close(AngularVelocity::from(Frequency::hertz(1.0)).as_revolutions_per_minute(), 60.0);
+        assert_close(Frequency::kilohertz(1.0).as_hertz(), 1000.0);
+        assert_close("90 °/s".parse::<AngularVelocity>().unwrap().as_degrees_per_second(), 90.0);
+        assert_close("50 Hz".parse::<Frequency>().unwrap().as_hertz(), 50.0);
+    }
+
+    #[test]
+    fn test_angular_integration() {
+        let angle = AngularVelocity::degrees_per_second(90.0) * Time::seconds(2.0);
+        assert_close(f64::from(angle), core::f64::consts::PI);
+        assert_close(f64::from(Time::seconds(2.0) * AngularVelocity::radians_per_second(1.5)), 3.0);
+        let rate = AngleRadians::pi() / Time::seconds(0.5);
+        assert_close(rate.as_radians_per_second(), TAU);
+    }
+
+    define_unit! {
+        /// An area, stored internally in square meters
+        linear Area("area", square_meters, "m²");
+        /// Units an area can be expressed in
+        enum AreaUnit {
+            SquareMeters => square_meters, as_square_meters, "square meters", ["m²", "m^2"], 1.0;
+            Hectares => hectares, as_hectares, "hectares", ["ha"], 10_000.0;
+        }
+    }
+
+    #[test]
+    fn test_define_unit() {
+        let area = Area::hectares(1.5);
+        assert_close(area.as_square_meters(), 15_000.0);
+        assert_close(Area::new(2.0, AreaUnit::Hectares).as_unit(AreaUnit::SquareMeters), 20_000.0);
+        assert_close((area + Area::square_meters(5000.0)).as_hectares(), 2.0);
+        assert_close((2.0 * -area / 3.0 - Area::hectares(1.0)).as_hectares(), -2.0);
+        assert_eq!(AreaUnit::ALL, [AreaUnit::SquareMeters, AreaUnit::Hectares]);
+        assert_eq!(AreaUnit::Hectares.abbreviation(), "ha");
+        assert_eq!(area.to_string(), "15000 m²");
+        assert_eq!(area.format_in(AreaUnit::Hectares, 2), "1.50 ha");
+        assert_close("30 m^2".parse::<Area>().unwrap().as_square_meters(), 30.0);
+        assert!(matches!("3 acres".parse::<Area>(), Err(UnitParseError::UnknownUnit { .. })));
+    }
+
+    #[test]
+    fn test_quantity_trait() {
+        assert_close(convert::<Length>(1.0, LengthUnit::Feet, LengthUnit::Inches), 12.0);
+        assert_close(convert::<Temperature>(100.0, TemperatureUnit::Celsius, TemperatureUnit::Fahrenheit), 212.0);
+        assert_close(Area::from_base(10_000.0).as_hectares(), 1.0);
+        assert_close(Pressure::atmospheres(1.0).base_value(), 101_325.0);
+    }
+
+    #[test]
+    fn test_length_scale() {
+        let scale = LengthScale::from_to(LengthUnit::Millimeters, LengthUnit::Meters);
+        assert_close(scale.factor(), 0.001);
+        assert_close(scale.inverse().apply_value(1.5), 1500.0);
+        assert_close(LengthScale::from_to(LengthUnit::Feet, LengthUnit::Inches).apply_value(2.0), 24.0);
+        let points = vec![Vec3d::new(1000.0, 0.0, -500.0), Vec3d::new(0.0, 250.0, 0.0)];
+        let scaled = scale.apply(&points);
+        assert!(scaled[0].distance_to(&Vec3d::new(1.0, 0.0, -0.5)) < 1e-12);
+        assert!(scaled[1].distance_to(&Vec3d::new(0.0, 0.25, 0.0)) < 1e-12);
+    }
+}
@@ -1,8 +1,19 @@
+use alloc::vec;
+use alloc::vec::Vec;
+
 use crate::angle::AngleRadians;
+use crate::error::Error;
+#[cfg(not(any(feature = "std", test)))]
+use crate::math::Float;
+use crate::parse::{parse_vector, ParseError};
 use crate::quat::Quat;
 
+/// Operations on slices of Vec3d
+pub mod batch;
+
 /// A 3D vector
-#[derive(Debug, Copy, Clone, PartialEq)]
+#[derive(Debug, Copy, Clone, PartialEq, Default)]
+#[repr(C)]
 pub struct Vec3d {
     /// The x component of the vector
     pub x: f64,
@@ -14,12 +25,12 @@ pub struct Vec3d {
 
 impl Vec3d {
     /// Create a new Vec3d
-    pub fn new(x: f64, y: f64, z: f64) -> Vec3d {
+    pub const fn new(x: f64, y: f64, z: f64) -> Vec3d {
         Vec3d { x, y, z }
     }
 
     /// Create a new Vec3d from a start point to an end point
-    pub fn new_from_to(from: &Vec3d, to: &Vec3d) -> Vec3d {
+    pub const fn new_from_to(from: &Vec3d, to: &Vec3d) -> Vec3d {
         Vec3d {
             x: to.x - from.x,
             y: to.y - from.y,
@@ -28,22 +39,22 @@ impl Vec3d {
     }
 
     /// Create a new Vec3d with all components set to 0
-    pub fn zero() -> Vec3d {
+    pub const fn zero() -> Vec3d {
         Vec3d { x: 0.0, y: 0.0, z: 0.0 }
     }
 
     /// Create a new Vec3d of the i unit vector
-    pub fn i() -> Vec3d {
+    pub const fn i() -> Vec3d {
         Vec3d { x: 1.0, y: 0.0, z: 0.0 }
     }
 
     /// Create a new Vec3d of the j unit vector
-    pub fn j() -> Vec3d {
+    pub const fn j() -> Vec3d {
         Vec3d { x: 0.0, y: 1.0, z: 0.0 }
     }
 
     /// Create a new Vec3d of the k unit vector
-    pub fn k() -> Vec3d {
+    pub const fn k() -> Vec3d {
         Vec3d { x: 0.0, y: 0.0, z: 1.0 }
     }
 
@@ -78,14 +89,45 @@ impl Vec3d {
     /// Create a new Vec3d from a slice of f64s
     /// the slice should have a length of 3
     /// any additional elements will be ignored
+    ///
+    /// # Panics
+    /// Panics if the slice has fewer than 3 elements
+    #[deprecated(note = "use `Vec3d::try_from_slice` or `Vec3d::try_from` instead")]
     pub fn from_slice(v: &[f64]) -> Vec3d {
-        Vec3d {
-            x: v[0],
-            y: v[1],
-            z: v[2]
+        Vec3d::new(v[0], v[1], v[2])
+    }
+
+    /// Create a new Vec3d from a slice of exactly 3 f64s without panicking
+    /// # Errors
+    /// returns `Error::TooFewElements` if the slice has fewer than 3 elements
+    /// or `Error::TooManyElements` if it has more
+    pub fn try_from_slice(v: &[f64]) -> Result<Vec3d, Error> {
+        match v {
+            [x, y, z] => Ok(Vec3d::new(*x, *y, *z)),
+            [_, _, _, ..] => Err(Error::TooManyElements { expected: 3 }),
+            _ => Err(Error::TooFewElements {
+                expected: 3,
+                found: v.len()
+            })
         }
     }
 
+    /// Create a new Vec3d from exactly 3 f64s
+    /// # Errors
+    /// returns `Error::TooFewElements` if there are fewer than 3 items
+    /// or `Error::TooManyElements` if there are more
+    pub fn from_iter_exact(iter: impl IntoIterator<Item = f64>) -> Result<Vec3d, Error> {
+        let mut iter = iter.into_iter();
+        let mut values = [0.0; 3];
+        for (found, value) in values.iter_mut().enumerate() {
+            *value = iter.next().ok_or(Error::TooFewElements { expected: 3, found })?;
+        }
+        if iter.next().is_some() {
+            return Err(Error::TooManyElements { expected: 3 });
+        }
+        Ok(Vec3d::from(values))
+    }
+
     /// Convert the Vec3d to a Vec of f64 with length 3
     pub fn to_vec(&self) -> Vec<f64> {
         vec![self.x, self.y, self.z]
@@ -111,8 +153,29 @@ impl Vec3d {
     }
 
     /// Check if the Vec3d is a unit vector
+    /// the magnitude only has to be within 1e-12 of 1 so vectors from `normalize` always pass
     pub fn is_unit(&self) -> bool {
-        (self.magnitude() - 1.0).abs() < f64::EPSILON
+        self.is_unit_with_tolerance(1e-12)
+    }
+
+    /// Check if the magnitude of the Vec3d is within a tolerance of 1
+    pub fn is_unit_with_tolerance(&self, tolerance: f64) -> bool {
+        (self.magnitude() - 1.0).abs() <= tolerance
+    }
+
+    /// Check if all components of the Vec3d are finite
+    /// i.e. not NaN or infinite
+    pub fn is_finite(&self) -> bool {
+        self.x.is_finite() && self.y.is_finite() && self.z.is_finite()
+    }
+
+    /// Compare two Vec3d by x, then y, then z using a total order
+    /// unlike `partial_cmp` every value including NaN has a place in the order, see `f64::total_cmp`
+    pub fn total_cmp(&self, other: &Vec3d) -> core::cmp::Ordering {
+        self.x
+            .total_cmp(&other.x)
+            .then(self.y.total_cmp(&other.y))
+            .then(self.z.total_cmp(&other.z))
     }
 
     /// Return a new Vec3d of the normalized vector
@@ -125,6 +188,16 @@ impl Vec3d {
         }
     }
 
+    /// Normalize the Vec3d without producing NaN for a zero vector
+    /// # Errors
+    /// returns `Error::ZeroVector` if the Vec3d has a length of zero
+    pub fn try_normalize(&self) -> Result<Vec3d, Error> {
+        if self.magnitude() == 0.0 {
+            return Err(Error::ZeroVector);
+        }
+        Ok(self.normalize())
+    }
+
     /// Calculate the angle between two Vec3d's
     /// the result is in radians
     pub fn angle_to(&self, other: &Vec3d) -> AngleRadians {
@@ -166,36 +239,68 @@ impl Vec3d {
         let t = (line_r - line_q).dot(&(line_q - self)) / (line_r - line_q).dot(&(line_r - line_q));
         line_q - t * (line_r - line_q)
     }
-}
 
-impl std::ops::Add for Vec3d {
-    type Output = Vec3d;
-
-    /// Add two Vec3d's together component-wise
-    fn add(self, other: Vec3d) -> Vec3d {
-        &self + &other
+    /// Rotate the vector about an axis through the origin with Rodrigues' rotation formula
+    /// the axis does not have to be a unit vector, a zero axis leaves the vector unchanged
+    /// the angle is positive for a counter-clockwise rotation, the same as `Quat::from_axis_angle` with `Quat::rotate`
+    pub fn rotated_about(&self, axis: &Vec3d, angle: impl Into<AngleRadians>) -> Vec3d {
+        let Ok(axis) = axis.try_normalize() else {
+            return *self;
+        };
+        let angle = angle.into();
+        let (sin, cos) = (angle.sin(), angle.cos());
+        self * cos + axis.cross(self) * sin + axis * (axis.dot(self) * (1.0 - cos))
     }
-}
 
-impl std::ops::Add<&Vec3d> for Vec3d {
-    type Output = Vec3d;
+    /// Get an arbitrary unit vector perpendicular to a unit vector
+    /// this is the x axis projected onto the plane normal to the vector, or the y axis when the vector is close to the x axis
+    pub(crate) fn any_perpendicular(&self) -> Vec3d {
+        let reference = if self.x.abs() < 0.9 { Vec3d::i() } else { Vec3d::j() };
+        reference.project_onto_plane(self).normalize()
+    }
 
-    /// Add two Vec3d's together component-wise
-    fn add(self, other: &Vec3d) -> Vec3d {
-        &self + other
+    /// Get the nearest direction inside a cone about an axis, keeping the length of the vector
+    /// vectors already within the half angle of the axis are returned unchanged,
+    /// others are turned towards the axis until they lie on the surface of the cone
+    /// a vector pointing exactly away from the axis is turned towards an arbitrary side
+    pub fn constrained_to_cone(&self, axis: &Vec3d, half_angle: impl Into<AngleRadians>) -> Vec3d {
+        let half_angle = half_angle.into();
+        let (Ok(axis), Ok(direction)) = (axis.try_normalize(), self.try_normalize()) else {
+            return *self;
+        };
+        if direction.angle_to(&axis) <= half_angle {
+            return *self;
+        }
+        let side = direction.project_onto_plane(&axis).try_normalize().unwrap_or_else(|_| axis.any_perpendicular());
+        (axis * half_angle.cos() + side * half_angle.sin()) * self.magnitude()
     }
-}
 
-impl std::ops::Add<Vec3d> for &Vec3d {
-    type Output = Vec3d;
+    /// Turn the vector towards the direction of a target by at most an angle, keeping the length of the vector
+    /// once the target is within the angle the result points exactly along it, so repeated steps settle on the target
+    /// if they point in opposite directions the vector is turned towards an arbitrary but fixed side
+    /// a zero vector or target gives the vector unchanged
+    pub fn rotate_towards(&self, target: &Vec3d, max_angle: impl Into<AngleRadians>) -> Vec3d {
+        let max_angle = f64::from(max_angle.into()).abs();
+        let (Ok(direction), Ok(target)) = (self.try_normalize(), target.try_normalize()) else {
+            return *self;
+        };
+        if f64::from(direction.angle_to(&target)) <= max_angle {
+            return target * self.magnitude();
+        }
+        let side = target
+            .project_onto_plane(&direction)
+            .try_normalize()
+            .unwrap_or_else(|_| direction.any_perpendicular());
+        (direction * max_angle.cos() + side * max_angle.sin()) * self.magnitude()
+    }
 
-    /// Add two Vec3d's together component-wise
-    fn add(self, other: Vec3d) -> Vec3d {
-        self + &other
+    /// Rotate the point about an axis through a pivot point, see `rotated_about`
+    pub fn rotate_about_point(&self, pivot: &Vec3d, axis: &Vec3d, angle: impl Into<AngleRadians>) -> Vec3d {
+        pivot + (self - pivot).rotated_about(axis, angle)
     }
 }
 
-impl std::ops::Add<&Vec3d> for &Vec3d {
+impl core::ops::Add<&Vec3d> for &Vec3d {
     type Output = Vec3d;
 
     /// Add two Vec3d's together component-wise
@@ -208,7 +313,7 @@ impl std::ops::Add<&Vec3d> for &Vec3d {
     }
 }
 
-impl std::ops::Sub<&Vec3d> for &Vec3d {
+impl core::ops::Sub<&Vec3d> for &Vec3d {
     type Output = Vec3d;
 
     /// Subtract one Vec3d from another component-wise
@@ -221,34 +326,26 @@ impl std::ops::Sub<&Vec3d> for &Vec3d {
     }
 }
 
-impl std::ops::Sub for Vec3d {
-    type Output = Vec3d;
-
-    /// Subtract one Vec3d from another component-wise
-    fn sub(self, other: Vec3d) -> Vec3d {
-        &self - &other
-    }
+impl_ref_ops! {
+    Add, add for Vec3d, Vec3d => Vec3d;
+    Sub, sub for Vec3d, Vec3d => Vec3d
 }
 
-impl std::ops::Sub<&Vec3d> for Vec3d {
-    type Output = Vec3d;
-
-    /// Subtract one Vec3d from another component-wise
-    fn sub(self, other: &Vec3d) -> Vec3d {
-        &self - other
+impl core::iter::Sum for Vec3d {
+    /// Sum Vec3d's component-wise, an empty iterator sums to zero
+    fn sum<I: Iterator<Item = Vec3d>>(iter: I) -> Vec3d {
+        iter.fold(Vec3d::zero(), |sum, v| sum + v)
     }
 }
 
-impl std::ops::Sub<Vec3d> for &Vec3d {
-    type Output = Vec3d;
-
-    /// Subtract one Vec3d from another component-wise
-    fn sub(self, other: Vec3d) -> Vec3d {
-        self - &other
+impl<'a> core::iter::Sum<&'a Vec3d> for Vec3d {
+    /// Sum Vec3d's component-wise, an empty iterator sums to zero
+    fn sum<I: Iterator<Item = &'a Vec3d>>(iter: I) -> Vec3d {
+        iter.fold(Vec3d::zero(), |sum, v| sum + v)
     }
 }
 
-impl std::ops::Mul<f64> for Vec3d {
+impl core::ops::Mul<f64> for Vec3d {
     type Output = Vec3d;
 
     /// Multiply a Vec3d by a scalar
@@ -257,7 +354,7 @@ impl std::ops::Mul<f64> for Vec3d {
     }
 }
 
-impl std::ops::Mul<f64> for &Vec3d {
+impl core::ops::Mul<f64> for &Vec3d {
     type Output = Vec3d;
 
     /// Multiply a Vec3d by a scalar
@@ -270,16 +367,18 @@ impl std::ops::Mul<f64> for &Vec3d {
     }
 }
 
-impl std::ops::Mul<Vec3d> for f64 {
+impl_commutative_op!(Mul, mul for f64, Vec3d);
+
+impl core::ops::Mul<&Vec3d> for f64 {
     type Output = Vec3d;
 
     /// Multiply a Vec3d by a scalar
-    fn mul(self, other: Vec3d) -> Vec3d {
+    fn mul(self, other: &Vec3d) -> Vec3d {
         other * self
     }
 }
 
-impl std::ops::Div<f64> for Vec3d {
+impl core::ops::Div<f64> for Vec3d {
     type Output = Vec3d;
 
     /// Divide a Vec3d by a scalar
@@ -292,7 +391,16 @@ impl std::ops::Div<f64> for Vec3d {
     }
 }
 
-impl std::ops::Neg for Vec3d {
+impl core::ops::Div<f64> for &Vec3d {
+    type Output = Vec3d;
+
+    /// Divide a Vec3d by a scalar
+    fn div(self, other: f64) -> Vec3d {
+        *self / other
+    }
+}
+
+impl core::ops::Neg for Vec3d {
     type Output = Vec3d;
 
     fn neg(self) -> Vec3d {
@@ -304,7 +412,15 @@ impl std::ops::Neg for Vec3d {
     }
 }
 
-impl std::ops::Index<usize> for Vec3d {
+impl core::ops::Neg for &Vec3d {
+    type Output = Vec3d;
+
+    fn neg(self) -> Vec3d {
+        -*self
+    }
+}
+
+impl core::ops::Index<usize> for Vec3d {
     type Output = f64;
 
     /// Index into a Vec3d
@@ -320,15 +436,185 @@ impl std::ops::Index<usize> for Vec3d {
     }
 }
 
-impl std::fmt::Display for Vec3d {
+impl core::fmt::Display for Vec3d {
     /// Format the Vec3d as a string
-    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        write!(f, "({}, {}, {})", self.x, self.y, self.z)
+    /// the precision is applied to each component
+    /// the alternate form `{:#}` gives the magnitude and unit direction instead, with 3 decimal places unless a precision is given
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        if f.alternate() {
+            let precision = f.precision().unwrap_or(3);
+            let magnitude = self.magnitude();
+            return if magnitude == 0.0 {
+                write!(f, "{magnitude:.precision$} (zero vector)")
+            } else {
+                write!(f, "{magnitude:.precision$} along {:.precision$}", self / magnitude)
+            };
+        }
+        if let Some(precision) = f.precision() {
+            write!(f, "({:.3$}, {:.3$}, {:.3$})", self.x, self.y, self.z, precision)
+        } else {
+            write!(f, "({}, {}, {})", self.x, self.y, self.z)
+        }
+    }
+}
+
+impl core::str::FromStr for Vec3d {
+    type Err = ParseError;
+
+    /// Parse a Vec3d written as "(x, y, z)", the same as its `Display` output
+    /// so formatting with `{}` and parsing gives back exactly the same vector
+    fn from_str(s: &str) -> Result<Vec3d, ParseError> {
+        parse_vector(s)
+    }
+}
+
+/// A Vec3d that is totally ordered and hashable so it can be used as a key in maps and sets
+/// two vectors are equal only if their components have identical bits, so 0.0 and -0.0 are different keys
+#[derive(Debug, Copy, Clone, Default)]
+pub struct OrderedVec3d(pub Vec3d);
+
+impl PartialEq for OrderedVec3d {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == core::cmp::Ordering::Equal
+    }
+}
+
+impl Eq for OrderedVec3d {}
+
+impl PartialOrd for OrderedVec3d {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for OrderedVec3d {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        self.0.total_cmp(&other.0)
+    }
+}
+
+impl core::hash::Hash for OrderedVec3d {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        self.0.x.to_bits().hash(state);
+        self.0.y.to_bits().hash(state);
+        self.0.z.to_bits().hash(state);
     }
 }
 
+impl From<Vec3d> for OrderedVec3d {
+    fn from(v: Vec3d) -> OrderedVec3d {
+        OrderedVec3d(v)
+    }
+}
+
+impl From<&Vec3d> for Vec3d {
+    fn from(v: &Vec3d) -> Vec3d {
+        *v
+    }
+}
+
+impl From<(f64, f64, f64)> for Vec3d {
+    fn from((x, y, z): (f64, f64, f64)) -> Vec3d {
+        Vec3d::new(x, y, z)
+    }
+}
+
+impl From<[f64; 3]> for Vec3d {
+    fn from(v: [f64; 3]) -> Vec3d {
+        Vec3d::new(v[0], v[1], v[2])
+    }
+}
+
+impl TryFrom<&[f64]> for Vec3d {
+    type Error = Error;
+
+    /// Create a new Vec3d from a slice of exactly 3 f64s
+    fn try_from(v: &[f64]) -> Result<Vec3d, Error> {
+        Vec3d::try_from_slice(v)
+    }
+}
+
+impl From<OrderedVec3d> for Vec3d {
+    fn from(v: OrderedVec3d) -> Vec3d {
+        v.0
+    }
+}
+
+#[cfg(feature = "glam")]
+impl From<glam::DVec3> for Vec3d {
+    fn from(v: glam::DVec3) -> Vec3d {
+        Vec3d::new(v.x, v.y, v.z)
+    }
+}
+
+#[cfg(feature = "glam")]
+impl From<Vec3d> for glam::DVec3 {
+    fn from(v: Vec3d) -> glam::DVec3 {
+        glam::DVec3::new(v.x, v.y, v.z)
+    }
+}
+
+#[cfg(feature = "glam")]
+impl From<glam::Vec3> for Vec3d {
+    fn from(v: glam::Vec3) -> Vec3d {
+        Vec3d::new(f64::from(v.x), f64::from(v.y), f64::from(v.z))
+    }
+}
+
+#[cfg(feature = "glam")]
+impl From<Vec3d> for glam::Vec3 {
+    /// Convert to a single precision glam vector
+    /// this is lossy, each component is rounded to the nearest f32
+    #[allow(clippy::cast_possible_truncation)]
+    fn from(v: Vec3d) -> glam::Vec3 {
+        glam::Vec3::new(v.x as f32, v.y as f32, v.z as f32)
+    }
+}
+
+#[cfg(feature = "mint")]
+impl From<mint::Point3<f64>> for Vec3d {
+    fn from(v: mint::Point3<f64>) -> Vec3d {
+        Vec3d::new(v.x, v.y, v.z)
+    }
+}
+
+#[cfg(feature = "mint")]
+impl From<Vec3d> for mint::Point3<f64> {
+    fn from(v: Vec3d) -> mint::Point3<f64> {
+        mint::Point3 { x: v.x, y: v.y, z: v.z }
+    }
+}
+
+#[cfg(feature = "mint")]
+impl From<mint::Vector3<f64>> for Vec3d {
+    fn from(v: mint::Vector3<f64>) -> Vec3d {
+        Vec3d::new(v.x, v.y, v.z)
+    }
+}
+
+#[cfg(feature = "mint")]
+impl From<Vec3d> for mint::Vector3<f64> {
+    fn from(v: Vec3d) -> mint::Vector3<f64> {
+        mint::Vector3 { x: v.x, y: v.y, z: v.z }
+    }
+}
+
+// SAFETY: Vec3d is repr(C) with only f64 fields, so it has no padding and every bit pattern is valid
+#[cfg(feature = "bytemuck")]
+unsafe impl bytemuck::Zeroable for Vec3d {}
+
+#[cfg(feature = "bytemuck")]
+unsafe impl bytemuck::Pod for Vec3d {}
+
+#[cfg(feature = "bytemuck")]
+const _: () = assert!(core::mem::size_of::<Vec3d>() == 3 * core::mem::size_of::<f64>());
+#[cfg(feature = "bytemuck")]
+const _: () = assert!(core::mem::align_of::<Vec3d>() == core::mem::align_of::<f64>());
+
 #[cfg(test)]
 mod tests {
+    use alloc::string::ToString;
+
     use super::*;
 
     #[test]
@@ -410,6 +696,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(deprecated)]
     fn test_from_slice() {
         let v = Vec3d::from_slice(&vec![1.0, 2.0, 3.0]);
         assert_eq!(v.x, 1.0);
@@ -422,6 +709,45 @@ mod tests {
         assert_eq!(v.z, 3.0);
     }
 
+    #[test]
+    fn test_try_from_slice() {
+        assert_eq!(Vec3d::try_from_slice(&[1.0, 2.0, 3.0]), Ok(Vec3d::new(1.0, 2.0, 3.0)));
+        assert_eq!(Vec3d::try_from_slice(&[1.0, 2.0, 3.0, 4.0]), Err(Error::TooManyElements { expected: 3 }));
+        assert_eq!(
+            Vec3d::try_from_slice(&[1.0]),
+            Err(Error::TooFewElements { expected: 3, found: 1 })
+        );
+    }
+
+    #[test]
+    fn test_try_from() {
+        let empty: &[f64] = &[];
+        assert_eq!(Vec3d::try_from(empty), Err(Error::TooFewElements { expected: 3, found: 0 }));
+        assert_eq!(
+            Vec3d::try_from([1.0, 2.0].as_slice()),
+            Err(Error::TooFewElements { expected: 3, found: 2 })
+        );
+        assert_eq!(Vec3d::try_from([1.0, 2.0, 3.0].as_slice()), Ok(Vec3d::new(1.0, 2.0, 3.0)));
+        assert_eq!(
+            Vec3d::try_from([1.0, 2.0, 3.0, 4.0].as_slice()),
+            Err(Error::TooManyElements { expected: 3 })
+        );
+        assert_eq!(Vec3d::from([1.0, 2.0, 3.0]), Vec3d::new(1.0, 2.0, 3.0));
+    }
+
+    #[test]
+    fn test_from_iter_exact() {
+        assert_eq!(Vec3d::from_iter_exact([]), Err(Error::TooFewElements { expected: 3, found: 0 }));
+        assert_eq!(Vec3d::from_iter_exact([1.0, 2.0]), Err(Error::TooFewElements { expected: 3, found: 2 }));
+        assert_eq!(Vec3d::from_iter_exact([1.0, 2.0, 3.0]), Ok(Vec3d::new(1.0, 2.0, 3.0)));
+        assert_eq!(Vec3d::from_iter_exact([1.0, 2.0, 3.0, 4.0]), Err(Error::TooManyElements { expected: 3 }));
+        assert_eq!(
+            Vec3d::from_iter_exact((1..=3).map(f64::from)),
+            Ok(Vec3d::new(1.0, 2.0, 3.0))
+        );
+        assert_eq!(Vec3d::from_iter_exact(core::iter::repeat(1.0)), Err(Error::TooManyElements { expected: 3 }));
+    }
+
     #[test]
     fn test_to_vec() {
         let v = Vec3d::new(1.0, 2.0, 3.0);
@@ -458,6 +784,27 @@ mod tests {
     fn test_is_unit() {
         let v = Vec3d::new(1.0, 2.0, 3.0);
         assert_eq!(v.is_unit(), false);
+        assert!(!Vec3d::new(1.1, 0.0, 0.0).is_unit());
+        for i in 1..200 {
+            let t = f64::from(i);
+            let v = Vec3d::new(t.sin() * 7.0, (t * 1.3).cos() * 0.01, t.sqrt());
+            assert!(v.normalize().is_unit());
+        }
+    }
+
+    #[test]
+    fn test_is_unit_with_tolerance() {
+        let v = Vec3d::new(1.0 + 1e-6, 0.0, 0.0);
+        assert!(!v.is_unit());
+        assert!(v.is_unit_with_tolerance(2e-6));
+        assert!(!v.is_unit_with_tolerance(5e-7));
+    }
+
+    #[test]
+    fn test_is_finite() {
+        assert!(Vec3d::new(1.0, 2.0, 3.0).is_finite());
+        assert!(!Vec3d::new(1.0, f64::NAN, 3.0).is_finite());
+        assert!(!Vec3d::new(1.0, 2.0, f64::NEG_INFINITY).is_finite());
     }
 
     #[test]
@@ -469,11 +816,17 @@ mod tests {
         assert_eq!(n.z, 0.8017837257372732);
     }
 
+    #[test]
+    fn test_try_normalize() {
+        assert_eq!(Vec3d::zero().try_normalize(), Err(Error::ZeroVector));
+        assert_eq!(Vec3d::new(0.0, 2.0, 0.0).try_normalize(), Ok(Vec3d::j()));
+    }
+
     #[test]
     fn test_angle_to() {
         let v1 = Vec3d::k();
         let v2 = Vec3d::i();
-        assert_eq!(v1.angle_to(&v2), std::f64::consts::FRAC_PI_2.into());
+        assert_eq!(v1.angle_to(&v2), core::f64::consts::FRAC_PI_2.into());
     }
 
     #[test]
@@ -519,6 +872,14 @@ mod tests {
         assert_eq!(v.z, 9.0);
     }
 
+    #[test]
+    fn test_sum() {
+        let points = [Vec3d::new(1.0, 2.0, 3.0), Vec3d::new(4.0, 5.0, 6.0)];
+        assert_eq!(points.iter().sum::<Vec3d>(), Vec3d::new(5.0, 7.0, 9.0));
+        assert_eq!(points.into_iter().sum::<Vec3d>(), Vec3d::new(5.0, 7.0, 9.0));
+        assert_eq!(core::iter::empty::<Vec3d>().sum::<Vec3d>(), Vec3d::zero());
+    }
+
     #[test]
     fn test_sub() {
         let v1 = Vec3d::new(1.0, 2.0, 3.0);
@@ -547,6 +908,54 @@ mod tests {
         assert_eq!(v.z, 1.5);
     }
 
+    #[test]
+    fn test_neg() {
+        let v = Vec3d::new(1.0, -2.0, 3.0);
+        assert_eq!(-v, Vec3d::new(-1.0, 2.0, -3.0));
+        assert_eq!(-&v, Vec3d::new(-1.0, 2.0, -3.0));
+    }
+
+    #[test]
+    #[allow(clippy::op_ref)]
+    fn test_reference_operators() {
+        let v1 = Vec3d::new(1.0, 2.0, 3.0);
+        let v2 = Vec3d::new(4.0, 5.0, 6.0);
+        let sum = Vec3d::new(5.0, 7.0, 9.0);
+        let difference = Vec3d::new(-3.0, -3.0, -3.0);
+        assert_eq!(v1 + v2, sum);
+        assert_eq!(&v1 + v2, sum);
+        assert_eq!(v1 + &v2, sum);
+        assert_eq!(&v1 + &v2, sum);
+        assert_eq!(v1 - v2, difference);
+        assert_eq!(&v1 - v2, difference);
+        assert_eq!(v1 - &v2, difference);
+        assert_eq!(&v1 - &v2, difference);
+    }
+
+    #[test]
+    #[allow(clippy::op_ref)]
+    fn test_scalar_operators() {
+        let v = Vec3d::new(1.0, 2.0, 3.0);
+        let doubled = Vec3d::new(2.0, 4.0, 6.0);
+        let halved = Vec3d::new(0.5, 1.0, 1.5);
+        assert_eq!(v * 2.0, doubled);
+        assert_eq!(&v * 2.0, doubled);
+        assert_eq!(2.0 * v, doubled);
+        assert_eq!(2.0 * &v, doubled);
+        assert_eq!(v / 2.0, halved);
+        assert_eq!(&v / 2.0, halved);
+    }
+
+    #[test]
+    fn test_display() {
+        let v = Vec3d::new(3.0, 0.0, 4.0);
+        assert_eq!(alloc::format!("{v}"), "(3, 0, 4)");
+        assert_eq!(alloc::format!("{v:.2}"), "(3.00, 0.00, 4.00)");
+        assert_eq!(alloc::format!("{v:#}"), "5.000 along (0.600, 0.000, 0.800)");
+        assert_eq!(alloc::format!("{v:#.1}"), "5.0 along (0.6, 0.0, 0.8)");
+        assert_eq!(alloc::format!("{:#}", Vec3d::zero()), "0.000 (zero vector)");
+    }
+
     #[test]
     fn test_index() {
         let v = Vec3d::new(1.0, 2.0, 3.0);
@@ -554,4 +963,161 @@ mod tests {
         assert_eq!(v[1], 2.0);
         assert_eq!(v[2], 3.0);
     }
+
+    #[cfg(feature = "glam")]
+    #[test]
+    fn test_glam_round_trip() {
+        let v = Vec3d::new(1.0, -2.5, 1e-20);
+        assert_eq!(Vec3d::from(glam::DVec3::from(v)), v);
+        let single: glam::Vec3 = v.into();
+        assert_eq!(single, glam::Vec3::new(1.0, -2.5, 1e-20));
+        assert_eq!(Vec3d::from(single), Vec3d::new(1.0, -2.5, f64::from(1e-20_f32)));
+    }
+
+    #[cfg(feature = "mint")]
+    #[test]
+    fn test_mint_round_trip() {
+        let v = Vec3d::new(1.0, -2.5, 3.25);
+        let point: mint::Point3<f64> = v.into();
+        assert_eq!((point.x, point.y, point.z), (1.0, -2.5, 3.25));
+        assert_eq!(Vec3d::from(point), v);
+        assert_eq!(Vec3d::from(mint::Vector3::from(v)), v);
+    }
+
+    #[cfg(feature = "bytemuck")]
+    #[test]
+    fn test_bytemuck_cast() {
+        let points = [Vec3d::new(1.0, 2.0, 3.0), Vec3d::new(-4.0, 5.5, 0.0)];
+        let bytes: &[u8] = bytemuck::cast_slice(&points);
+        assert_eq!(bytes.len(), 48);
+        assert_eq!(&bytes[..8], &1.0_f64.to_ne_bytes());
+        let floats: &[f64] = bytemuck::cast_slice(bytes);
+        assert_eq!(floats, &[1.0, 2.0, 3.0, -4.0, 5.5, 0.0]);
+        let round_trip: &[Vec3d] = bytemuck::cast_slice(floats);
+        assert_eq!(round_trip, &points);
+    }
+
+    #[test]
+    fn test_const_constructors() {
+        const ORIGIN: Vec3d = Vec3d::zero();
+        const AXES: [Vec3d; 3] = [Vec3d::i(), Vec3d::j(), Vec3d::k()];
+        const DIAGONAL: Vec3d = Vec3d::new_from_to(&Vec3d::new(1.0, 1.0, 1.0), &Vec3d::new(2.0, 3.0, 4.0));
+        assert_eq!(ORIGIN, Vec3d::new(0.0, 0.0, 0.0));
+        assert_eq!(AXES[1], Vec3d::new(0.0, 1.0, 0.0));
+        assert_eq!(DIAGONAL, Vec3d::new(1.0, 2.0, 3.0));
+    }
+
+    #[test]
+    fn test_default() {
+        assert_eq!(Vec3d::default(), Vec3d::zero());
+    }
+
+    #[test]
+    fn test_total_cmp() {
+        let a = Vec3d::new(1.0, 2.0, 3.0);
+        assert_eq!(a.total_cmp(&Vec3d::new(1.0, 2.0, 4.0)), core::cmp::Ordering::Less);
+        assert_eq!(a.total_cmp(&Vec3d::new(0.0, 5.0, 5.0)), core::cmp::Ordering::Greater);
+        assert_eq!(a.total_cmp(&a), core::cmp::Ordering::Equal);
+        assert_eq!(Vec3d::new(f64::NAN, 0.0, 0.0).total_cmp(&a), core::cmp::Ordering::Greater);
+    }
+
+    #[test]
+    fn test_ordered_deduplication() {
+        let points = [
+            Vec3d::new(1.0, 0.0, 0.0),
+            Vec3d::new(0.0, 1.0, 0.0),
+            Vec3d::new(1.0, 0.0, 0.0),
+            Vec3d::new(0.0, 0.0, f64::NAN),
+            Vec3d::new(0.0, 0.0, f64::NAN)
+        ];
+        let unique: alloc::collections::BTreeSet<OrderedVec3d> = points.iter().copied().map(OrderedVec3d).collect();
+        assert_eq!(unique.len(), 3);
+        assert!(unique.first().unwrap().0.z.is_nan());
+        assert_eq!(unique.last().unwrap().0, Vec3d::new(1.0, 0.0, 0.0));
+        #[cfg(feature = "std")]
+        {
+            let hashed: std::collections::HashSet<OrderedVec3d> = points.iter().copied().map(OrderedVec3d).collect();
+            assert_eq!(hashed.len(), 3);
+        }
+    }
+
+    #[test]
+    fn test_rotated_about() {
+        let v = Vec3d::i().rotated_about(&Vec3d::new(0.0, 0.0, 2.0), core::f64::consts::FRAC_PI_2);
+        assert!((v - Vec3d::j()).magnitude() < 1e-15);
+        assert_eq!(Vec3d::i().rotated_about(&Vec3d::zero(), 1.0), Vec3d::i());
+        crate::testing::for_all(500, |(axis, angle, v): (Vec3d, AngleRadians, Vec3d)| {
+            let expected = Quat::from_axis_angle(&axis.normalize(), angle).rotate(&v);
+            assert!((v.rotated_about(&axis, angle) - expected).magnitude() < 1e-9);
+        });
+    }
+
+    #[test]
+    fn test_rotate_about_point() {
+        let pivot = Vec3d::new(1.0, 1.0, 0.0);
+        let rotated = Vec3d::new(2.0, 1.0, 5.0).rotate_about_point(&pivot, &Vec3d::k(), core::f64::consts::PI);
+        assert!((rotated - Vec3d::new(0.0, 1.0, 5.0)).magnitude() < 1e-12);
+        assert!((pivot.rotate_about_point(&pivot, &Vec3d::i(), 1.0) - pivot).magnitude() < 1e-15);
+    }
+
+    #[test]
+    fn test_any_perpendicular() {
+        for v in [Vec3d::i(), Vec3d::j(), -Vec3d::k(), Vec3d::new(0.9, -0.1, 0.2).normalize()] {
+            let perpendicular = v.any_perpendicular();
+            assert!(perpendicular.dot(&v).abs() < 1e-12);
+            assert!((perpendicular.magnitude() - 1.0).abs() < 1e-12);
+        }
+        assert_eq!(Vec3d::k().any_perpendicular(), Vec3d::i());
+    }
+
+    #[test]
+    fn test_constrained_to_cone() {
+        let half_angle = AngleRadians::quarter_pi();
+        let inside = Vec3d::new(0.2, 0.1, 1.0);
+        assert_eq!(inside.constrained_to_cone(&Vec3d::k(), half_angle), inside);
+        let outside = Vec3d::new(2.0, 0.0, 0.0);
+        let constrained = outside.constrained_to_cone(&Vec3d::new(0.0, 0.0, 3.0), half_angle);
+        assert!((constrained - Vec3d::new(2.0_f64.sqrt(), 0.0, 2.0_f64.sqrt())).magnitude() < 1e-12);
+        let behind = (-Vec3d::k()).constrained_to_cone(&Vec3d::k(), half_angle);
+        assert!((f64::from(behind.angle_to(&Vec3d::k())) - core::f64::consts::FRAC_PI_4).abs() < 1e-12);
+        assert!((behind.magnitude() - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_from_str_round_trip() {
+        crate::testing::for_all(100, |v: Vec3d| {
+            assert_eq!(v.to_string().parse::<Vec3d>(), Ok(v));
+        });
+        assert_eq!(" ( 1 ,2,  -3.5 ) ".parse::<Vec3d>(), Ok(Vec3d::new(1.0, 2.0, -3.5)));
+        assert!("(1, 2)".parse::<Vec3d>().is_err());
+    }
+
+    #[test]
+    fn test_rotate_towards() {
+        let target = Vec3d::new(0.0, 3.0, 0.0);
+        let mut v = Vec3d::new(2.0, 0.0, 0.0);
+        let step = core::f64::consts::FRAC_PI_2 / 4.5;
+        let mut steps = 0;
+        while v != Vec3d::new(0.0, 2.0, 0.0) {
+            v = v.rotate_towards(&target, step);
+            assert!((v.magnitude() - 2.0).abs() < 1e-12);
+            steps += 1;
+            assert!(steps <= 5);
+        }
+        assert_eq!(steps, 5);
+        let turned = Vec3d::i().rotate_towards(&Vec3d::j(), 0.2);
+        assert!((f64::from(turned.angle_to(&Vec3d::i())) - 0.2).abs() < 1e-12);
+        assert!(turned.z.abs() < 1e-15);
+    }
+
+    #[test]
+    fn test_rotate_towards_antiparallel() {
+        let v = Vec3d::new(0.0, 0.0, 2.0);
+        let turned = v.rotate_towards(&-v, 0.5);
+        assert!(turned.is_finite());
+        assert!((turned.magnitude() - 2.0).abs() < 1e-12);
+        assert!((f64::from(turned.angle_to(&v)) - 0.5).abs() < 1e-12);
+        assert_eq!(turned, v.rotate_towards(&-v, 0.5));
+        assert_eq!(v.rotate_towards(&Vec3d::zero(), 0.5), v);
+    }
 }
@@ -0,0 +1,189 @@
+//! Operations on slices of Vec3d
+//! with the `simd` feature the slices are processed in fixed width lanes laid out so the compiler can vectorize them,
+//! otherwise they fall back to a plain loop over the single value methods
+
+#[cfg(all(feature = "simd", not(any(feature = "std", test))))]
+use crate::math::Float;
+use crate::vec3d::Vec3d;
+
+/// The number of vectors processed together with the `simd` feature
+#[cfg(feature = "simd")]
+pub(crate) const LANES: usize = 4;
+
+/// A lane of vectors split into one array per component
+#[cfg(feature = "simd")]
+pub(crate) struct Lanes {
+    pub(crate) x: [f64; LANES],
+    pub(crate) y: [f64; LANES],
+    pub(crate) z: [f64; LANES]
+}
+
+#[cfg(feature = "simd")]
+impl Lanes {
+    /// Split a chunk of exactly `LANES` vectors into its components
+    pub(crate) fn load(chunk: &[Vec3d]) -> Lanes {
+        Lanes {
+            x: core::array::from_fn(|lane| chunk[lane].x),
+            y: core::array::from_fn(|lane| chunk[lane].y),
+            z: core::array::from_fn(|lane| chunk[lane].z)
+        }
+    }
+
+    /// Write the lanes back into a chunk of exactly `LANES` vectors
+    pub(crate) fn store(&self, chunk: &mut [Vec3d]) {
+        for (lane, v) in chunk.iter_mut().enumerate() {
+            *v = Vec3d::new(self.x[lane], self.y[lane], self.z[lane]);
+        }
+    }
+}
+
+/// Calculate the dot product of each pair of vectors in two slices
+///
+/// # Panics
+/// Panics if the slices are not all the same length
+pub fn dot_many(a: &[Vec3d], b: &[Vec3d], out: &mut [f64]) {
+    assert_eq!(a.len(), b.len(), "slices must be the same length");
+    assert_eq!(a.len(), out.len(), "slices must be the same length");
+    #[cfg(feature = "simd")]
+    {
+        let mut a_chunks = a.chunks_exact(LANES);
+        let mut b_chunks = b.chunks_exact(LANES);
+        let mut out_chunks = out.chunks_exact_mut(LANES);
+        for ((a, b), out) in (&mut a_chunks).zip(&mut b_chunks).zip(&mut out_chunks) {
+            let a = Lanes::load(a);
+            let b = Lanes::load(b);
+            for (lane, dot) in out.iter_mut().enumerate() {
+                *dot = a.x[lane] * b.x[lane] + a.y[lane] * b.y[lane] + a.z[lane] * b.z[lane];
+            }
+        }
+        dot_scalar(a_chunks.remainder(), b_chunks.remainder(), out_chunks.into_remainder());
+    }
+    #[cfg(not(feature = "simd"))]
+    dot_scalar(a, b, out);
+}
+
+/// Add each vector in a slice to the matching vector in another slice in place
+///
+/// # Panics
+/// Panics if the slices are not the same length
+pub fn add_assign_many(a: &mut [Vec3d], b: &[Vec3d]) {
+    assert_eq!(a.len(), b.len(), "slices must be the same length");
+    #[cfg(feature = "simd")]
+    {
+        let mut a_chunks = a.chunks_exact_mut(LANES);
+        let mut b_chunks = b.chunks_exact(LANES);
+        for (a, b) in (&mut a_chunks).zip(&mut b_chunks) {
+            let mut sum = Lanes::load(a);
+            let b = Lanes::load(b);
+            for lane in 0..LANES {
+                sum.x[lane] += b.x[lane];
+                sum.y[lane] += b.y[lane];
+                sum.z[lane] += b.z[lane];
+            }
+            sum.store(a);
+        }
+        add_assign_scalar(a_chunks.into_remainder(), b_chunks.remainder());
+    }
+    #[cfg(not(feature = "simd"))]
+    add_assign_scalar(a, b);
+}
+
+/// Normalize every vector in a slice in place
+pub fn normalize_many(points: &mut [Vec3d]) {
+    #[cfg(feature = "simd")]
+    {
+        let mut chunks = points.chunks_exact_mut(LANES);
+        for chunk in &mut chunks {
+            let mut v = Lanes::load(chunk);
+            for lane in 0..LANES {
+                let magnitude = (v.x[lane] * v.x[lane] + v.y[lane] * v.y[lane] + v.z[lane] * v.z[lane]).sqrt();
+                v.x[lane] /= magnitude;
+                v.y[lane] /= magnitude;
+                v.z[lane] /= magnitude;
+            }
+            v.store(chunk);
+        }
+        normalize_scalar(chunks.into_remainder());
+    }
+    #[cfg(not(feature = "simd"))]
+    normalize_scalar(points);
+}
+
+fn dot_scalar(a: &[Vec3d], b: &[Vec3d], out: &mut [f64]) {
+    for ((a, b), out) in a.iter().zip(b).zip(out) {
+        *out = a.dot(b);
+    }
+}
+
+fn add_assign_scalar(a: &mut [Vec3d], b: &[Vec3d]) {
+    for (a, b) in a.iter_mut().zip(b) {
+        *a = *a + b;
+    }
+}
+
+fn normalize_scalar(points: &mut [Vec3d]) {
+    for v in points {
+        *v = v.normalize();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+    use alloc::vec::Vec;
+
+    use super::*;
+
+    /// A small deterministic generator so the tests do not need a random number crate
+    fn pseudo_random_points(count: usize, seed: u64) -> Vec<Vec3d> {
+        let mut state = seed;
+        let mut next = || {
+            state = state.wrapping_mul(6_364_136_223_846_793_005).wrapping_add(1_442_695_040_888_963_407);
+            f64::from(u32::try_from(state >> 32).unwrap()) / f64::from(u32::MAX) * 20.0 - 10.0
+        };
+        (0..count).map(|_| Vec3d::new(next(), next(), next())).collect()
+    }
+
+    #[test]
+    fn test_dot_many() {
+        for count in [0, 1, 3, 4, 5, 17] {
+            let a = pseudo_random_points(count, 1);
+            let b = pseudo_random_points(count, 2);
+            let mut out = vec![0.0; count];
+            dot_many(&a, &b, &mut out);
+            for ((a, b), dot) in a.iter().zip(&b).zip(&out) {
+                assert!((a.dot(b) - dot).abs() < 1e-12);
+            }
+        }
+    }
+
+    #[test]
+    fn test_add_assign_many() {
+        for count in [0, 2, 4, 9] {
+            let mut a = pseudo_random_points(count, 3);
+            let b = pseudo_random_points(count, 4);
+            let expected: Vec<Vec3d> = a.iter().zip(&b).map(|(a, b)| a + b).collect();
+            add_assign_many(&mut a, &b);
+            assert_eq!(a, expected);
+        }
+    }
+
+    #[test]
+    fn test_normalize_many() {
+        for count in [1, 4, 7, 13] {
+            let mut points = pseudo_random_points(count, 5);
+            let expected: Vec<Vec3d> = points.iter().map(Vec3d::normalize).collect();
+            normalize_many(&mut points);
+            for (point, expected) in points.iter().zip(&expected) {
+                assert!(point.distance_to(expected) < 1e-12);
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "slices must be the same length")]
+    fn test_length_mismatch() {
+        let mut out = [0.0; 2];
+        dot_many(&[Vec3d::i(), Vec3d::j()], &[Vec3d::k()], &mut out);
+    }
+}